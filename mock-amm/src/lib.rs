@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{mint_to, transfer, Mint, MintTo, Token, TokenAccount, Transfer},
+};
+
+declare_id!("MockAmm111111111111111111111111111111111111");
+
+/// Stand-in for a real DEX so pool creation can be exercised locally. It takes
+/// the liquidity into its vaults and mints LP tokens for it, nothing more.
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    pub fn create_pool(
+        ctx: Context<CreatePool>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Result<()> {
+        require!(token_a_amount > 0 && token_b_amount > 0, MockAmmError::EmptyLiquidity);
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_token_a.to_account_info(),
+                    to: ctx.accounts.token_a_vault.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            token_a_amount,
+        )?;
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_token_b.to_account_info(),
+                    to: ctx.accounts.token_b_vault.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            token_b_amount,
+        )?;
+
+        // a real pool would mint sqrt(a * b), any non-zero amount does here
+        let lp_amount = token_a_amount.min(token_b_amount);
+
+        let pool_key = ctx.accounts.pool.key();
+        let seeds = &[b"lp".as_ref(), pool_key.as_ref(), &[ctx.bumps.lp_mint]];
+        let signer = [&seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.creator_lp_token.to_account_info(),
+                    authority: ctx.accounts.lp_mint.to_account_info(),
+                },
+                &signer,
+            ),
+            lp_amount,
+        )?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.token_a_mint = ctx.accounts.token_a_mint.key();
+        pool.token_b_mint = ctx.accounts.token_b_mint.key();
+        pool.lp_mint = ctx.accounts.lp_mint.key();
+        pool.token_a_amount = token_a_amount;
+        pool.token_b_amount = token_b_amount;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreatePool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_a_mint: Box<Account<'info, Mint>>,
+
+    pub token_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool", token_a_mint.key().as_ref(), token_b_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = 9,
+        mint::authority = lp_mint,
+        seeds = [b"lp", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = token_a_mint, token::authority = creator)]
+    pub creator_token_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = token_b_mint, token::authority = creator)]
+    pub creator_token_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = lp_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_lp_token: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_a_mint,
+        token::authority = pool,
+        seeds = [b"vault", pool.key().as_ref(), token_a_mint.key().as_ref()],
+        bump
+    )]
+    pub token_a_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_b_mint,
+        token::authority = pool,
+        seeds = [b"vault", pool.key().as_ref(), token_b_mint.key().as_ref()],
+        bump
+    )]
+    pub token_b_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("Both sides of the pool need liquidity.")]
+    EmptyLiquidity,
+}
@@ -1,4 +1,5 @@
 pub const WSOL_MINT_ADDRESS: &str = "So11111111111111111111111111111111111111112";
+pub const DEFAULT_MINT_SUFFIX: &str = "meme";
 pub const CREATE_RAYDIUM_POOL_FEE: u64 = 150_000_000; //  mainnet 0.15 SOL , dev 1sol
 
 pub const CREATOR_GAIN_SOL_FEE: u64 = 1_000_000_000;  // 1 sol
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::dex::{DexAdapter, PoolLiquidity};
+use crate::errors::ErrorCode;
+
+/// Local mock AMM, only compiled with the `mock-dex` feature for tests.
+///
+/// Remaining accounts: mock_amm_program, pool, lp_mint, creator_lp_token,
+/// token_a_vault, token_b_vault, associated_token_program, system_program, rent.
+pub struct MockAmm<'a, 'info> {
+    accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> MockAmm<'a, 'info> {
+    pub const ACCOUNTS_LEN: usize = 9;
+
+    pub fn load(remaining_accounts: &'a [AccountInfo<'info>]) -> Result<Self> {
+        require!(remaining_accounts.len() >= Self::ACCOUNTS_LEN, ErrorCode::InvalidAccount);
+        require_keys_eq!(remaining_accounts[0].key(), ::mock_amm::ID, ErrorCode::UnsupportedDex);
+
+        Ok(Self { accounts: &remaining_accounts[..Self::ACCOUNTS_LEN] })
+    }
+}
+
+impl<'a, 'info> DexAdapter<'info> for MockAmm<'a, 'info> {
+    fn create_pool(&self, liquidity: &PoolLiquidity<'info>) -> Result<()> {
+        let [program, pool, lp_mint, creator_lp_token, token_a_vault, token_b_vault, associated_token_program, system_program, rent] =
+            self.accounts
+        else {
+            return err!(ErrorCode::InvalidAccount);
+        };
+
+        ::mock_amm::cpi::create_pool(
+            CpiContext::new(
+                program.clone(),
+                ::mock_amm::cpi::accounts::CreatePool {
+                    creator: liquidity.creator.clone(),
+                    token_a_mint: liquidity.mint.clone(),
+                    token_b_mint: liquidity.wrapped_sol_mint.clone(),
+                    pool: pool.clone(),
+                    lp_mint: lp_mint.clone(),
+                    creator_token_a: liquidity.creator_token.clone(),
+                    creator_token_b: liquidity.creator_wsol.clone(),
+                    creator_lp_token: creator_lp_token.clone(),
+                    token_a_vault: token_a_vault.clone(),
+                    token_b_vault: token_b_vault.clone(),
+                    token_program: liquidity.token_program.clone(),
+                    associated_token_program: associated_token_program.clone(),
+                    system_program: system_program.clone(),
+                    rent: rent.clone(),
+                },
+            ),
+            liquidity.token_amount,
+            liquidity.wsol_amount,
+        )
+    }
+
+    fn lp_mint(&self) -> &AccountInfo<'info> {
+        &self.accounts[2]
+    }
+
+    fn creator_lp_token(&self) -> &AccountInfo<'info> {
+        &self.accounts[3]
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+pub mod raydium_cp_swap;
+#[cfg(feature = "mock-dex")]
+pub mod mock_amm;
+
+pub use raydium_cp_swap::RaydiumCpSwap;
+#[cfg(feature = "mock-dex")]
+pub use mock_amm::MockAmm;
+
+/// Liquidity a launched memecoin seeds its pool with. `creator` signs for the
+/// DEX and holds both sides in `creator_token` and `creator_wsol` for the
+/// duration of the call.
+pub struct PoolLiquidity<'info> {
+    pub creator: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub wrapped_sol_mint: AccountInfo<'info>,
+    pub creator_token: AccountInfo<'info>,
+    pub creator_wsol: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_amount: u64,
+    pub wsol_amount: u64,
+}
+
+/// A DEX the presale can create its pool on. Each adapter reads the accounts
+/// its DEX needs from the instruction's remaining accounts, the first one
+/// being the DEX program.
+pub trait DexAdapter<'info> {
+    fn create_pool(&self, liquidity: &PoolLiquidity<'info>) -> Result<()>;
+
+    /// LP mint of the created pool.
+    fn lp_mint(&self) -> &AccountInfo<'info>;
+
+    /// Account the DEX mints the creator's LP tokens into.
+    fn creator_lp_token(&self) -> &AccountInfo<'info>;
+}
+
+/// Picks the adapter for the DEX program passed first in `remaining_accounts`.
+pub fn load_dex_adapter<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<Box<dyn DexAdapter<'info> + 'a>> {
+    let dex_program = remaining_accounts.first().ok_or(ErrorCode::UnsupportedDex)?;
+
+    if dex_program.key() == raydium_cp_swap::ID {
+        return Ok(Box::new(RaydiumCpSwap::load(remaining_accounts)?));
+    }
+
+    #[cfg(feature = "mock-dex")]
+    if dex_program.key() == ::mock_amm::ID {
+        return Ok(Box::new(MockAmm::load(remaining_accounts)?));
+    }
+
+    err!(ErrorCode::UnsupportedDex)
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{AccountMeta, Instruction}, program::invoke};
+use crate::dex::{DexAdapter, PoolLiquidity};
+use crate::errors::ErrorCode;
+
+#[cfg(not(feature = "devnet"))]
+declare_id!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+#[cfg(feature = "devnet")]
+declare_id!("CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW");
+
+/// Anchor discriminator of the cp-swap `initialize` instruction.
+const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+
+/// Raydium CP-swap. The instruction is built by hand so the program doesn't
+/// need the raydium-cp-swap crate.
+///
+/// Remaining accounts: cp_swap_program, amm_config, authority, pool_state,
+/// lp_mint, creator_lp_token, token_0_vault, token_1_vault, create_pool_fee,
+/// observation_state, associated_token_program, system_program, rent.
+pub struct RaydiumCpSwap<'a, 'info> {
+    accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> RaydiumCpSwap<'a, 'info> {
+    pub const ACCOUNTS_LEN: usize = 13;
+
+    pub fn load(remaining_accounts: &'a [AccountInfo<'info>]) -> Result<Self> {
+        require!(remaining_accounts.len() >= Self::ACCOUNTS_LEN, ErrorCode::InvalidAccount);
+        require_keys_eq!(remaining_accounts[0].key(), ID, ErrorCode::UnsupportedDex);
+
+        Ok(Self { accounts: &remaining_accounts[..Self::ACCOUNTS_LEN] })
+    }
+}
+
+impl<'a, 'info> DexAdapter<'info> for RaydiumCpSwap<'a, 'info> {
+    fn create_pool(&self, liquidity: &PoolLiquidity<'info>) -> Result<()> {
+        let [program, amm_config, authority, pool_state, lp_mint, creator_lp_token, token_0_vault, token_1_vault, create_pool_fee, observation_state, associated_token_program, system_program, rent] =
+            self.accounts
+        else {
+            return err!(ErrorCode::InvalidAccount);
+        };
+
+        // cp-swap wants the mints ordered by key
+        let memecoin_side = (&liquidity.mint, &liquidity.creator_token, liquidity.token_amount);
+        let wsol_side = (&liquidity.wrapped_sol_mint, &liquidity.creator_wsol, liquidity.wsol_amount);
+        let ((token_0_mint, creator_token_0, init_amount_0), (token_1_mint, creator_token_1, init_amount_1)) =
+            if liquidity.mint.key() < liquidity.wrapped_sol_mint.key() {
+                (memecoin_side, wsol_side)
+            } else {
+                (wsol_side, memecoin_side)
+            };
+
+        let open_time = Clock::get()?.unix_timestamp as u64;
+        let mut data = INITIALIZE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&init_amount_0.to_le_bytes());
+        data.extend_from_slice(&init_amount_1.to_le_bytes());
+        data.extend_from_slice(&open_time.to_le_bytes());
+
+        let account_infos = [
+            liquidity.creator.clone(),
+            amm_config.clone(),
+            authority.clone(),
+            pool_state.clone(),
+            token_0_mint.clone(),
+            token_1_mint.clone(),
+            lp_mint.clone(),
+            creator_token_0.clone(),
+            creator_token_1.clone(),
+            creator_lp_token.clone(),
+            token_0_vault.clone(),
+            token_1_vault.clone(),
+            create_pool_fee.clone(),
+            observation_state.clone(),
+            liquidity.token_program.clone(),
+            liquidity.token_program.clone(),
+            liquidity.token_program.clone(),
+            associated_token_program.clone(),
+            system_program.clone(),
+            rent.clone(),
+        ];
+
+        let instruction = Instruction {
+            program_id: program.key(),
+            accounts: vec![
+                AccountMeta::new(liquidity.creator.key(), true),
+                AccountMeta::new_readonly(amm_config.key(), false),
+                AccountMeta::new_readonly(authority.key(), false),
+                AccountMeta::new(pool_state.key(), false),
+                AccountMeta::new_readonly(token_0_mint.key(), false),
+                AccountMeta::new_readonly(token_1_mint.key(), false),
+                AccountMeta::new(lp_mint.key(), false),
+                AccountMeta::new(creator_token_0.key(), false),
+                AccountMeta::new(creator_token_1.key(), false),
+                AccountMeta::new(creator_lp_token.key(), false),
+                AccountMeta::new(token_0_vault.key(), false),
+                AccountMeta::new(token_1_vault.key(), false),
+                AccountMeta::new(create_pool_fee.key(), false),
+                AccountMeta::new(observation_state.key(), false),
+                AccountMeta::new_readonly(liquidity.token_program.key(), false),
+                AccountMeta::new_readonly(liquidity.token_program.key(), false),
+                AccountMeta::new_readonly(liquidity.token_program.key(), false),
+                AccountMeta::new_readonly(associated_token_program.key(), false),
+                AccountMeta::new_readonly(system_program.key(), false),
+                AccountMeta::new_readonly(rent.key(), false),
+            ],
+            data,
+        };
+
+        invoke(&instruction, &account_infos)?;
+
+        Ok(())
+    }
+
+    fn lp_mint(&self) -> &AccountInfo<'info> {
+        &self.accounts[4]
+    }
+
+    fn creator_lp_token(&self) -> &AccountInfo<'info> {
+        &self.accounts[5]
+    }
+}
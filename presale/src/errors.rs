@@ -30,5 +30,11 @@ pub enum ErrorCode {
     #[msg("Invalid account.")]
     InvalidAccount, // 0x177c
     #[msg("Invalid mint address.")]
-    InvalidMintAddress,
+    InvalidMintAddress, // 0x177d
+    #[msg("Unsupported DEX program.")]
+    UnsupportedDex, // 0x177e
+    #[msg("Nothing to seed the pool with.")]
+    EmptyPoolLiquidity, // 0x177f
+    #[msg("Mint suffix must be 1 to 9 base58 characters.")]
+    InvalidMintSuffix, // 0x1780
 }
\ No newline at end of file
@@ -99,6 +99,10 @@ pub fn handler(
     let tier = match funding_raise_tier {
         0 => FundingRaiseTier::TwentySol,
         1 => FundingRaiseTier::FiftySol,
+        2 => FundingRaiseTier::OneHundredSol,
+        3 => FundingRaiseTier::FiveHundredSol,
+        4 => FundingRaiseTier::OneThousandSol,
+        _ => return err!(ErrorCode::InvalidFundingRaiseTier),
     };
     memecoin_config.create_memecoin_config(
         creator,
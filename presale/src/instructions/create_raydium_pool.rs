@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Burn, Mint, Token, TokenAccount, Transfer},
+};
+use crate::constants::{CREATE_RAYDIUM_POOL_FEE, WSOL_MINT_ADDRESS};
+use crate::dex::{load_dex_adapter, PoolLiquidity};
+use crate::errors::ErrorCode;
+use std::str::FromStr;
+
+#[derive(Accounts)]
+pub struct CreateRaydiumPool<'info> {
+    #[account(
+    mut,
+    seeds = [memecoin_config.creator.key().as_ref(), & memecoin_config.creator_memecoin_index.to_le_bytes()],
+    bump
+    )]
+    pub memecoin_config: Account<'info, MemecoinConfig>,
+
+    #[account(
+    mut,
+    address = memecoin_config.creator
+    )]
+    pub creator: Signer<'info>,
+
+    #[account(
+    address = memecoin_config.mint.key()
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK：checked in the handler
+    pub wrapped_sol_mint: Account<'info, Mint>,
+
+    #[account(
+    mut,
+    token::mint = mint,
+    token::authority = memecoin_config,
+    seeds = [b"MEME_COIN", mint.key().as_ref(), memecoin_config.key().as_ref()],
+    bump
+    )]
+    pub memecoin_config_token: Account<'info, TokenAccount>,
+
+    #[account(
+    mut,
+    token::mint = wrapped_sol_mint,
+    token::authority = memecoin_config,
+    seeds = [b"WSOL", memecoin_config.key().as_ref()],
+    bump
+    )]
+    pub memecoin_config_wrapped_sol_account: Account<'info, TokenAccount>,
+
+    #[account(
+    init_if_needed,
+    payer = creator,
+    associated_token::mint = mint,
+    associated_token::authority = creator
+    )]
+    pub creator_token: Account<'info, TokenAccount>,
+
+    #[account(
+    init_if_needed,
+    payer = creator,
+    associated_token::mint = wrapped_sol_mint,
+    associated_token::authority = creator
+    )]
+    pub creator_wrapped_sol_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub memecoin_config: Pubkey,
+    pub mint: Pubkey,
+    pub dex_program: Pubkey,
+    pub token_amount: u64,
+    pub wsol_amount: u64,
+}
+
+/// Accounts shared by the pool creation instructions. `payer` is whoever
+/// signed for the pool and briefly holds the liquidity.
+pub struct PoolSeeding<'a, 'info> {
+    pub memecoin_config: &'a mut Account<'info, MemecoinConfig>,
+    pub memecoin_config_bump: u8,
+    pub payer: &'a Signer<'info>,
+    pub mint: &'a Account<'info, Mint>,
+    pub wrapped_sol_mint: &'a Account<'info, Mint>,
+    pub memecoin_config_token: &'a Account<'info, TokenAccount>,
+    pub memecoin_config_wrapped_sol_account: &'a Account<'info, TokenAccount>,
+    pub payer_token: &'a Account<'info, TokenAccount>,
+    pub payer_wrapped_sol_account: &'a Account<'info, TokenAccount>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateRaydiumPool<'info>>,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    seed_pool(
+        PoolSeeding {
+            memecoin_config: &mut accounts.memecoin_config,
+            memecoin_config_bump: ctx.bumps.memecoin_config,
+            payer: &accounts.creator,
+            mint: &accounts.mint,
+            wrapped_sol_mint: &accounts.wrapped_sol_mint,
+            memecoin_config_token: &accounts.memecoin_config_token,
+            memecoin_config_wrapped_sol_account: &accounts.memecoin_config_wrapped_sol_account,
+            payer_token: &accounts.creator_token,
+            payer_wrapped_sol_account: &accounts.creator_wrapped_sol_account,
+            token_program: &accounts.token_program,
+        },
+        ctx.remaining_accounts,
+    )
+}
+
+/// Creates the pool on the DEX passed in `remaining_accounts` from the
+/// wrapped SOL and the unsold memecoin, then burns the LP tokens it mints.
+pub fn seed_pool<'info>(
+    accounts: PoolSeeding<'_, 'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    require!(accounts.memecoin_config.status == LaunchStatus::Succeed, ErrorCode::OnlyCreatePoolWhenLaunchSuccess);
+
+    let wsol_mint_pubkey = Pubkey::from_str(WSOL_MINT_ADDRESS).unwrap();
+    require_keys_eq!(accounts.wrapped_sol_mint.key(), wsol_mint_pubkey, ErrorCode::WrongWSOLMint);
+
+    let token_amount = accounts.memecoin_config_token.amount;
+    let wsol_amount = accounts.memecoin_config_wrapped_sol_account.amount;
+    require!(token_amount > 0 && wsol_amount > 0, ErrorCode::EmptyPoolLiquidity);
+
+    let dex = load_dex_adapter(remaining_accounts)?;
+
+    let seeds = &[
+        accounts.memecoin_config.creator.as_ref(),
+        &accounts.memecoin_config.creator_memecoin_index.to_le_bytes(),
+        &[accounts.memecoin_config_bump]
+    ];
+    let signer = [&seeds[..]];
+
+    // Hand the liquidity to the payer, who provides it to the DEX
+    token::transfer(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.memecoin_config_token.to_account_info(),
+                to: accounts.payer_token.to_account_info(),
+                authority: accounts.memecoin_config.to_account_info(),
+            },
+            &signer,
+        ),
+        token_amount,
+    )?;
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.memecoin_config_wrapped_sol_account.to_account_info(),
+                to: accounts.payer_wrapped_sol_account.to_account_info(),
+                authority: accounts.memecoin_config.to_account_info(),
+            },
+            &signer,
+        ),
+        wsol_amount,
+    )?;
+
+    dex.create_pool(&PoolLiquidity {
+        creator: accounts.payer.to_account_info(),
+        mint: accounts.mint.to_account_info(),
+        wrapped_sol_mint: accounts.wrapped_sol_mint.to_account_info(),
+        creator_token: accounts.payer_token.to_account_info(),
+        creator_wsol: accounts.payer_wrapped_sol_account.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        token_amount,
+        wsol_amount,
+    })?;
+
+    // Burn the LP so the liquidity stays in the pool
+    let lp_amount = token::accessor::amount(dex.creator_lp_token())?;
+    token::burn(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: dex.lp_mint().clone(),
+                from: dex.creator_lp_token().clone(),
+                authority: accounts.payer.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    // The DEX charged its pool creation fee to the payer. Moving lamports
+    // directly before the CPI would unbalance it, as memecoin_config isn't
+    // one of its accounts, so the payer is paid back afterwards.
+    accounts.memecoin_config.sub_lamports(CREATE_RAYDIUM_POOL_FEE)?;
+    accounts.payer.add_lamports(CREATE_RAYDIUM_POOL_FEE)?;
+
+    accounts.memecoin_config.set_memecoin_status(LaunchStatus::PoolCreated)?;
+
+    emit!(PoolCreated {
+        memecoin_config: accounts.memecoin_config.key(),
+        mint: accounts.mint.key(),
+        dex_program: remaining_accounts[0].key(),
+        token_amount,
+        wsol_amount,
+    });
+
+    Ok(())
+}
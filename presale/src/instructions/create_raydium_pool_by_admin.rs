@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+use crate::instructions::create_raydium_pool::{seed_pool, PoolSeeding};
+
+#[derive(Accounts)]
+pub struct CreateRaydiumPoolByAdmin<'info> {
+    #[account(
+    mut,
+    seeds = [memecoin_config.creator.key().as_ref(), & memecoin_config.creator_memecoin_index.to_le_bytes()],
+    bump
+    )]
+    pub memecoin_config: Account<'info, MemecoinConfig>,
+
+    #[account(
+    seeds = [b"CONFIG"],
+    bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+    mut,
+    address = global_config.admin
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+    address = memecoin_config.mint.key()
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK：checked in the handler
+    pub wrapped_sol_mint: Account<'info, Mint>,
+
+    #[account(
+    mut,
+    token::mint = mint,
+    token::authority = memecoin_config,
+    seeds = [b"MEME_COIN", mint.key().as_ref(), memecoin_config.key().as_ref()],
+    bump
+    )]
+    pub memecoin_config_token: Account<'info, TokenAccount>,
+
+    #[account(
+    mut,
+    token::mint = wrapped_sol_mint,
+    token::authority = memecoin_config,
+    seeds = [b"WSOL", memecoin_config.key().as_ref()],
+    bump
+    )]
+    pub memecoin_config_wrapped_sol_account: Account<'info, TokenAccount>,
+
+    #[account(
+    init_if_needed,
+    payer = admin,
+    associated_token::mint = mint,
+    associated_token::authority = admin
+    )]
+    pub admin_token: Account<'info, TokenAccount>,
+
+    #[account(
+    init_if_needed,
+    payer = admin,
+    associated_token::mint = wrapped_sol_mint,
+    associated_token::authority = admin
+    )]
+    pub admin_wrapped_sol_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateRaydiumPoolByAdmin<'info>>,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    seed_pool(
+        PoolSeeding {
+            memecoin_config: &mut accounts.memecoin_config,
+            memecoin_config_bump: ctx.bumps.memecoin_config,
+            payer: &accounts.admin,
+            mint: &accounts.mint,
+            wrapped_sol_mint: &accounts.wrapped_sol_mint,
+            memecoin_config_token: &accounts.memecoin_config_token,
+            memecoin_config_wrapped_sol_account: &accounts.memecoin_config_wrapped_sol_account,
+            payer_token: &accounts.admin_token,
+            payer_wrapped_sol_account: &accounts.admin_wrapped_sol_account,
+            token_program: &accounts.token_program,
+        },
+        ctx.remaining_accounts,
+    )
+}
@@ -0,0 +1,76 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+    Discriminator,
+};
+use crate::errors::ErrorCode;
+use crate::state::GlobalConfig;
+
+#[derive(Accounts)]
+pub struct MigrateGlobalConfig<'info> {
+    /// CHECK: may still have the smaller layout of an older version, checked in the handler
+    #[account(
+        mut,
+        seeds = [b"CONFIG"],
+        bump,
+        owner = crate::ID,
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a `GlobalConfig` created before the mint suffix policy existed. The
+/// policy gets the same case-insensitive "meme" default as `initialize`, so
+/// the suffix stays enforced until the admin sets another one.
+pub fn handler(
+    ctx: Context<MigrateGlobalConfig>,
+) -> Result<()> {
+    let global_config = ctx.accounts.global_config.to_account_info();
+
+    {
+        let data = global_config.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + 32 && data[..8] == GlobalConfig::DISCRIMINATOR,
+            ErrorCode::InvalidAccount
+        );
+        // the admin is the first field in every layout
+        require!(
+            data[8..40] == ctx.accounts.admin.key().to_bytes(),
+            anchor_lang::error::ErrorCode::ConstraintHasOne
+        );
+    }
+
+    let old_len = global_config.data_len();
+    if old_len >= GlobalConfig::LEN {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?
+        .minimum_balance(GlobalConfig::LEN)
+        .saturating_sub(global_config.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: global_config.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+
+    global_config.realloc(GlobalConfig::LEN, true)?;
+
+    // the grown account has to read back as the current layout
+    let mut config = GlobalConfig::try_deserialize(&mut &global_config.try_borrow_data()?[..])?;
+    config.fill_grown_fields(old_len)?;
+    config.try_serialize(&mut &mut global_config.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -36,6 +36,12 @@ pub struct MintMemecoin<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
+    #[account(
+        seeds = [b"CONFIG"],
+        bump
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
     #[account(
         init,
         seeds = [ &seed.to_le_bytes()],
@@ -99,7 +105,7 @@ pub struct MemecoinCreated {
 
 pub fn handler(
     ctx: Context<MintMemecoin>,
-    seed: u64,
+    _seed: u64,
     memecoin_name: &str,
     memecoin_symbol: &str,
     memecoin_uri: &str,
@@ -108,11 +114,13 @@ pub fn handler(
     memecoin_telegram: &str,
     memecoin_twitter: &str,
 ) -> Result<()> {
-    // Verify that the mint address ends with "meme"
-    let (mint_pda, _bump) =
-        Pubkey::find_program_address(&[&seed.to_le_bytes()], ctx.program_id);
+    // Verify that the mint address ends with the configured suffix,
+    // the seeds constraint already derived it from `seed`
     require!(
-        mint_pda.to_string().to_lowercase().ends_with("meme"),
+        ctx.accounts
+            .global_config
+            .mint_suffix_policy
+            .matches(&ctx.accounts.mint.key()),
         ErrorCode::InvalidMintAddress
     );
 
@@ -140,6 +148,8 @@ pub fn handler(
         ctx.accounts.token_metadata_program.to_account_info(),
         CreateMetadataAccountsV3 {
             payer: ctx.accounts.creator.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             update_authority: ctx.accounts.memecoin_config.to_account_info(),
             mint_authority: ctx.accounts.memecoin_config.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
@@ -196,6 +206,16 @@ pub fn handler(
         created_time: ctx.accounts.memecoin_config.created_time,
         memecoin_config: ctx.accounts.memecoin_config.key().to_string(),
         mint: ctx.accounts.mint.key().to_string(),
+        destination: ctx.accounts.destination.key().to_string(),
+        name: memecoin_name.to_string(),
+        symbol: memecoin_symbol.to_string(),
+        uri: memecoin_uri.to_string(),
+        description: memecoin_description.to_string(),
+        decimal: ctx.accounts.mint.decimals,
+        website: memecoin_website.to_string(),
+        telegram: memecoin_telegram.to_string(),
+        twitter: memecoin_twitter.to_string(),
+        funding_raise_tier,
     };
     let serialized = serde_json::to_string(&event).unwrap();
 
@@ -1,8 +1,10 @@
 pub mod initialize_global_config;
+pub mod migrate_global_config;
 pub mod set_create_memecoin_fee_receiver;
 pub mod set_launch_success_fee_receiver;
 pub mod set_create_memecoin_fee;
 pub mod set_launch_success_fee_bps;
+pub mod set_mint_suffix_policy;
 pub mod create_memecoin_config;
 pub mod buy_memecoin;
 pub mod claim_lamports;
@@ -16,10 +18,12 @@ pub mod revoke_mint_authority;
 
 
 pub use initialize_global_config::*;
+pub use migrate_global_config::*;
 pub use set_create_memecoin_fee_receiver::*;
 pub use set_launch_success_fee_receiver::*;
 pub use set_create_memecoin_fee::*;
 pub use set_launch_success_fee_bps::*;
+pub use set_mint_suffix_policy::*;
 pub use create_memecoin_config::*;
 pub use buy_memecoin::*;
 pub use claim_lamports::*;
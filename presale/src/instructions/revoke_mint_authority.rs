@@ -42,6 +42,12 @@ pub struct RevokeMintAuthority<'info> {
 }
 
 pub fn handler(ctx: Context<RevokeMintAuthority>) -> Result<()> {
+    let seeds = &[
+        ctx.accounts.memecoin_config.creator.as_ref(),
+        &ctx.accounts.memecoin_config.creator_memecoin_index.to_le_bytes(),
+        &[ctx.bumps.memecoin_config]
+    ];
+    let signer = [&seeds[..]];
 
     let cpi_accounts = SetAuthority {
         account_or_mint: ctx.accounts.mint.to_account_info(),
@@ -10,6 +10,9 @@ pub struct SetCreateMemecoinFeeReceiver<'info> {
         bump
     )]
     pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
 }
 
 pub fn handler(
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::state::{GlobalConfig, MintSuffixMode};
+
+#[derive(Accounts)]
+pub struct SetMintSuffixPolicy<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"CONFIG"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[event]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MintSuffixPolicySet {
+    pub admin: String,
+    pub enabled: bool,
+    pub case_sensitive: bool,
+    pub suffix: String,
+}
+
+pub fn handler(
+    ctx: Context<SetMintSuffixPolicy>,
+    mode: MintSuffixMode,
+    suffix: &str,
+) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.set_mint_suffix_policy(mode, suffix)?;
+
+    let event = MintSuffixPolicySet {
+        admin: ctx.accounts.admin.key().to_string(),
+        enabled: mode != MintSuffixMode::Disabled,
+        case_sensitive: mode == MintSuffixMode::CaseSensitive,
+        suffix: global_config.mint_suffix_policy.suffix_str(),
+    };
+    let serialized = serde_json::to_string(&event).unwrap();
+    msg!("MintSuffixPolicySet:{}", serialized);
+    emit!(event);
+
+    Ok(())
+}
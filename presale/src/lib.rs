@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use instructions::*;
-use state::{InitTokenParams, FundingRaiseTier};
+use state::{InitTokenParams, FundingRaiseTier, MintSuffixMode};
 
 declare_id!("4fTeRu8jGLDErJnPv9Cx9cdpRt9sm2vSvF41oTQ6CbED");
 
@@ -8,6 +8,7 @@ pub mod errors;
 pub mod constants;
 pub mod instructions;
 pub mod state;
+pub mod dex;
 
 #[program]
 pub mod memecoin {
@@ -31,6 +32,14 @@ pub mod memecoin {
         );
     }
 
+    pub fn migrate_global_config(
+        ctx: Context<MigrateGlobalConfig>,
+    ) -> Result<()> {
+        return migrate_global_config::handler(
+            ctx,
+        );
+    }
+
     pub fn set_create_memecoin_fee_receiver(
         ctx: Context<SetCreateMemecoinFeeReceiver>,
         create_memecoin_fee_receiver: Pubkey,
@@ -59,6 +68,14 @@ pub mod memecoin {
         return set_launch_success_fee_bps::handler(ctx, launch_success_fee_bps);
     }
 
+    pub fn set_mint_suffix_policy(
+        ctx: Context<SetMintSuffixPolicy>,
+        mode: MintSuffixMode,
+        suffix: String,
+    ) -> Result<()> {
+        return set_mint_suffix_policy::handler(ctx, mode, &suffix);
+    }
+
     /* ====================================== User Instructions ============================================ */
 
     pub fn create_memecoin_config(
@@ -134,16 +151,16 @@ pub mod memecoin {
         );
     }
 
-    pub fn create_raydium_pool(
-        ctx: Context<CreateRaydiumPool>,
+    pub fn create_raydium_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateRaydiumPool<'info>>,
     ) -> Result<()> {
         return create_raydium_pool::handler(
             ctx,
         );
     }
 
-    pub fn create_raydium_pool_by_admin(
-        ctx: Context<CreateRaydiumPoolByAdmin>,
+    pub fn create_raydium_pool_by_admin<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateRaydiumPoolByAdmin<'info>>,
     ) -> Result<()> {
         return create_raydium_pool_by_admin::handler(
             ctx,
@@ -7,6 +7,7 @@ pub struct CreatorMemecoinCounter {
 }
 
 impl CreatorMemecoinCounter {
+    pub const LEN: usize = 8 + 4;
 
     pub fn increment(
         &mut self,
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::errors::ErrorCode;
+use crate::constants::DEFAULT_MINT_SUFFIX;
+use crate::state::{MintSuffixMode, MintSuffixPolicy};
 
 #[account]
 pub struct GlobalConfig {
@@ -8,15 +9,19 @@ pub struct GlobalConfig {
     pub launch_success_fee_receiver: Pubkey,
     pub create_memecoin_fee: u64, // default 30000000(0.03 SOL)
     pub launch_success_fee_bps: u16,  // default 175(1.75%)
+    pub mint_suffix_policy: MintSuffixPolicy, // default case-insensitive "meme"
 }
 
 impl GlobalConfig {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 2;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 2 + MintSuffixPolicy::LEN;
+    // size of the layout before the mint suffix policy was added
+    pub const MINT_SUFFIX_POLICY_OFFSET: usize = 8 + 32 + 32 + 32 + 8 + 2;
 
     pub fn initialize(
         &mut self,
         admin: Pubkey,
         create_memecoin_fee_receiver: Pubkey,
+        launch_success_fee_receiver: Pubkey,
         create_memecoin_fee: u64,
         launch_success_fee_bps: u16,
     ) -> Result<()> {
@@ -25,6 +30,19 @@ impl GlobalConfig {
         self.launch_success_fee_receiver = launch_success_fee_receiver;
         self.create_memecoin_fee = create_memecoin_fee;
         self.launch_success_fee_bps = launch_success_fee_bps;
+        self.mint_suffix_policy =
+            MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, DEFAULT_MINT_SUFFIX)?;
+
+        Ok(())
+    }
+
+    // gives the fields an account of `old_len` bytes did not have yet the
+    // defaults of `initialize`
+    pub fn fill_grown_fields(&mut self, old_len: usize) -> Result<()> {
+        if old_len <= Self::MINT_SUFFIX_POLICY_OFFSET {
+            self.mint_suffix_policy =
+                MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, DEFAULT_MINT_SUFFIX)?;
+        }
 
         Ok(())
     }
@@ -40,6 +58,7 @@ impl GlobalConfig {
         &mut self,
         launch_success_fee_receiver: &Pubkey,
     ) {
+        self.launch_success_fee_receiver = *launch_success_fee_receiver;
     }
 
     pub fn set_create_memecoin_fee(
@@ -59,4 +78,14 @@ impl GlobalConfig {
 
         Ok(())
     }
+
+    pub fn set_mint_suffix_policy(
+        &mut self,
+        mode: MintSuffixMode,
+        suffix: &str,
+    ) -> Result<()> {
+        self.mint_suffix_policy = MintSuffixPolicy::new(mode, suffix)?;
+
+        Ok(())
+    }
 }
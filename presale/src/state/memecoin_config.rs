@@ -48,6 +48,7 @@ pub enum LaunchStatus {
     Ongoing,
     Failed,
     Succeed,
+    PoolCreated,
 }
 
 #[account]
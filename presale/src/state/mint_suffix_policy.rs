@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Longest suffix the policy accepts. `58^9 * 256` still fits a u64, which
+/// keeps the suffix check free of wide arithmetic.
+pub const MAX_MINT_SUFFIX_LEN: usize = 9;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, AnchorSerialize, AnchorDeserialize)]
+pub enum MintSuffixMode {
+    Disabled,
+    CaseInsensitive,
+    CaseSensitive,
+}
+
+/// Suffix memecoin mint addresses must end with in base58.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct MintSuffixPolicy {
+    pub mode: MintSuffixMode,
+    pub len: u8,
+    pub suffix: [u8; MAX_MINT_SUFFIX_LEN], // lowercased when case-insensitive
+}
+
+impl MintSuffixPolicy {
+    pub const LEN: usize = 1 + 1 + MAX_MINT_SUFFIX_LEN;
+
+    pub fn new(mode: MintSuffixMode, suffix: &str) -> Result<Self> {
+        let mut policy = MintSuffixPolicy {
+            mode,
+            len: 0,
+            suffix: [0; MAX_MINT_SUFFIX_LEN],
+        };
+        if mode == MintSuffixMode::Disabled {
+            return Ok(policy);
+        }
+
+        require!(
+            !suffix.is_empty() && suffix.len() <= MAX_MINT_SUFFIX_LEN,
+            ErrorCode::InvalidMintSuffix
+        );
+        for (i, c) in suffix.bytes().enumerate() {
+            let c = policy.normalize(c);
+            require!(
+                BASE58_ALPHABET.iter().any(|a| policy.normalize(*a) == c),
+                ErrorCode::InvalidMintSuffix
+            );
+            policy.suffix[i] = c;
+        }
+        policy.len = suffix.len() as u8;
+
+        Ok(policy)
+    }
+
+    pub fn suffix_str(&self) -> String {
+        String::from_utf8_lossy(&self.suffix[..self.len as usize]).into_owned()
+    }
+
+    /// The last `len` base58 digits of an address are the address modulo
+    /// `58^len`, so they can be compared without encoding the whole key.
+    pub fn matches(&self, address: &Pubkey) -> bool {
+        if self.mode == MintSuffixMode::Disabled {
+            return true;
+        }
+
+        let len = self.len as usize;
+        let modulus = 58u64.pow(len as u32);
+        let mut rem = 0u64;
+        for byte in address.as_ref() {
+            rem = ((rem << 8) | *byte as u64) % modulus;
+        }
+
+        for expected in self.suffix[..len].iter().rev() {
+            let digit = BASE58_ALPHABET[(rem % 58) as usize];
+            if self.normalize(digit) != *expected {
+                return false;
+            }
+            rem /= 58;
+        }
+
+        true
+    }
+
+    fn normalize(&self, c: u8) -> u8 {
+        match self.mode {
+            MintSuffixMode::CaseSensitive => c,
+            _ => c.to_ascii_lowercase(),
+        }
+    }
+}
@@ -1,9 +1,11 @@
 pub mod global_config;
 pub mod memecoin_config;
 pub mod creator_memecoin_counter;
+pub mod mint_suffix_policy;
 
 
 
 pub use global_config::*;
 pub use memecoin_config::*;
-pub use creator_memecoin_counter::*;
\ No newline at end of file
+pub use creator_memecoin_counter::*;
+pub use mint_suffix_policy::*;
\ No newline at end of file
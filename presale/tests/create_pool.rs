@@ -0,0 +1,300 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        entrypoint::ProgramResult, instruction::Instruction, program_option::COption, program_pack::Pack,
+        system_program, sysvar,
+    },
+    AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token::spl_token::{
+        self,
+        native_mint,
+        state::{Account as TokenAccount, AccountState, Mint},
+    },
+};
+use memecoin::errors::ErrorCode;
+use memecoin::state::{FundingRaiseTier, LaunchStatus, MemecoinConfig, MEMECOIN_TOTAL_SOLD, MEMECOIN_TOTAL_SUPPLY};
+use solana_program_test::{processor, BanksClient, BanksClientError, ProgramTest, ProgramTestBanksClientExt};
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+const POOL_TOKEN_AMOUNT: u64 = MEMECOIN_TOTAL_SUPPLY - MEMECOIN_TOTAL_SOLD;
+const POOL_WSOL_AMOUNT: u64 = 17_000_000_000;
+
+fn process_memecoin(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    memecoin::entry(program_id, accounts, data)
+}
+
+fn process_mock_amm(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    mock_amm::entry(program_id, accounts, data)
+}
+
+fn packed_account<T: Pack>(state: T, lamports: u64) -> Account {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+    Account {
+        lamports: Rent::default().minimum_balance(T::LEN) + lamports,
+        data,
+        owner: spl_token::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn mint_account(supply: u64, decimals: u8) -> Account {
+    packed_account(
+        Mint {
+            mint_authority: COption::None,
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        },
+        0,
+    )
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, amount: u64, is_native: bool) -> Account {
+    let rent = Rent::default().minimum_balance(TokenAccount::LEN);
+    packed_account(
+        TokenAccount {
+            mint,
+            owner,
+            amount,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: if is_native { COption::Some(rent) } else { COption::None },
+            delegated_amount: 0,
+            close_authority: COption::None,
+        },
+        if is_native { amount } else { 0 },
+    )
+}
+
+async fn token_amount(banks_client: &mut BanksClient, address: Pubkey) -> u64 {
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    TokenAccount::unpack(&account.data).unwrap().amount
+}
+
+struct Presale {
+    creator: Keypair,
+    mint: Pubkey,
+    memecoin_config: Pubkey,
+    memecoin_config_token: Pubkey,
+    memecoin_config_wrapped_sol_account: Pubkey,
+}
+
+/// A presale that sold out and has wrapped its SOL, ready for its pool.
+fn setup() -> (ProgramTest, Presale) {
+    let mut program_test = ProgramTest::new("memecoin", memecoin::ID, processor!(process_memecoin));
+    program_test.add_program("mock_amm", mock_amm::ID, processor!(process_mock_amm));
+
+    let creator = Keypair::new();
+    let mint = Pubkey::new_unique();
+    let (memecoin_config, _) =
+        Pubkey::find_program_address(&[creator.pubkey().as_ref(), &0u32.to_le_bytes()], &memecoin::ID);
+    let (memecoin_config_token, _) = Pubkey::find_program_address(
+        &[b"MEME_COIN", mint.as_ref(), memecoin_config.as_ref()],
+        &memecoin::ID,
+    );
+    let (memecoin_config_wrapped_sol_account, _) =
+        Pubkey::find_program_address(&[b"WSOL", memecoin_config.as_ref()], &memecoin::ID);
+
+    program_test.add_account(
+        creator.pubkey(),
+        Account::new(10_000_000_000, 0, &system_program::ID),
+    );
+    program_test.add_account(mint, mint_account(MEMECOIN_TOTAL_SUPPLY, 6));
+    program_test.add_account(native_mint::id(), mint_account(0, 9));
+
+    let config = MemecoinConfig {
+        creator: creator.pubkey(),
+        creator_memecoin_index: 0,
+        created_time: 0,
+        funding_raise_tier: FundingRaiseTier::TwentySol,
+        status: LaunchStatus::Succeed,
+        mint,
+    };
+    let mut data = Vec::with_capacity(MemecoinConfig::LEN);
+    config.try_serialize(&mut data).unwrap();
+    data.resize(MemecoinConfig::LEN, 0);
+    program_test.add_account(
+        memecoin_config,
+        Account {
+            lamports: Rent::default().minimum_balance(MemecoinConfig::LEN) + 2_000_000_000,
+            data,
+            owner: memecoin::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    program_test.add_account(
+        memecoin_config_token,
+        token_account(mint, memecoin_config, POOL_TOKEN_AMOUNT, false),
+    );
+    program_test.add_account(
+        memecoin_config_wrapped_sol_account,
+        token_account(native_mint::id(), memecoin_config, POOL_WSOL_AMOUNT, true),
+    );
+
+    (
+        program_test,
+        Presale {
+            creator,
+            mint,
+            memecoin_config,
+            memecoin_config_token,
+            memecoin_config_wrapped_sol_account,
+        },
+    )
+}
+
+/// Error of the create pool instruction, which follows the compute budget one.
+fn create_pool_error(error_code: ErrorCode) -> TransactionError {
+    TransactionError::InstructionError(1, InstructionError::Custom(error_code.into()))
+}
+
+fn create_pool_instruction(presale: &Presale, dex_accounts: Vec<AccountMeta>) -> Instruction {
+    let creator = presale.creator.pubkey();
+    let mut accounts = memecoin::accounts::CreateRaydiumPool {
+        memecoin_config: presale.memecoin_config,
+        creator,
+        mint: presale.mint,
+        wrapped_sol_mint: native_mint::id(),
+        memecoin_config_token: presale.memecoin_config_token,
+        memecoin_config_wrapped_sol_account: presale.memecoin_config_wrapped_sol_account,
+        creator_token: get_associated_token_address(&creator, &presale.mint),
+        creator_wrapped_sol_account: get_associated_token_address(&creator, &native_mint::id()),
+        token_program: spl_token::id(),
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(dex_accounts);
+
+    Instruction {
+        program_id: memecoin::ID,
+        accounts,
+        data: memecoin::instruction::CreateRaydiumPool {}.data(),
+    }
+}
+
+struct MockPool {
+    pool: Pubkey,
+    lp_mint: Pubkey,
+    creator_lp_token: Pubkey,
+    token_vault: Pubkey,
+    wsol_vault: Pubkey,
+}
+
+fn mock_amm_accounts(presale: &Presale) -> (MockPool, Vec<AccountMeta>) {
+    let (pool, _) = Pubkey::find_program_address(
+        &[b"pool", presale.mint.as_ref(), native_mint::id().as_ref()],
+        &mock_amm::ID,
+    );
+    let (lp_mint, _) = Pubkey::find_program_address(&[b"lp", pool.as_ref()], &mock_amm::ID);
+    let (token_vault, _) =
+        Pubkey::find_program_address(&[b"vault", pool.as_ref(), presale.mint.as_ref()], &mock_amm::ID);
+    let (wsol_vault, _) = Pubkey::find_program_address(
+        &[b"vault", pool.as_ref(), native_mint::id().as_ref()],
+        &mock_amm::ID,
+    );
+    let creator_lp_token = get_associated_token_address(&presale.creator.pubkey(), &lp_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(mock_amm::ID, false),
+        AccountMeta::new(pool, false),
+        AccountMeta::new(lp_mint, false),
+        AccountMeta::new(creator_lp_token, false),
+        AccountMeta::new(token_vault, false),
+        AccountMeta::new(wsol_vault, false),
+        AccountMeta::new_readonly(associated_token::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    (
+        MockPool {
+            pool,
+            lp_mint,
+            creator_lp_token,
+            token_vault,
+            wsol_vault,
+        },
+        accounts,
+    )
+}
+
+async fn send(
+    banks_client: &mut BanksClient,
+    creator: &Keypair,
+    instruction: Instruction,
+    recent_blockhash: Hash,
+) -> std::result::Result<(), BanksClientError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), instruction],
+        Some(&creator.pubkey()),
+        &[creator],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await
+}
+
+#[tokio::test]
+async fn test_create_pool_on_mock_amm() {
+    let (program_test, presale) = setup();
+    let (mut banks_client, _payer, recent_blockhash) = program_test.start().await;
+
+    let (mock_pool, dex_accounts) = mock_amm_accounts(&presale);
+    let instruction = create_pool_instruction(&presale, dex_accounts.clone());
+    send(&mut banks_client, &presale.creator, instruction, recent_blockhash).await.unwrap();
+
+    // all the liquidity ended up in the pool
+    assert_eq!(token_amount(&mut banks_client, mock_pool.token_vault).await, POOL_TOKEN_AMOUNT);
+    assert_eq!(token_amount(&mut banks_client, mock_pool.wsol_vault).await, POOL_WSOL_AMOUNT);
+    assert_eq!(token_amount(&mut banks_client, presale.memecoin_config_token).await, 0);
+    assert_eq!(token_amount(&mut banks_client, presale.memecoin_config_wrapped_sol_account).await, 0);
+    assert!(banks_client.get_account(mock_pool.pool).await.unwrap().is_some());
+
+    // and the LP was burned
+    assert_eq!(token_amount(&mut banks_client, mock_pool.creator_lp_token).await, 0);
+    let lp_mint = banks_client.get_account(mock_pool.lp_mint).await.unwrap().unwrap();
+    assert_eq!(Mint::unpack(&lp_mint.data).unwrap().supply, 0);
+
+    let config = banks_client.get_account(presale.memecoin_config).await.unwrap().unwrap();
+    let config = MemecoinConfig::try_deserialize(&mut config.data.as_slice()).unwrap();
+    assert!(config.status == LaunchStatus::PoolCreated);
+
+    // the pool can only be created once
+    let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+    let instruction = create_pool_instruction(&presale, dex_accounts);
+    let error = send(&mut banks_client, &presale.creator, instruction, recent_blockhash).await.unwrap_err();
+    assert_eq!(error.unwrap(), create_pool_error(ErrorCode::OnlyCreatePoolWhenLaunchSuccess));
+}
+
+#[tokio::test]
+async fn test_create_pool_rejects_unknown_dex() {
+    let (program_test, presale) = setup();
+    let (mut banks_client, _payer, recent_blockhash) = program_test.start().await;
+
+    let (_, mut dex_accounts) = mock_amm_accounts(&presale);
+    dex_accounts[0] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+    let instruction = create_pool_instruction(&presale, dex_accounts);
+    let error = send(&mut banks_client, &presale.creator, instruction, recent_blockhash).await.unwrap_err();
+    assert_eq!(error.unwrap(), create_pool_error(ErrorCode::UnsupportedDex));
+
+    let config = banks_client.get_account(presale.memecoin_config).await.unwrap().unwrap();
+    let config = MemecoinConfig::try_deserialize(&mut config.data.as_slice()).unwrap();
+    assert!(config.status == LaunchStatus::Succeed);
+}
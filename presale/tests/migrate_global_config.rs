@@ -0,0 +1,82 @@
+use std::str::FromStr;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, instruction::Instruction, system_program},
+    AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use memecoin::state::{GlobalConfig, MintSuffixMode, MintSuffixPolicy};
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+fn process_memecoin(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    memecoin::entry(program_id, accounts, data)
+}
+
+#[tokio::test]
+async fn test_migrated_global_config_keeps_meme_suffix() {
+    let mut program_test = ProgramTest::new("memecoin", memecoin::ID, processor!(process_memecoin));
+
+    let admin = Keypair::new();
+    let (global_config, _) = Pubkey::find_program_address(&[b"CONFIG"], &memecoin::ID);
+    program_test.add_account(admin.pubkey(), Account::new(10_000_000_000, 0, &system_program::ID));
+
+    // a config written before the mint suffix policy existed
+    let config = GlobalConfig {
+        admin: admin.pubkey(),
+        create_memecoin_fee_receiver: Pubkey::new_unique(),
+        launch_success_fee_receiver: Pubkey::new_unique(),
+        create_memecoin_fee: 30_000_000,
+        launch_success_fee_bps: 175,
+        mint_suffix_policy: MintSuffixPolicy::new(MintSuffixMode::Disabled, "").unwrap(),
+    };
+    let mut data = Vec::with_capacity(GlobalConfig::LEN);
+    config.try_serialize(&mut data).unwrap();
+    data.truncate(GlobalConfig::MINT_SUFFIX_POLICY_OFFSET);
+    program_test.add_account(
+        global_config,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: memecoin::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let (mut banks_client, _payer, recent_blockhash) = program_test.start().await;
+
+    let instruction = Instruction {
+        program_id: memecoin::ID,
+        accounts: memecoin::accounts::MigrateGlobalConfig {
+            global_config,
+            admin: admin.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: memecoin::instruction::MigrateGlobalConfig {}.data(),
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&admin.pubkey()),
+        &[&admin],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let account = banks_client.get_account(global_config).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), GlobalConfig::LEN);
+    let migrated = GlobalConfig::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(migrated.launch_success_fee_bps, 175);
+
+    let policy = migrated.mint_suffix_policy;
+    assert_eq!(policy.mode, MintSuffixMode::CaseInsensitive);
+    assert_eq!(policy.suffix_str(), "meme");
+    assert!(policy.matches(&Pubkey::from_str("3mvmucaXydtR5YPyKMaMfbPLpHf1pTaniDGPNf6QMeMe").unwrap()));
+    assert!(!policy.matches(&memecoin::ID));
+}
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::amm::{math, sol_with_fee, sol_without_fee, CurveModel, CurveReserves};
+
 #[derive(Debug)]
 pub struct BuyResult {
     pub token_amount: u64,
@@ -12,13 +14,16 @@ pub struct SellResult {
     pub sol_amount: u64,
 }
 
+/// Constant-product curve over virtual reserves.
+///
+/// Every quote rounds the new SOL reserve up, so `virtual_sol_reserves *
+/// virtual_token_reserves` never decreases and rounding dust stays in the pool.
 #[derive(Debug)]
 pub struct AMM {
     pub virtual_sol_reserves: u128,
     pub virtual_token_reserves: u128,
     pub real_sol_reserves: u128,
     pub real_token_reserves: u128,
-    pub initial_virtual_token_reserves: u128,
 }
 
 impl AMM {
@@ -27,78 +32,138 @@ impl AMM {
         virtual_token_reserves: u128,
         real_sol_reserves: u128,
         real_token_reserves: u128,
-        initial_virtual_token_reserves: u128,
     ) -> Self {
         AMM {
             virtual_sol_reserves,
             virtual_token_reserves,
             real_sol_reserves,
             real_token_reserves,
-            initial_virtual_token_reserves,
         }
     }
 
-    pub fn get_buy_price(&self, tokens: u128) -> Option<u128> {
-        if tokens == 0 || tokens > self.virtual_token_reserves {
+    /// k / `reserve` rounded up: what one side must hold to keep k once the
+    /// other side holds `reserve`.
+    fn reserve_keeping_k(&self, reserve: u128) -> Option<u128> {
+        math::mul_div_ceil(
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves,
+            reserve,
+        )
+    }
+}
+
+impl CurveModel for AMM {
+    fn get_buy_price(&self, tokens: u128) -> Option<u128> {
+        if tokens == 0 || tokens >= self.virtual_token_reserves || self.virtual_sol_reserves == 0 {
             return None;
         }
 
-        let product_of_reserves = self.virtual_sol_reserves.checked_mul(self.virtual_token_reserves)?;
-        let new_virtual_token_reserves = self.virtual_token_reserves.checked_sub(tokens)?;
-        let new_virtual_sol_reserves = product_of_reserves.checked_div(new_virtual_token_reserves)?.checked_add(1)?;
-        let amount_needed = new_virtual_sol_reserves.checked_sub(self.virtual_sol_reserves)?;
+        let new_virtual_token_reserves = self.virtual_token_reserves - tokens;
+        let new_virtual_sol_reserves = self.reserve_keeping_k(new_virtual_token_reserves)?;
 
-        Some(amount_needed)
+        new_virtual_sol_reserves.checked_sub(self.virtual_sol_reserves)
     }
 
-    pub fn apply_buy(&mut self, token_amount: u128) -> Option<BuyResult> {
-        let final_token_amount = if token_amount > self.real_token_reserves {
-            self.real_token_reserves
-        } else {
-            token_amount
-        };
+    fn get_sell_price(&self, tokens: u128) -> Option<u128> {
+        if tokens == 0 {
+            return None;
+        }
+
+        let new_virtual_token_reserves = self.virtual_token_reserves.checked_add(tokens)?;
+        let new_virtual_sol_reserves = self.reserve_keeping_k(new_virtual_token_reserves)?;
+        let sol_received = self.virtual_sol_reserves.checked_sub(new_virtual_sol_reserves)?;
+
+        Some(sol_received.min(self.real_sol_reserves))
+    }
+
+    fn apply_buy(&mut self, token_amount: u128) -> Option<BuyResult> {
+        let final_token_amount = token_amount.min(self.real_token_reserves);
 
         let sol_amount = self.get_buy_price(final_token_amount)?;
+        let new_virtual_sol_reserves = self.virtual_sol_reserves.checked_add(sol_amount)?;
+
+        // reserves are stored as u64 on the bonding curve, refuse anything that would truncate
+        if new_virtual_sol_reserves > u64::MAX as u128 {
+            return None;
+        }
 
         self.virtual_token_reserves = self.virtual_token_reserves.checked_sub(final_token_amount)?;
         self.real_token_reserves = self.real_token_reserves.checked_sub(final_token_amount)?;
 
-        self.virtual_sol_reserves = self.virtual_sol_reserves.checked_add(sol_amount)?;
+        self.virtual_sol_reserves = new_virtual_sol_reserves;
         self.real_sol_reserves = self.real_sol_reserves.checked_add(sol_amount)?;
 
         Some(BuyResult {
-            token_amount: final_token_amount as u64,
-            sol_amount: sol_amount as u64,
+            token_amount: u64::try_from(final_token_amount).ok()?,
+            sol_amount: u64::try_from(sol_amount).ok()?,
         })
     }
 
-    pub fn apply_sell(&mut self, token_amount: u128) -> Option<SellResult> {
-        self.virtual_token_reserves = self.virtual_token_reserves.checked_add(token_amount)?;
-        self.real_token_reserves = self.real_token_reserves.checked_add(token_amount)?;
-
+    fn apply_sell(&mut self, token_amount: u128) -> Option<SellResult> {
         let sol_amount = self.get_sell_price(token_amount)?;
+        let new_virtual_token_reserves = self.virtual_token_reserves.checked_add(token_amount)?;
+
+        if new_virtual_token_reserves > u64::MAX as u128 {
+            return None;
+        }
+
+        self.virtual_token_reserves = new_virtual_token_reserves;
+        self.real_token_reserves = self.real_token_reserves.checked_add(token_amount)?;
 
         self.virtual_sol_reserves = self.virtual_sol_reserves.checked_sub(sol_amount)?;
         self.real_sol_reserves = self.real_sol_reserves.checked_sub(sol_amount)?;
 
         Some(SellResult {
-            token_amount: token_amount as u64,
-            sol_amount: sol_amount as u64,
+            token_amount: u64::try_from(token_amount).ok()?,
+            sol_amount: u64::try_from(sol_amount).ok()?,
         })
     }
 
-    pub fn get_sell_price(&self, tokens: u128) -> Option<u128> {
-        if tokens <= 0 || tokens > self.virtual_token_reserves {
+    fn spot_price(&self) -> Option<u128> {
+        math::mul_div(self.virtual_sol_reserves, math::ONE, self.virtual_token_reserves)
+    }
+
+    fn reserves(&self) -> CurveReserves {
+        CurveReserves {
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+        }
+    }
+
+    fn get_buy_token_amount(&self, sol_amount: u128, fee_basis_points: u128) -> Option<u128> {
+        let sol_for_curve = sol_without_fee(sol_amount, fee_basis_points)?;
+
+        // ceil(k / (vt - t)) <= vs + s  <=>  vt - t >= ceil(k / (vs + s))
+        let new_virtual_sol_reserves = self.virtual_sol_reserves.checked_add(sol_for_curve)?;
+        let min_virtual_token_reserves = self.reserve_keeping_k(new_virtual_sol_reserves)?;
+        let tokens = self
+            .virtual_token_reserves
+            .saturating_sub(min_virtual_token_reserves)
+            .min(self.real_token_reserves);
+
+        if tokens == 0 {
             return None;
         }
 
-        let scaling_factor = self.initial_virtual_token_reserves;
+        Some(tokens)
+    }
 
-        let scaled_tokens = tokens.checked_mul(scaling_factor)?;
-        let token_sell_proportion = scaled_tokens.checked_div(self.virtual_token_reserves)?;
-        let sol_received = (self.virtual_sol_reserves.checked_mul(token_sell_proportion)?).checked_div(scaling_factor)?;
+    fn get_sell_token_amount(&self, sol_amount: u128, fee_basis_points: u128) -> Option<u128> {
+        let gross_sol = sol_with_fee(sol_amount, fee_basis_points)?;
 
-        Some(sol_received.min(self.real_sol_reserves))
+        if gross_sol > self.real_sol_reserves || gross_sol >= self.virtual_sol_reserves {
+            return None;
+        }
+
+        // vs - ceil(k / (vt + t)) >= g  <=>  vt + t >= ceil(k / (vs - g))
+        let min_virtual_token_reserves =
+            self.reserve_keeping_k(self.virtual_sol_reserves - gross_sol)?;
+
+        min_virtual_token_reserves
+            .checked_sub(self.virtual_token_reserves)
+            .filter(|tokens| *tokens > 0)
     }
 }
 
@@ -107,15 +172,16 @@ impl fmt::Display for AMM {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "AMM {{ virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, initial_virtual_token_reserves: {} }}",
-            self.virtual_sol_reserves, self.virtual_token_reserves, self.real_sol_reserves, self.real_token_reserves, self.initial_virtual_token_reserves
+            "AMM {{ virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {} }}",
+            self.virtual_sol_reserves, self.virtual_token_reserves, self.real_sol_reserves, self.real_token_reserves
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::amm::AMM;
+    use crate::amm::{math, CurveModel, AMM};
+    use proptest::prelude::*;
 
     #[test]
     fn test_buy_and_sell_too_much() {
@@ -124,60 +190,82 @@ mod tests {
         let virtual_token_reserves = 600;
         let real_sol_reserves = 0;
         let real_token_reserves = 500;
-        let initial_virtual_token_reserves = 1000;
 
-        let mut amm = AMM::new(virtual_sol_reserves, virtual_token_reserves, real_sol_reserves, real_token_reserves, initial_virtual_token_reserves);
+        let mut amm = AMM::new(virtual_sol_reserves, virtual_token_reserves, real_sol_reserves, real_token_reserves);
 
         //println!("{} \n", 1/0);
         // Attempt to buy more tokens than available in reserves
         let buy_result = amm.apply_buy(2000).unwrap();
         println!("{:?} \n", buy_result);
         assert_eq!(buy_result.token_amount, 500); // Should buy up to available real_token_reserves
-        assert_eq!(buy_result.sol_amount, 3001);
+        assert_eq!(buy_result.sol_amount, 3000);
         assert_eq!(amm.real_token_reserves, real_token_reserves - buy_result.token_amount as u128);
-        assert_eq!(amm.virtual_token_reserves, virtual_token_reserves - buy_result.token_amount as u128); 
-        assert_eq!(amm.real_sol_reserves, real_sol_reserves + buy_result.sol_amount as u128);   
-        assert_eq!(amm.virtual_sol_reserves, virtual_sol_reserves + buy_result.sol_amount as u128); 
+        assert_eq!(amm.virtual_token_reserves, virtual_token_reserves - buy_result.token_amount as u128);
+        assert_eq!(amm.real_sol_reserves, real_sol_reserves + buy_result.sol_amount as u128);
+        assert_eq!(amm.virtual_sol_reserves, virtual_sol_reserves + buy_result.sol_amount as u128);
         println!("{} \n", amm);
         println!("{:?} \n", buy_result);
 
         // Attempt to sell more tokens than available in reserves
         let sell_result = amm.apply_sell(2000).unwrap();
         assert_eq!(sell_result.token_amount, 2000); // Should sell requested amount
-        assert_eq!(sell_result.sol_amount, 3001);    
-        assert_eq!(amm.real_sol_reserves, 0); 
-        assert_eq!(amm.virtual_sol_reserves, 600);  
-        assert_eq!(amm.real_token_reserves, 2000);  
-        assert_eq!(amm.virtual_token_reserves, 2100); 
+        assert_eq!(sell_result.sol_amount, 3000);
+        assert_eq!(amm.real_sol_reserves, 0);
+        assert_eq!(amm.virtual_sol_reserves, 600);
+        assert_eq!(amm.real_token_reserves, 2000);
+        assert_eq!(amm.virtual_token_reserves, 2100);
         println!("{} \n", amm);
         println!("{:?} \n", sell_result);
     }
 
     #[test]
     fn test_apply_sell() {
-        let mut amm = AMM::new(1000, 1000, 500, 500, 1000);
+        let mut amm = AMM::new(1000, 1000, 500, 500);
         let result = amm.apply_sell(100).unwrap();
 
         assert_eq!(result.token_amount, 100);
-        assert_eq!(result.sol_amount, 90); 
+        assert_eq!(result.sol_amount, 90);
         assert_eq!(amm.virtual_token_reserves, 1100);
         assert_eq!(amm.real_token_reserves, 600);
-        assert_eq!(amm.virtual_sol_reserves, 910); 
-        assert_eq!(amm.real_sol_reserves, 410);    
+        assert_eq!(amm.virtual_sol_reserves, 910);
+        assert_eq!(amm.real_sol_reserves, 410);
+    }
+
+    #[test]
+    fn test_get_sell_token_amount() {
+        let amm = AMM::new(1000, 1000, 500, 500);
+
+        assert_eq!(amm.get_sell_token_amount(0, 0), None);
+
+        // no fee: the smallest amount that pays out exactly 90 lamports
+        let tokens = amm.get_sell_token_amount(90, 0).unwrap();
+        assert_eq!(tokens, 99);
+        assert!(amm.get_sell_price(tokens).unwrap() >= 90);
+        assert!(amm.get_sell_price(tokens - 1).unwrap() < 90);
+
+        // 1% fee is added on top of the requested output
+        let tokens = amm.get_sell_token_amount(90, 100).unwrap();
+        let gross = amm.get_sell_price(tokens).unwrap();
+        assert!(gross - gross * 100 / 10000 >= 90);
+        let gross = amm.get_sell_price(tokens - 1).unwrap();
+        assert!(gross - gross * 100 / 10000 < 90);
+
+        // can't pay out more than the real sol reserves
+        assert_eq!(amm.get_sell_token_amount(501, 0), None);
     }
 
     #[test]
     fn test_get_sell_price() {
-        let amm = AMM::new(1000, 1000, 500, 500, 1000);
+        let amm = AMM::new(1000, 1000, 500, 500);
 
         // Edge case: zero tokens
         assert_eq!(amm.get_sell_price(0), None);
 
-        // Normal case
-        assert_eq!(amm.get_sell_price(100), Some(100)); 
+        // Normal case, same payout as apply_sell
+        assert_eq!(amm.get_sell_price(100), Some(90));
 
         // Should not exceed real sol reserves
-        assert_eq!(amm.get_sell_price(5000), None); 
+        assert_eq!(amm.get_sell_price(5000), Some(500));
     }
 
     #[test]
@@ -186,38 +274,161 @@ mod tests {
         let virtual_token_reserves = 600;
         let real_sol_reserves = 500;
         let real_token_reserves = 500;
-        let initial_virtual_token_reserves = 1000;
 
         let mut amm = AMM::new(
-            virtual_sol_reserves, 
-            virtual_token_reserves, 
-            real_sol_reserves, 
-            real_token_reserves, 
-            initial_virtual_token_reserves
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            real_sol_reserves,
+            real_token_reserves,
         );
 
         let purchase_amount = 100;
 
         let result = amm.apply_buy(100).unwrap();
-        
+
         assert_eq!(result.token_amount, purchase_amount as u64);
-        assert_eq!(result.sol_amount, 121); 
+        assert_eq!(result.sol_amount, 120);
         assert_eq!(amm.virtual_token_reserves, virtual_token_reserves - purchase_amount);
         assert_eq!(amm.real_token_reserves, real_token_reserves - purchase_amount);
-        assert_eq!(amm.virtual_sol_reserves, 721);
-        assert_eq!(amm.real_sol_reserves, 621);
+        assert_eq!(amm.virtual_sol_reserves, 720);
+        assert_eq!(amm.real_sol_reserves, 620);
+    }
+
+    #[test]
+    fn test_get_buy_token_amount() {
+        let amm = AMM::new(1000, 1000, 500, 500);
+
+        assert_eq!(amm.get_buy_token_amount(0, 0), None);
+
+        // no fee: 112 lamports buys exactly what get_buy_price(100) quotes
+        assert_eq!(amm.get_buy_token_amount(112, 0), Some(100));
+        assert_eq!(amm.get_buy_token_amount(111, 0), Some(99));
+
+        // 1% fee comes out of the budget first
+        let tokens = amm.get_buy_token_amount(112, 100).unwrap();
+        let cost = amm.get_buy_price(tokens).unwrap();
+        assert!(cost + cost * 100 / 10000 <= 112);
+        assert!(amm.get_buy_price(tokens + 1).unwrap() > 112 * 10000 / 10100);
+
+        // capped by the real token reserves
+        assert_eq!(amm.get_buy_token_amount(1_000_000, 0), Some(500));
     }
 
     #[test]
     fn test_get_buy_price() {
-        let amm = AMM::new(1000, 1000, 500, 500, 1000);
-        
+        let amm = AMM::new(1000, 1000, 500, 500);
+
         assert_eq!(amm.get_buy_price(0), None);
-        
+
         // Normal case
-        assert_eq!(amm.get_buy_price(100), Some(112)); 
+        assert_eq!(amm.get_buy_price(100), Some(112));
 
         // Edge case: very large token amount
-        assert_eq!(amm.get_buy_price(2000), None); 
+        assert_eq!(amm.get_buy_price(2000), None);
+
+        // Edge case: the whole virtual reserve can never be bought
+        assert_eq!(amm.get_buy_price(1000), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_analytics() {
+        let amm = AMM::new(1000, 1000, 0, 500);
+
+        // one lamport per token base unit
+        assert_eq!(amm.spot_price(), Some(math::ONE));
+        assert_eq!(amm.market_cap(2000), Some(2000));
+
+        // buying the last 500 of 1000 virtual tokens doubles the sol side
+        assert_eq!(amm.sol_to_complete(), Some(1000));
+
+        // 100 tokens for 112 lamports is 12% above spot, selling 100 pays 90 (10% below)
+        assert_eq!(amm.price_impact_bps(100, true), Some(1200));
+        let amm = AMM::new(1000, 1000, 1000, 500);
+        assert_eq!(amm.price_impact_bps(100, false), Some(1000));
+    }
+
+    /// k = virtual_sol_reserves * virtual_token_reserves as a 256 bit (high, low) pair.
+    fn k(amm: &AMM) -> (u128, u128) {
+        math::full_mul(amm.virtual_sol_reserves, amm.virtual_token_reserves)
+    }
+
+    /// Any curve `set_amm_params` accepts: non-zero virtual sol and real tokens
+    /// strictly below the virtual token reserves.
+    fn amm_params() -> impl Strategy<Value = (u64, u64, u64)> {
+        (1..=u64::MAX, 2..=u64::MAX).prop_flat_map(|(virtual_sol, virtual_token)| {
+            (Just(virtual_sol), Just(virtual_token), 1..virtual_token)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_buy_never_decreases_k((virtual_sol, virtual_token, real_token) in amm_params(), tokens in 1..=u64::MAX) {
+            let mut amm = AMM::new(virtual_sol as u128, virtual_token as u128, 0, real_token as u128);
+            let k_before = k(&amm);
+
+            if let Some(buy_result) = amm.apply_buy(tokens as u128) {
+                prop_assert!(k(&amm) >= k_before);
+                prop_assert!(buy_result.sol_amount > 0);
+                prop_assert_eq!(amm.real_sol_reserves, buy_result.sol_amount as u128);
+            }
+        }
+
+        #[test]
+        fn prop_sell_never_decreases_k(
+            (virtual_sol, virtual_token, real_token) in amm_params(),
+            real_sol in 0..=u64::MAX,
+            tokens in 1..=u64::MAX,
+        ) {
+            let mut amm = AMM::new(virtual_sol as u128, virtual_token as u128, real_sol.min(virtual_sol) as u128, real_token as u128);
+            let k_before = k(&amm);
+
+            if let Some(sell_result) = amm.apply_sell(tokens as u128) {
+                prop_assert!(k(&amm) >= k_before);
+                prop_assert!(sell_result.sol_amount as u128 <= real_sol as u128);
+            }
+        }
+
+        #[test]
+        fn prop_buy_then_sell_never_profits((virtual_sol, virtual_token, real_token) in amm_params(), tokens in 1..=u64::MAX) {
+            let mut amm = AMM::new(virtual_sol as u128, virtual_token as u128, 0, real_token as u128);
+            let k_before = k(&amm);
+
+            if let Some(buy_result) = amm.apply_buy(tokens as u128) {
+                if let Some(sell_result) = amm.apply_sell(buy_result.token_amount as u128) {
+                    prop_assert!(sell_result.sol_amount <= buy_result.sol_amount);
+                    prop_assert!(k(&amm) >= k_before);
+                    prop_assert_eq!(amm.virtual_token_reserves, virtual_token as u128);
+                }
+            }
+        }
+
+        #[test]
+        fn prop_inverse_quotes_are_tight(
+            (virtual_sol, virtual_token, real_token) in amm_params(),
+            sol_amount in 1..=u64::MAX,
+            fee_basis_points in 0..=1000u128,
+        ) {
+            let amm = AMM::new(virtual_sol as u128, virtual_token as u128, virtual_sol as u128, real_token as u128);
+            let sol_amount = sol_amount as u128;
+
+            if let Some(tokens) = amm.get_buy_token_amount(sol_amount, fee_basis_points) {
+                let cost = amm.get_buy_price(tokens).unwrap();
+                prop_assert!(cost + cost * fee_basis_points / 10000 <= sol_amount);
+                if tokens < real_token as u128 {
+                    if let Some(cost) = amm.get_buy_price(tokens + 1) {
+                        prop_assert!(cost + cost * fee_basis_points / 10000 > sol_amount);
+                    }
+                }
+            }
+
+            if let Some(tokens) = amm.get_sell_token_amount(sol_amount, fee_basis_points) {
+                let gross = amm.get_sell_price(tokens).unwrap();
+                prop_assert!(gross - gross * fee_basis_points / 10000 >= sol_amount);
+                if tokens > 1 {
+                    let gross = amm.get_sell_price(tokens - 1).unwrap();
+                    prop_assert!(gross - gross * fee_basis_points / 10000 < sol_amount);
+                }
+            }
+        }
+    }
+}
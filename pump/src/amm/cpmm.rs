@@ -0,0 +1,125 @@
+use crate::amm::math;
+
+/// Output of an `amount_in` swap against a constant product pool, rounded down
+/// so the product of the reserves never decreases.
+pub fn swap_output(reserve_in: u64, reserve_out: u64, amount_in: u64) -> Option<u64> {
+    if amount_in == 0 || reserve_in == 0 || reserve_out == 0 {
+        return None;
+    }
+
+    let amount_out = math::mul_div(
+        reserve_out as u128,
+        amount_in as u128,
+        reserve_in as u128 + amount_in as u128,
+    )?;
+    if amount_out == 0 {
+        return None;
+    }
+
+    u64::try_from(amount_out).ok()
+}
+
+/// LP supply minted for the liquidity a pool is seeded with.
+pub fn initial_lp_supply(sol_amount: u64, token_amount: u64) -> Option<u64> {
+    let lp_supply = math::sqrt(sol_amount as u128 * token_amount as u128);
+    if lp_supply == 0 {
+        return None;
+    }
+
+    u64::try_from(lp_supply).ok()
+}
+
+/// SOL and tokens to deposit for `lp_amount` LP tokens, rounded up.
+pub fn deposit_amounts(
+    sol_reserves: u64,
+    token_reserves: u64,
+    lp_supply: u64,
+    lp_amount: u64,
+) -> Option<(u64, u64)> {
+    if lp_amount == 0 {
+        return None;
+    }
+
+    let sol_amount = math::mul_div_ceil(sol_reserves as u128, lp_amount as u128, lp_supply as u128)?;
+    let token_amount = math::mul_div_ceil(token_reserves as u128, lp_amount as u128, lp_supply as u128)?;
+
+    Some((u64::try_from(sol_amount).ok()?, u64::try_from(token_amount).ok()?))
+}
+
+/// SOL and tokens paid out for burning `lp_amount` LP tokens, rounded down.
+pub fn withdraw_amounts(
+    sol_reserves: u64,
+    token_reserves: u64,
+    lp_supply: u64,
+    lp_amount: u64,
+) -> Option<(u64, u64)> {
+    if lp_amount == 0 || lp_amount > lp_supply {
+        return None;
+    }
+
+    let sol_amount = math::mul_div(sol_reserves as u128, lp_amount as u128, lp_supply as u128)?;
+    let token_amount = math::mul_div(token_reserves as u128, lp_amount as u128, lp_supply as u128)?;
+
+    Some((sol_amount as u64, token_amount as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_swap_output() {
+        // 1000 * 100 / (1000 + 100) = 90.9
+        assert_eq!(swap_output(1000, 1000, 100), Some(90));
+        assert_eq!(swap_output(1000, 1000, u64::MAX), Some(999));
+        assert_eq!(swap_output(1000, 1000, 0), None);
+        // too small to get anything out
+        assert_eq!(swap_output(1000, 1, 1), None);
+    }
+
+    #[test]
+    fn test_liquidity() {
+        assert_eq!(initial_lp_supply(400, 900), Some(600));
+        assert_eq!(initial_lp_supply(0, 900), None);
+
+        assert_eq!(deposit_amounts(400, 900, 600, 100), Some((67, 150)));
+        assert_eq!(withdraw_amounts(400, 900, 600, 100), Some((66, 150)));
+        assert_eq!(withdraw_amounts(400, 900, 600, 601), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_swap_never_decreases_k(
+            reserve_in in 1..=u64::MAX / 2,
+            reserve_out in 1..=u64::MAX,
+            amount_in in 1..=u64::MAX / 2,
+        ) {
+            if let Some(amount_out) = swap_output(reserve_in, reserve_out, amount_in) {
+                let k_before = reserve_in as u128 * reserve_out as u128;
+                let k_after = (reserve_in + amount_in) as u128 * (reserve_out - amount_out) as u128;
+                prop_assert!(k_after >= k_before);
+            }
+        }
+
+        #[test]
+        fn prop_deposit_then_withdraw_never_profits(
+            sol_reserves in 1..=u64::MAX / 4,
+            token_reserves in 1..=u64::MAX / 4,
+            lp_supply in 1..=u64::MAX / 4,
+            lp_amount in 1..=u64::MAX / 4,
+        ) {
+            if let Some((sol_in, token_in)) = deposit_amounts(sol_reserves, token_reserves, lp_supply, lp_amount) {
+                let (Some(sol_reserves), Some(token_reserves)) =
+                    (sol_reserves.checked_add(sol_in), token_reserves.checked_add(token_in))
+                else {
+                    return Ok(());
+                };
+                let (sol_out, token_out) =
+                    withdraw_amounts(sol_reserves, token_reserves, lp_supply + lp_amount, lp_amount).unwrap();
+                prop_assert!(sol_out <= sol_in);
+                prop_assert!(token_out <= token_in);
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt;
+
+use crate::amm::{math, BuyResult, SellResult};
+
+/// Reserves a curve model writes back into its `BondingCurve` after a trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveReserves {
+    pub virtual_sol_reserves: u128,
+    pub virtual_token_reserves: u128,
+    pub real_sol_reserves: u128,
+    pub real_token_reserves: u128,
+}
+
+/// Pricing of a bonding curve family.
+///
+/// Prices are quoted in lamports and always rounded in favour of the curve.
+pub trait CurveModel: fmt::Debug {
+    /// SOL needed to buy `tokens` out of the curve.
+    fn get_buy_price(&self, tokens: u128) -> Option<u128>;
+
+    /// SOL paid out for selling `tokens` into the curve.
+    fn get_sell_price(&self, tokens: u128) -> Option<u128>;
+
+    fn apply_buy(&mut self, token_amount: u128) -> Option<BuyResult>;
+
+    fn apply_sell(&mut self, token_amount: u128) -> Option<SellResult>;
+
+    /// Marginal price in lamports per token base unit, scaled by `math::ONE`.
+    fn spot_price(&self) -> Option<u128>;
+
+    fn reserves(&self) -> CurveReserves;
+
+    /// Fully diluted market cap of `token_supply` tokens at the spot price, in lamports.
+    fn market_cap(&self, token_supply: u128) -> Option<u128> {
+        math::mul_div(self.spot_price()?, token_supply, math::ONE)
+    }
+
+    /// SOL, before fees, still needed to buy out the real token reserves.
+    fn sol_to_complete(&self) -> Option<u128> {
+        match self.reserves().real_token_reserves {
+            0 => Some(0),
+            real_token_reserves => self.get_buy_price(real_token_reserves),
+        }
+    }
+
+    /// How far the average execution price of a `token_amount` trade is from
+    /// the spot price, in basis points of the spot price.
+    fn price_impact_bps(&self, token_amount: u128, is_buy: bool) -> Option<u64> {
+        let spot_price = self.spot_price()?;
+        let sol_amount = if is_buy {
+            self.get_buy_price(token_amount)?
+        } else {
+            self.get_sell_price(token_amount)?
+        };
+        let execution_price = math::mul_div(sol_amount, math::ONE, token_amount)?;
+        let impact = math::mul_div(execution_price.abs_diff(spot_price), 10000, spot_price)?;
+
+        Some(impact.min(u64::MAX as u128) as u64)
+    }
+
+    /// Inverse of `get_buy_price`: the largest token amount whose cost plus a
+    /// `fee_basis_points` fee on that cost fits in `sol_amount`.
+    fn get_buy_token_amount(&self, sol_amount: u128, fee_basis_points: u128) -> Option<u128> {
+        let sol_for_curve = sol_without_fee(sol_amount, fee_basis_points)?;
+
+        let mut low = 0u128;
+        let mut high = self.reserves().real_token_reserves;
+        while low < high {
+            let mid = high - (high - low) / 2;
+            match self.get_buy_price(mid) {
+                Some(cost) if cost <= sol_for_curve => low = mid,
+                _ => high = mid - 1,
+            }
+        }
+
+        if low == 0 {
+            return None;
+        }
+
+        Some(low)
+    }
+
+    /// Inverse of `get_sell_price`: the smallest token amount whose sale pays
+    /// out at least `sol_amount` after a `fee_basis_points` fee on the gross output.
+    fn get_sell_token_amount(&self, sol_amount: u128, fee_basis_points: u128) -> Option<u128> {
+        let gross_sol = sol_with_fee(sol_amount, fee_basis_points)?;
+        if gross_sol > self.reserves().real_sol_reserves {
+            return None;
+        }
+
+        // amounts the curve can't price count as "too much" so the search stays bounded
+        let pays_enough = |tokens: u128| match self.get_sell_price(tokens) {
+            Some(sol) => sol >= gross_sol,
+            None => true,
+        };
+
+        let mut high = 1u128;
+        while !pays_enough(high) {
+            high = high.checked_mul(2)?;
+        }
+
+        let mut low = 1u128;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pays_enough(mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        if self.get_sell_price(low)? < gross_sol {
+            return None;
+        }
+
+        Some(low)
+    }
+}
+
+/// Largest amount that, plus a `fee_basis_points` fee on itself, fits in `sol_amount`.
+pub fn sol_without_fee(sol_amount: u128, fee_basis_points: u128) -> Option<u128> {
+    if sol_amount == 0 {
+        return None;
+    }
+
+    let fee_denominator = 10000u128.checked_add(fee_basis_points)?;
+    let mut sol_for_curve = sol_amount.checked_mul(10000)?.checked_div(fee_denominator)?;
+    // the fee is floored, so the exact-rate estimate can leave a lamport on the table
+    while sol_for_curve < sol_amount
+        && (sol_for_curve + 1).checked_add((sol_for_curve + 1).checked_mul(fee_basis_points)? / 10000)? <= sol_amount
+    {
+        sol_for_curve += 1;
+    }
+
+    if sol_for_curve == 0 {
+        return None;
+    }
+
+    Some(sol_for_curve)
+}
+
+/// Smallest gross amount that still leaves `sol_amount` after a `fee_basis_points` fee on itself.
+pub fn sol_with_fee(sol_amount: u128, fee_basis_points: u128) -> Option<u128> {
+    if sol_amount == 0 || fee_basis_points >= 10000 {
+        return None;
+    }
+
+    let fee_denominator = 10000u128 - fee_basis_points;
+    let mut gross_sol = sol_amount
+        .checked_mul(10000)?
+        .checked_add(fee_denominator - 1)?
+        .checked_div(fee_denominator)?;
+    while gross_sol > sol_amount
+        && (gross_sol - 1) - ((gross_sol - 1).checked_mul(fee_basis_points)? / 10000) >= sol_amount
+    {
+        gross_sol -= 1;
+    }
+
+    Some(gross_sol)
+}
@@ -0,0 +1,51 @@
+use crate::amm::{math, SupplyPricing};
+
+/// Price growing as `start_price * e^(growth * s / token_range)`, so the curve ends
+/// at `start_price * e^growth`. `growth` is scaled by `math::ONE`.
+#[derive(Debug)]
+pub struct ExponentialPricing {
+    pub start_price: u128,
+    pub growth: u128,
+    pub token_range: u128,
+}
+
+impl ExponentialPricing {
+    /// Keeps e^growth well inside u128 once multiplied by prices and supply.
+    pub const MAX_GROWTH: u128 = 20 * math::ONE;
+
+    pub fn new(start_price: u128, growth: u128, token_range: u128) -> Option<Self> {
+        if start_price == 0 || growth > Self::MAX_GROWTH || token_range == 0 {
+            return None;
+        }
+
+        Some(ExponentialPricing {
+            start_price,
+            growth,
+            token_range,
+        })
+    }
+
+    fn exponent(&self, tokens_sold: u128) -> Option<u128> {
+        math::mul_div(self.growth, tokens_sold, self.token_range)
+    }
+}
+
+impl SupplyPricing for ExponentialPricing {
+    fn price_at(&self, tokens_sold: u128) -> Option<u128> {
+        math::mul_div(self.start_price, math::exp(self.exponent(tokens_sold)?)?, math::ONE)
+    }
+
+    fn area_to(&self, tokens_sold: u128) -> Option<u128> {
+        if self.growth == 0 {
+            return self.start_price.checked_mul(tokens_sold);
+        }
+
+        // start * range / growth * (e^(growth * s / range) - 1)
+        let growth_factor = math::exp(self.exponent(tokens_sold)?)?.checked_sub(math::ONE)?;
+        math::mul_div(
+            self.start_price.checked_mul(self.token_range)?,
+            growth_factor,
+            self.growth,
+        )
+    }
+}
@@ -0,0 +1,41 @@
+use crate::amm::{math, SupplyPricing};
+
+/// Price rising linearly from `start_price` to `end_price` over `token_range` tokens sold.
+#[derive(Debug)]
+pub struct LinearPricing {
+    pub start_price: u128,
+    pub end_price: u128,
+    pub token_range: u128,
+}
+
+impl LinearPricing {
+    pub fn new(start_price: u128, end_price: u128, token_range: u128) -> Option<Self> {
+        if start_price == 0 || end_price < start_price || token_range == 0 {
+            return None;
+        }
+
+        Some(LinearPricing {
+            start_price,
+            end_price,
+            token_range,
+        })
+    }
+}
+
+impl SupplyPricing for LinearPricing {
+    fn price_at(&self, tokens_sold: u128) -> Option<u128> {
+        let slope_part = math::mul_div(self.end_price - self.start_price, tokens_sold, self.token_range)?;
+        self.start_price.checked_add(slope_part)
+    }
+
+    fn area_to(&self, tokens_sold: u128) -> Option<u128> {
+        // start * s + (end - start) * s^2 / (2 * range)
+        let flat_part = self.start_price.checked_mul(tokens_sold)?;
+        let slope_part = math::mul_div(
+            (self.end_price - self.start_price).checked_mul(tokens_sold)?,
+            tokens_sold,
+            self.token_range.checked_mul(2)?,
+        )?;
+        flat_part.checked_add(slope_part)
+    }
+}
@@ -0,0 +1,154 @@
+/// Fixed-point one, also the scale of every price returned by the curve models.
+pub const ONE: u128 = 1_000_000_000_000;
+
+/// ln(2) scaled by `ONE`.
+const LN_2: u128 = 693_147_180_560;
+
+/// 128x128 -> 256 bit multiplication, returned as (high, low).
+pub(crate) fn full_mul(a: u128, b: u128) -> (u128, u128) {
+    let mask = u64::MAX as u128;
+    let (a0, a1) = (a & mask, a >> 64);
+    let (b0, b1) = (b & mask, b >> 64);
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = (p00 >> 64) + (p01 & mask) + (p10 & mask);
+    let low = (p00 & mask) | (mid << 64);
+    let high = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+
+    (high, low)
+}
+
+/// (a * b / denominator, remainder) with a 256 bit intermediate product.
+fn mul_div_rem(a: u128, b: u128, denominator: u128) -> Option<(u128, u128)> {
+    if denominator == 0 {
+        return None;
+    }
+
+    let (high, low) = full_mul(a, b);
+    if high == 0 {
+        return Some((low / denominator, low % denominator));
+    }
+    if high >= denominator {
+        return None;
+    }
+
+    let mut quotient = 0u128;
+    let mut remainder = high;
+    for i in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> i) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= denominator {
+            remainder = remainder.wrapping_sub(denominator);
+            quotient |= 1;
+        }
+    }
+
+    Some((quotient, remainder))
+}
+
+/// floor(a * b / denominator), `None` on division by zero or if the result overflows u128.
+pub fn mul_div(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    mul_div_rem(a, b, denominator).map(|(quotient, _)| quotient)
+}
+
+/// ceil(a * b / denominator), `None` on division by zero or if the result overflows u128.
+pub fn mul_div_ceil(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    let (quotient, remainder) = mul_div_rem(a, b, denominator)?;
+    if remainder > 0 {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// floor(sqrt(x)).
+pub fn sqrt(x: u128) -> u128 {
+    if x < 2 {
+        return x;
+    }
+
+    // newton's method from an initial guess above the root
+    let mut root = 1u128 << ((128 - x.leading_zeros()).div_ceil(2));
+    loop {
+        let next = (root + x / root) / 2;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// e^x for a non-negative `x` scaled by `ONE`, result scaled by `ONE`.
+pub fn exp(x: u128) -> Option<u128> {
+    // e^x = 2^k * e^r with r in [0, ln 2)
+    let k = x / LN_2;
+    let r = x - k * LN_2;
+
+    // taylor series for e^r, summed with 6 extra digits so truncation stays below 1 unit
+    let extra = 1_000_000u128;
+    let r = r * extra;
+    let one = ONE * extra;
+    let mut term = one;
+    let mut sum = one;
+    let mut n = 1u128;
+    while term > 0 {
+        term = term.checked_mul(r)? / n.checked_mul(one)?;
+        sum = sum.checked_add(term)?;
+        n += 1;
+    }
+    let sum = sum / extra;
+
+    if k >= sum.leading_zeros() as u128 {
+        return None;
+    }
+
+    Some(sum << k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_div(10, 10, 3), Some(33));
+        assert_eq!(mul_div_ceil(10, 10, 3), Some(34));
+        assert_eq!(mul_div_ceil(10, 9, 3), Some(30));
+        assert_eq!(mul_div(1, 1, 0), None);
+
+        // intermediate product does not fit in u128
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, 6, 3), None);
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90), Some(1 << 110));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(sqrt(0), 0);
+        assert_eq!(sqrt(1), 1);
+        assert_eq!(sqrt(15), 3);
+        assert_eq!(sqrt(16), 4);
+        assert_eq!(sqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(sqrt((1 << 100) - 1), (1 << 50) - 1);
+    }
+
+    #[test]
+    fn test_exp() {
+        assert_eq!(exp(0), Some(ONE));
+
+        // e = 2.718281828459...
+        let e = exp(ONE).unwrap();
+        assert!(e.abs_diff(2_718_281_828_459) <= 2);
+
+        // e^10 = 22026.465794806...
+        let e10 = exp(10 * ONE).unwrap();
+        assert!(e10.abs_diff(22_026_465_794_806_716) <= 1_000_000);
+
+        assert_eq!(exp(100 * ONE), None);
+    }
+}
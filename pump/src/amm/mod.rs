@@ -1,2 +1,16 @@
+#[allow(clippy::module_inception)]
 pub mod amm;
-pub use amm::*;
\ No newline at end of file
+pub mod cpmm;
+pub mod curve_model;
+pub mod exponential;
+pub mod linear;
+pub mod math;
+pub mod sigmoid;
+pub mod supply_curve;
+
+pub use amm::*;
+pub use curve_model::*;
+pub use exponential::*;
+pub use linear::*;
+pub use sigmoid::*;
+pub use supply_curve::*;
@@ -0,0 +1,56 @@
+use crate::amm::{math, SupplyPricing};
+
+/// Piecewise-linear sigmoid: flat at `start_price` until `ramp_start` tokens are
+/// sold, linear up to `end_price` at `ramp_end`, flat at `end_price` afterwards.
+#[derive(Debug)]
+pub struct SigmoidPricing {
+    pub start_price: u128,
+    pub end_price: u128,
+    pub ramp_start: u128,
+    pub ramp_end: u128,
+}
+
+impl SigmoidPricing {
+    pub fn new(start_price: u128, end_price: u128, ramp_start: u128, ramp_end: u128) -> Option<Self> {
+        if start_price == 0 || end_price < start_price || ramp_end <= ramp_start {
+            return None;
+        }
+
+        Some(SigmoidPricing {
+            start_price,
+            end_price,
+            ramp_start,
+            ramp_end,
+        })
+    }
+}
+
+impl SupplyPricing for SigmoidPricing {
+    fn price_at(&self, tokens_sold: u128) -> Option<u128> {
+        let into_ramp = tokens_sold.clamp(self.ramp_start, self.ramp_end) - self.ramp_start;
+        let ramp_part = math::mul_div(
+            self.end_price - self.start_price,
+            into_ramp,
+            self.ramp_end - self.ramp_start,
+        )?;
+        self.start_price.checked_add(ramp_part)
+    }
+
+    fn area_to(&self, tokens_sold: u128) -> Option<u128> {
+        let flat_part = self.start_price.checked_mul(tokens_sold)?;
+
+        // extra area above start_price: a triangle over the ramp, then a rectangle
+        let ramp_width = self.ramp_end - self.ramp_start;
+        let price_rise = self.end_price - self.start_price;
+        let into_ramp = tokens_sold.clamp(self.ramp_start, self.ramp_end) - self.ramp_start;
+        let triangle = math::mul_div(
+            price_rise.checked_mul(into_ramp)?,
+            into_ramp,
+            ramp_width.checked_mul(2)?,
+        )?;
+        let after_ramp = tokens_sold.saturating_sub(self.ramp_end);
+        let rectangle = price_rise.checked_mul(after_ramp)?;
+
+        flat_part.checked_add(triangle)?.checked_add(rectangle)
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt;
+
+use crate::amm::{math, BuyResult, CurveModel, CurveReserves, SellResult};
+
+/// Price as a function of how many tokens the curve has sold.
+pub trait SupplyPricing: fmt::Debug {
+    /// Marginal price once `tokens_sold` tokens are out, in lamports per token
+    /// base unit scaled by `math::ONE`.
+    fn price_at(&self, tokens_sold: u128) -> Option<u128>;
+
+    /// Area under `price_at` from 0 to `tokens_sold`, i.e. the lamports paid for
+    /// the first `tokens_sold` tokens, scaled by `math::ONE`.
+    fn area_to(&self, tokens_sold: u128) -> Option<u128>;
+}
+
+/// Curve whose price only depends on the supply sold. There are no virtual
+/// reserves, so the reported virtual reserves mirror the real ones.
+#[derive(Debug)]
+pub struct SupplyCurve<P: SupplyPricing> {
+    pub pricing: P,
+    pub real_sol_reserves: u128,
+    pub real_token_reserves: u128,
+    pub initial_real_token_reserves: u128,
+}
+
+impl<P: SupplyPricing> SupplyCurve<P> {
+    pub fn new(
+        pricing: P,
+        real_sol_reserves: u128,
+        real_token_reserves: u128,
+        initial_real_token_reserves: u128,
+    ) -> Self {
+        SupplyCurve {
+            pricing,
+            real_sol_reserves,
+            real_token_reserves,
+            initial_real_token_reserves,
+        }
+    }
+
+    pub fn tokens_sold(&self) -> u128 {
+        self.initial_real_token_reserves.saturating_sub(self.real_token_reserves)
+    }
+}
+
+impl<P: SupplyPricing> CurveModel for SupplyCurve<P> {
+    fn get_buy_price(&self, tokens: u128) -> Option<u128> {
+        if tokens == 0 || tokens > self.real_token_reserves {
+            return None;
+        }
+
+        let tokens_sold = self.tokens_sold();
+        let area = self
+            .pricing
+            .area_to(tokens_sold.checked_add(tokens)?)?
+            .checked_sub(self.pricing.area_to(tokens_sold)?)?;
+
+        math::mul_div_ceil(area, 1, math::ONE)
+    }
+
+    fn get_sell_price(&self, tokens: u128) -> Option<u128> {
+        let tokens_sold = self.tokens_sold();
+        if tokens == 0 || tokens > tokens_sold {
+            return None;
+        }
+
+        let area = self
+            .pricing
+            .area_to(tokens_sold)?
+            .checked_sub(self.pricing.area_to(tokens_sold - tokens)?)?;
+
+        Some((area / math::ONE).min(self.real_sol_reserves))
+    }
+
+    fn apply_buy(&mut self, token_amount: u128) -> Option<BuyResult> {
+        let final_token_amount = token_amount.min(self.real_token_reserves);
+
+        let sol_amount = self.get_buy_price(final_token_amount)?;
+
+        self.real_token_reserves = self.real_token_reserves.checked_sub(final_token_amount)?;
+        self.real_sol_reserves = self.real_sol_reserves.checked_add(sol_amount)?;
+
+        Some(BuyResult {
+            token_amount: u64::try_from(final_token_amount).ok()?,
+            sol_amount: u64::try_from(sol_amount).ok()?,
+        })
+    }
+
+    fn apply_sell(&mut self, token_amount: u128) -> Option<SellResult> {
+        let sol_amount = self.get_sell_price(token_amount)?;
+
+        self.real_token_reserves = self.real_token_reserves.checked_add(token_amount)?;
+        self.real_sol_reserves = self.real_sol_reserves.checked_sub(sol_amount)?;
+
+        Some(SellResult {
+            token_amount: u64::try_from(token_amount).ok()?,
+            sol_amount: u64::try_from(sol_amount).ok()?,
+        })
+    }
+
+    fn spot_price(&self) -> Option<u128> {
+        self.pricing.price_at(self.tokens_sold())
+    }
+
+    fn reserves(&self) -> CurveReserves {
+        CurveReserves {
+            virtual_sol_reserves: self.real_sol_reserves,
+            virtual_token_reserves: self.real_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amm::{
+        math, CurveModel, ExponentialPricing, LinearPricing, SigmoidPricing, SupplyCurve, SupplyPricing,
+    };
+
+    const RESERVES: u128 = 1_000_000;
+
+    fn check_round_trip<P: SupplyPricing>(pricing: P) {
+        let mut curve = SupplyCurve::new(pricing, 0, RESERVES, RESERVES);
+
+        let first = curve.apply_buy(100_000).unwrap();
+        let second = curve.apply_buy(100_000).unwrap();
+        // price goes up (or stays flat) as supply is sold
+        assert!(second.sol_amount >= first.sol_amount);
+        assert_eq!(curve.real_token_reserves, RESERVES - 200_000);
+        assert_eq!(curve.real_sol_reserves, (first.sol_amount + second.sol_amount) as u128);
+
+        // selling everything back never pays out more than was paid in
+        let sell_result = curve.apply_sell(200_000).unwrap();
+        assert!(sell_result.sol_amount <= first.sol_amount + second.sol_amount);
+        assert_eq!(curve.real_token_reserves, RESERVES);
+
+        // can't sell more than the curve sold
+        assert_eq!(curve.get_sell_price(1), None);
+    }
+
+    #[test]
+    fn test_linear() {
+        let pricing = LinearPricing::new(math::ONE, 3 * math::ONE, RESERVES).unwrap();
+        assert_eq!(pricing.price_at(0), Some(math::ONE));
+        assert_eq!(pricing.price_at(RESERVES / 2), Some(2 * math::ONE));
+        assert_eq!(pricing.price_at(RESERVES), Some(3 * math::ONE));
+
+        // 1 lamport per token rising to 3: the whole curve costs 2 lamports per token
+        let curve = SupplyCurve::new(pricing, 0, RESERVES, RESERVES);
+        assert_eq!(curve.get_buy_price(RESERVES), Some(2 * RESERVES));
+
+        assert!(LinearPricing::new(3 * math::ONE, math::ONE, RESERVES).is_none());
+        check_round_trip(LinearPricing::new(math::ONE, 3 * math::ONE, RESERVES).unwrap());
+    }
+
+    #[test]
+    fn test_exponential() {
+        let pricing = ExponentialPricing::new(math::ONE, math::ONE, RESERVES).unwrap();
+        assert_eq!(pricing.price_at(0), Some(math::ONE));
+        assert!(pricing.price_at(RESERVES).unwrap().abs_diff(2_718_281_828_459) <= 2);
+
+        // integral of e^x over [0, 1] is e - 1
+        let curve = SupplyCurve::new(pricing, 0, RESERVES, RESERVES);
+        assert!(curve.get_buy_price(RESERVES).unwrap().abs_diff(1_718_282) <= 1);
+
+        // zero growth degenerates into a flat price
+        let flat = ExponentialPricing::new(math::ONE, 0, RESERVES).unwrap();
+        assert_eq!(flat.area_to(RESERVES), Some(RESERVES * math::ONE));
+
+        check_round_trip(ExponentialPricing::new(math::ONE, 2 * math::ONE, RESERVES).unwrap());
+    }
+
+    #[test]
+    fn test_sigmoid() {
+        let pricing = SigmoidPricing::new(math::ONE, 3 * math::ONE, 250_000, 750_000).unwrap();
+        assert_eq!(pricing.price_at(0), Some(math::ONE));
+        assert_eq!(pricing.price_at(250_000), Some(math::ONE));
+        assert_eq!(pricing.price_at(500_000), Some(2 * math::ONE));
+        assert_eq!(pricing.price_at(RESERVES), Some(3 * math::ONE));
+
+        // flat 1, ramp averaging 2, flat 3
+        let curve = SupplyCurve::new(pricing, 0, RESERVES, RESERVES);
+        assert_eq!(curve.get_buy_price(RESERVES), Some(250_000 + 2 * 500_000 + 3 * 250_000));
+
+        assert!(SigmoidPricing::new(math::ONE, 3 * math::ONE, 750_000, 250_000).is_none());
+        check_round_trip(SigmoidPricing::new(math::ONE, 3 * math::ONE, 250_000, 750_000).unwrap());
+    }
+
+    #[test]
+    fn test_inverse_quotes() {
+        let pricing = LinearPricing::new(math::ONE, 3 * math::ONE, RESERVES).unwrap();
+        let mut curve = SupplyCurve::new(pricing, 0, RESERVES, RESERVES);
+
+        let tokens = curve.get_buy_token_amount(10_000, 100).unwrap();
+        let cost = curve.get_buy_price(tokens).unwrap();
+        assert!(cost + cost * 100 / 10000 <= 10_000);
+        let cost = curve.get_buy_price(tokens + 1).unwrap();
+        assert!(cost + cost * 100 / 10000 > 10_000);
+
+        curve.apply_buy(500_000).unwrap();
+        let tokens = curve.get_sell_token_amount(10_000, 100).unwrap();
+        let gross = curve.get_sell_price(tokens).unwrap();
+        assert!(gross - gross * 100 / 10000 >= 10_000);
+        let gross = curve.get_sell_price(tokens - 1).unwrap();
+        assert!(gross - gross * 100 / 10000 < 10_000);
+
+        // more than the curve holds
+        assert_eq!(curve.get_sell_token_amount(u64::MAX as u128, 0), None);
+    }
+}
@@ -1,13 +1,14 @@
-use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_lang::{prelude::*, solana_program::incinerator};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::{
-    amm, calculate_fee,
-    state::{BondingCurve, FeeAccount, Global, UserInviteStats},
-    CompleteEvent, CurveLaunchpadError, TradeEvent,
+    amm::{BuyResult, CurveModel},
+    quote_buy, quote_buy_exact_sol_in, settle_buy,
+    state::{AllowlistProof, BondingCurve, FeeAccount, Global, UserCurveStats, UserInviteStats},
+    CurveLaunchpadError, CurveTrade, SolQuote, SolSource, TradeFees, TradeQuote,
 };
 
 #[derive(Accounts)]
@@ -27,12 +28,13 @@ pub struct Buy<'info> {
         mut,
         address = bonding_curve.mint.key()
     )]
-    mint: Account<'info, Mint>,
+    mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
         bump,
+        constraint = bonding_curve.is_native_quote() @ CurveLaunchpadError::QuoteMintMismatch,
     )]
     bonding_curve: Box<Account<'info, BondingCurve>>,
 
@@ -40,18 +42,36 @@ pub struct Buy<'info> {
         mut,
         token::mint = mint,
         token::authority = bonding_curve,
+        token::token_program = token_program,
         seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
         bump
     )]
-    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    user_token_account: Box<Account<'info, TokenAccount>>,
+    user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserCurveStats::INIT_SPACE,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Box<Account<'info, UserCurveStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,user.key().as_ref()],
+        bump
+    )]
+    user_invite_account: Box<Account<'info, UserInviteStats>>,
 
     #[account(
         mut,
@@ -82,15 +102,23 @@ pub struct Buy<'info> {
     )]
     fee_account : Box<Account<'info,FeeAccount>>,
 
+    /// CHECK: only receives sniper fees, needed when the curve burns them
+    #[account(
+        mut,
+        address = incinerator::ID,
+    )]
+    incinerator: Option<UncheckedAccount<'info>>,
+
     system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
     associated_token_program: Program<'info, AssociatedToken>,
 
 }
 
-pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64, hash: &str) -> Result<()> {
-    //bonding curve is not complete
+pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64, hash: &str, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+    //bonding curve is still trading
     require!(
-        ctx.accounts.bonding_curve.complete == false,
+        ctx.accounts.bonding_curve.is_trading(),
         CurveLaunchpadError::BondingCurveComplete,
     );
 
@@ -104,25 +132,14 @@ pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64, hash: &str)
 
     require!(token_amount > 0, CurveLaunchpadError::MinBuy,);
 
-    let targe_token_amount = if ctx.accounts.bonding_curve_token_account.amount < token_amount {
-        ctx.accounts.bonding_curve_token_account.amount
-    } else {
-        token_amount
-    };
-
-    let mut amm = amm::amm::AMM::new(
-        ctx.accounts.bonding_curve.virtual_sol_reserves as u128,
-        ctx.accounts.bonding_curve.virtual_token_reserves as u128,
-        ctx.accounts.global.initial_virtual_token_reserves as u128,
-    );
-
-    let buy_result = amm.apply_buy(targe_token_amount as u128).unwrap();
-    let fee = calculate_fee(
-        buy_result.sol_amount,
-        ctx.accounts.global.fee_basis_points + ctx.accounts.global.creator_fee_basis_points + ctx.accounts.global.invite_fee_basis_points,
-    );
-    let buy_amount_with_fee = buy_result.sol_amount + fee;
-
+    let quote = quote_buy(
+        &ctx.accounts.bonding_curve,
+        ctx.accounts.bonding_curve_token_account.amount,
+        &ctx.accounts.mint.to_account_info(),
+        token_amount,
+        Clock::get()?.unix_timestamp as u64,
+    )?;
+    let buy_amount_with_fee = quote.buy_result.sol_amount + quote.fees.total_fee;
 
     //check if the amount of SOL to transfe plus fee is less than the max_sol_cost
     require!(
@@ -130,131 +147,74 @@ pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64, hash: &str)
         CurveLaunchpadError::MaxSOLCostExceeded,
     );
 
-    //check if the user has enough SOL
-    require!(
-        ctx.accounts.user.lamports() >= buy_amount_with_fee,
-        CurveLaunchpadError::InsufficientSOL,
-    );
-
-    // transfer SOL to bonding curve
-    let from_account = &ctx.accounts.user;
-    let to_bonding_curve_account = &ctx.accounts.bonding_curve;
+    process_buy(ctx, quote.curve.as_ref(), &quote.buy_result, &quote.fees, hash, allowlist_proof)
+}
 
-    let transfer_instruction = system_instruction::transfer(
-        from_account.key,
-        to_bonding_curve_account.to_account_info().key,
-        buy_result.sol_amount,
+pub fn buy_exact_sol_in(ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64, hash: &str, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+    //bonding curve is still trading
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
     );
 
-    anchor_lang::solana_program::program::invoke_signed(
-        &transfer_instruction,
-        &[
-            from_account.to_account_info(),
-            to_bonding_curve_account.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        &[],
-    )?;
-
-    //transfer SOL to fee account
+    require!(ctx.accounts.user_invite_account.is_init,CurveLaunchpadError::InviteAccountError);
 
-    let transfer_instruction =
-        system_instruction::transfer(from_account.key, ctx.accounts.fee_account.to_account_info().key, fee);
+    require!(sol_amount > 0, CurveLaunchpadError::MinBuy,);
 
-    anchor_lang::solana_program::program::invoke_signed(
-        &transfer_instruction,
-        &[
-            from_account.to_account_info(),
-            ctx.accounts.fee_account.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        &[],
+    let quote = quote_buy_exact_sol_in(
+        &ctx.accounts.bonding_curve,
+        ctx.accounts.bonding_curve_token_account.amount,
+        &ctx.accounts.mint.to_account_info(),
+        sol_amount,
+        Clock::get()?.unix_timestamp as u64,
     )?;
 
-    ctx.accounts.fee_account.received += fee;
-    require!(is_ok,CurveLaunchpadError::FeeAccountStatusAbnormal);
-
-    
-    let protocol_fee = calculate_fee(buy_result.sol_amount, ctx.accounts.global.fee_basis_points);
-    let creator_fee = calculate_fee(buy_result.sol_amount, ctx.accounts.global.creator_fee_basis_points);
-
-
-    ctx.accounts.fee_recipient_invite_account.profit_claimable += protocol_fee;
-    ctx.accounts.creator_fee_recipient_invite_account.profit_claimable += creator_fee;
-    ctx.accounts.parent_invite_account.profit_claimable += invite_fee;
-
-    ctx.accounts.user_invite_account.profit_to_parent += invite_fee;
-    ctx.accounts.parent_invite_account.profit_from_child += invite_fee;
-
+    //min_tokens_out is what reaches the user, after any transfer fee
+    require!(
+        quote.net_token_amount >= min_tokens_out,
+        CurveLaunchpadError::MinTokensOutputExceeded,
+    );
 
+    process_buy(ctx, quote.curve.as_ref(), &quote.buy_result, &quote.fees, hash, allowlist_proof)
+}
 
-    //transfer SPL
-    let cpi_accounts = Transfer {
-        from: ctx
-            .accounts
-            .bonding_curve_token_account
-            .to_account_info()
-            .clone(),
-        to: ctx.accounts.user_token_account.to_account_info().clone(),
-    };
+fn process_buy(
+    ctx: Context<Buy>,
+    curve: &dyn CurveModel,
+    buy_result: &BuyResult,
+    fees: &TradeFees,
+    hash: &str,
+    allowlist_proof: Option<AllowlistProof>,
+) -> Result<()> {
+    let buy_amount_with_fee = buy_result.sol_amount + fees.total_fee;
 
-    let signer: [&[&[u8]]; 1] = [&[
-        BondingCurve::SEED_PREFIX,
-        ctx.accounts.mint.to_account_info().key.as_ref(),
-    ]];
-
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            &signer,
-        ),
-        buy_result.token_amount,
-    )?;
+    //check if the user has enough SOL
+    require!(
+        ctx.accounts.user.lamports() >= buy_amount_with_fee,
+        CurveLaunchpadError::InsufficientSOL,
+    );
 
-    //apply the buy to the bonding curve
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
-    bonding_curve.real_token_reserves = amm.real_token_reserves as u64;
-    bonding_curve.real_sol_reserves = amm.real_sol_reserves as u64;
-    bonding_curve.virtual_token_reserves = amm.virtual_token_reserves as u64;
-    bonding_curve.virtual_sol_reserves = amm.virtual_sol_reserves as u64;
-
-    let curr_time = Clock::get()?.unix_timestamp;
-    bonding_curve.update_time = curr_time as u64;
-
-    let trade_event = TradeEvent {
-        mint: ctx.accounts.mint.to_account_info().key().to_string(),
-        sol_amount: buy_result.sol_amount,
-        token_amount: buy_result.token_amount,
-        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
-        virtual_token_reserves: bonding_curve.virtual_token_reserves,
-        real_sol_reserves: bonding_curve.real_sol_reserves,
-        real_token_reserves: bonding_curve.real_token_reserves,
-        hash: hash.to_string(),
+    let accounts = ctx.accounts;
+    let sol_source = SolSource::Wallet(accounts.user.to_account_info());
+    let mut trade = CurveTrade {
+        trader: accounts.user.key(),
+        mint: &accounts.mint,
+        bonding_curve: &mut accounts.bonding_curve,
+        bonding_curve_bump: ctx.bumps.bonding_curve,
+        bonding_curve_token_account: &mut accounts.bonding_curve_token_account,
+        trader_token_account: &mut accounts.user_token_account,
+        user_curve_stats: &mut accounts.user_curve_stats,
+        quote: TradeQuote::Sol(SolQuote {
+            user_invite_account: &mut accounts.user_invite_account,
+            parent_invite_account: &mut accounts.parent_invite_account,
+            fee_recipient_invite_account: &mut accounts.fee_recipient_invite_account,
+            creator_fee_recipient_invite_account: &mut accounts.creator_fee_recipient_invite_account,
+            fee_account: &mut accounts.fee_account,
+            incinerator: accounts.incinerator.as_ref().map(|incinerator| incinerator.to_account_info()),
+            system_program: accounts.system_program.to_account_info(),
+        }),
+        token_program: accounts.token_program.to_account_info(),
     };
 
-    let serialized = serde_json::to_string(&trade_event).unwrap();
-
-    msg!("tradelog:{}", serialized);
-
-    if bonding_curve.real_token_reserves == 0 {
-
-        let complete_event = CompleteEvent {
-            user: ctx.accounts.user.to_account_info().key().to_string(),
-            mint: ctx.accounts.mint.to_account_info().key().to_string(),
-            bonding_curve: ctx
-                .accounts
-                .bonding_curve
-                .to_account_info()
-                .key()
-                .to_string(),
-            timestamp: curr_time,
-        };
-        let serialized = serde_json::to_string(&complete_event).unwrap();
-        msg!("completelog:{}", serialized);
-    }
-
-    msg!("bonding_curve: {:?}", amm);
-
-    Ok(())
+    settle_buy(&mut trade, &sol_source, curve, buy_result, fees, hash, allowlist_proof)
 }
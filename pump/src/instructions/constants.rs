@@ -1,4 +1,5 @@
 pub const DEFAULT_DECIMALS: u32 = 6;
-pub const DEFAULT_TOKEN_LAMPORTS: u64 = (10 as u64).pow(DEFAULT_DECIMALS);
+pub const DEFAULT_TOKEN_LAMPORTS: u64 = 10_u64.pow(DEFAULT_DECIMALS);
 pub const DEFAULT_TOKEN_SUPPLY: u64 = 1_000_000_000 * DEFAULT_TOKEN_LAMPORTS;
-pub const WSOL_MINT_ADDRESS: &str = "So11111111111111111111111111111111111111112";
\ No newline at end of file
+pub const WSOL_MINT_ADDRESS: &str = "So11111111111111111111111111111111111111112";
+pub const DEFAULT_MINT_SUFFIX: &str = "meme";
\ No newline at end of file
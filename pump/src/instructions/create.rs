@@ -1,15 +1,18 @@
 use crate::{
-    state::{BondingCurve, Global, UserInviteStats}, CreateEvent, CurveLaunchpadError, DEFAULT_DECIMALS
+    calculate_trade_fees, record_launch_buy, transfer_fee,
+    state::{AllowlistParams, AllowlistPhase, AllowlistProof, BondingCurve, CurveKind, CurveStatus, CurveTemplate, FeeAccount, Global, UserCurveStats, UserInviteStats, VestingEscrow}, CreateEvent, CurveLaunchpadError, DEFAULT_DECIMALS
 };
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
+    associated_token::AssociatedToken,
     metadata::{
         create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
         Metadata as Metaplex,
     },
     token::{
-        self, mint_to, spl_token::instruction::AuthorityType, Mint, MintTo, Token, TokenAccount,
+        self, mint_to, spl_token::instruction::AuthorityType, Mint, MintTo, Token,
     },
+    token_interface::{self, TokenAccount, TransferChecked},
 };
 
 
@@ -19,12 +22,15 @@ pub struct InitCreateAccount<'info> {
     #[account(mut)]
     creator: Signer<'info>,
 
+    /// Template the curve will be created with, it sets the mint decimals
+    curve_template: Option<Box<Account<'info, CurveTemplate>>>,
+
     #[account(
         init,
         payer = creator,
         seeds = [ &_seed.to_le_bytes()],
         bump,
-        mint::decimals = DEFAULT_DECIMALS as u8,
+        mint::decimals = curve_template.as_ref().map_or(DEFAULT_DECIMALS as u8, |curve_template| curve_template.decimals),
         mint::authority = bonding_curve,
     )]
     mint: Account<'info, Mint>,
@@ -44,9 +50,11 @@ pub struct InitCreateAccount<'info> {
 
 }
 
-pub fn  init_create_account(_ctx: Context<InitCreateAccount>,
+pub fn  init_create_account(ctx: Context<InitCreateAccount>,
     _seed: u64,
 ) -> Result<()> {
+    //only this creator can set the curve up and create it
+    ctx.accounts.bonding_curve.creator = ctx.accounts.creator.key();
 
     Ok(())
 }
@@ -56,10 +64,10 @@ pub fn  init_create_account(_ctx: Context<InitCreateAccount>,
 pub struct Create<'info> {
     #[account(
         mut,
-        mint::decimals = DEFAULT_DECIMALS as u8,
         mint::authority = bonding_curve,
+        mint::token_program = token_program,
     )]
-    mint: Account<'info, Mint>,
+    mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     #[account(mut)]
     creator: Signer<'info>,
@@ -69,6 +77,7 @@ pub struct Create<'info> {
         mut,
         seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
         bump,
+        has_one = creator @ CurveLaunchpadError::InvalidAuthority,
     )]
     bonding_curve: Box<Account<'info, BondingCurve>>,
 
@@ -77,10 +86,32 @@ pub struct Create<'info> {
         payer = creator,
         token::mint = mint,
         token::authority = bonding_curve,
+        token::token_program = token_program,
         seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
         bump
     )]
-    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Needed when `Global` takes a protocol token allocation.
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + VestingEscrow::INIT_SPACE,
+        seeds = [VestingEscrow::PROTOCOL_SEED_PREFIX, bonding_curve.key().as_ref()],
+        bump,
+    )]
+    protocol_allocation: Option<Box<Account<'info, VestingEscrow>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = protocol_allocation,
+        token::token_program = token_program,
+        seeds=[VestingEscrow::PROTOCOL_SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    protocol_allocation_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(
         seeds = [Global::SEED_PREFIX],
@@ -88,6 +119,8 @@ pub struct Create<'info> {
     )]
     global: Box<Account<'info, Global>>,
 
+    curve_template: Option<Box<Account<'info, CurveTemplate>>>,
+
     ///CHECK: Using seed to validate metadata account
     #[account(
         mut,
@@ -107,6 +140,60 @@ pub struct Create<'info> {
         seeds=[UserInviteStats::SEED_PREFIX,creator.key().as_ref()],
         bump
     )]
+    creator_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,creator_invite_account.parent.key().as_ref()],
+        bump
+    )]
+    parent_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,global.fee_recipient.key().as_ref()],
+        bump
+    )]
+    fee_recipient_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account: Box<Account<'info, FeeAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Set up by `init_vesting_escrow`, receives the dev-buy tokens instead
+    /// of `creator_token_account`.
+    #[account(
+        mut,
+        seeds = [VestingEscrow::SEED_PREFIX, bonding_curve.key().as_ref()],
+        bump,
+    )]
+    vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    #[account(mut)]
+    vesting_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Needed with a dev buy, which counts against the creator's launch and
+    /// allowlist caps like any other buy.
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + UserCurveStats::INIT_SPACE,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), creator.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Option<Box<Account<'info, UserCurveStats>>>,
 
     system_program: Program<'info, System>,
 
@@ -122,6 +209,7 @@ pub struct Create<'info> {
 }
 
 
+#[allow(clippy::too_many_arguments)]
 pub fn create(ctx: Context<Create>,
     name: String,
     symbol: String,
@@ -130,6 +218,13 @@ pub fn create(ctx: Context<Create>,
     website: String,
     telegram: String,
     twitter: String,
+    curve_kind: CurveKind,
+    curve_params: [u64; 4],
+    template_id: Option<u32>,
+    dev_buy_sol: Option<u64>,
+    max_sol_cost: u64,
+    allowlist: Option<AllowlistParams>,
+    allowlist_proof: Option<AllowlistProof>,
 ) -> Result<()> {
     //confirm program is initialized
     require!(
@@ -137,10 +232,19 @@ pub fn create(ctx: Context<Create>,
         CurveLaunchpadError::NotInitialized
     );
 
+    let curve_template = resolve_curve_template(
+        ctx.accounts.curve_template.as_deref().map(|curve_template| &**curve_template),
+        template_id,
+        &ctx.accounts.global,
+    )?;
+    require!(
+        ctx.accounts.mint.decimals == curve_template.decimals,
+        CurveLaunchpadError::InvalidCurveTemplate
+    );
 
-    // Verify that the mint address ends with "meme"
+    // Verify that the mint address ends with the configured suffix
     require!(
-        ctx.accounts.mint.key().to_string().to_lowercase().ends_with("meme"),
+        ctx.accounts.global.mint_suffix_policy.matches(&ctx.accounts.mint.key()),
         CurveLaunchpadError::InvalidMintAddress
     );
 
@@ -165,6 +269,8 @@ pub fn create(ctx: Context<Create>,
         CreateMetadataAccountsV3 {
             payer: ctx.accounts.creator.to_account_info(),
             mint: ctx.accounts.mint.to_account_info(),
+            mint_authority: ctx.accounts.bonding_curve.to_account_info(),
+            update_authority: ctx.accounts.bonding_curve.to_account_info(),
             metadata: ctx.accounts.metadata.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
             rent: ctx.accounts.rent.to_account_info(),
@@ -174,7 +280,12 @@ pub fn create(ctx: Context<Create>,
 
     create_metadata_accounts_v3(metadata_ctx, token_data, false, true, None)?;
 
-    //mint tokens to bonding_curve_token_account
+    let protocol_allocation = ctx
+        .accounts
+        .global
+        .protocol_token_allocation(curve_template.token_total_supply);
+
+    //mint tokens to bonding_curve_token_account, less the protocol allocation
     mint_to(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
@@ -185,8 +296,27 @@ pub fn create(ctx: Context<Create>,
             },
             &signer,
         ),
-        ctx.accounts.global.initial_token_supply,
+        curve_template.token_total_supply - protocol_allocation,
     )?;
+    if protocol_allocation > 0 {
+        let protocol_allocation_token_account = ctx
+            .accounts
+            .protocol_allocation_token_account
+            .as_ref()
+            .ok_or(CurveLaunchpadError::MissingProtocolAllocation)?;
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                    to: protocol_allocation_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+                &signer,
+            ),
+            protocol_allocation,
+        )?;
+    }
 
     //remove mint_authority
     let cpi_context = CpiContext::new_with_signer(
@@ -202,17 +332,57 @@ pub fn create(ctx: Context<Create>,
     let current_timestamp = ctx.accounts.clock.unix_timestamp as u64;
 
 
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
-    bonding_curve.virtual_token_reserves = ctx.accounts.global.initial_virtual_token_reserves;
-    bonding_curve.real_token_reserves = ctx.accounts.global.initial_real_token_reserves;
-    bonding_curve.token_total_supply = ctx.accounts.global.initial_token_supply;
-    bonding_curve.complete = false;
+    init_bonding_curve(
+        &mut ctx.accounts.bonding_curve,
+        &ctx.accounts.global,
+        &curve_template,
+        ctx.accounts.creator.key(),
+        ctx.accounts.mint.key(),
+        curve_kind,
+        curve_params,
+        protocol_allocation,
+        allowlist,
+        current_timestamp,
+    )?;
+    init_protocol_allocation(
+        ctx.accounts.protocol_allocation.as_deref_mut(),
+        ctx.accounts.protocol_allocation_token_account.as_ref().map(|token_account| token_account.key()),
+        &ctx.accounts.global,
+        ctx.accounts.bonding_curve.key(),
+        ctx.accounts.mint.key(),
+        protocol_allocation,
+        current_timestamp,
+    )?;
 
-    bonding_curve.pool_sol_amount = 0;
-    bonding_curve.pool_token_amount = 0;
-    bonding_curve.creator = ctx.accounts.creator.to_account_info().key();
-    bonding_curve.mint = ctx.accounts.mint.to_account_info().key();
-    bonding_curve.create_time = current_timestamp;
+    let accounts = &mut *ctx.accounts;
+    dev_buy(
+        DevBuy {
+            creator: &accounts.creator,
+            mint: &accounts.mint,
+            bonding_curve: &mut accounts.bonding_curve,
+            bonding_curve_bump: ctx.bumps.bonding_curve,
+            bonding_curve_token_account: &accounts.bonding_curve_token_account,
+            creator_token_account: &accounts.creator_token_account,
+            creator_invite_account: &mut accounts.creator_invite_account,
+            parent_invite_account: &mut accounts.parent_invite_account,
+            fee_recipient_invite_account: &mut accounts.fee_recipient_invite_account,
+            fee_account: &mut accounts.fee_account,
+            vesting_escrow: accounts.vesting_escrow.as_deref_mut(),
+            vesting_token_account: accounts.vesting_token_account.as_deref(),
+            user_curve_stats: accounts.user_curve_stats.as_deref_mut(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        },
+        dev_buy_sol,
+        max_sol_cost,
+        allowlist_proof,
+    )?;
+
+    let (vesting_cliff_seconds, vesting_duration_seconds) = ctx
+        .accounts
+        .vesting_escrow
+        .as_ref()
+        .map_or((0, 0), |vesting_escrow| (vesting_escrow.cliff_seconds, vesting_escrow.duration_seconds));
 
 
     let create_event = CreateEvent {
@@ -220,6 +390,7 @@ pub fn create(ctx: Context<Create>,
         symbol:symbol.clone(),
         uri:uri.clone(),
         mint: ctx.accounts.mint.to_account_info().key().to_string(),
+        memecoin_config: ctx.accounts.global.key().to_string(),
         creator: ctx.accounts.creator.to_account_info().key().to_string(),
         created_time: current_timestamp,
         destination: ctx.accounts.bonding_curve_token_account.to_account_info().key().to_string(),
@@ -227,7 +398,14 @@ pub fn create(ctx: Context<Create>,
         website: website.clone(),
         telegram: telegram.clone(),
         twitter: twitter.clone(),
-        decimal: 6,
+        decimal: curve_template.decimals,
+        dev_buy_sol_amount: ctx.accounts.bonding_curve.dev_buy_sol_amount,
+        dev_buy_token_amount: ctx.accounts.bonding_curve.dev_buy_token_amount,
+        vesting_cliff_seconds,
+        vesting_duration_seconds,
+        protocol_token_allocation: protocol_allocation,
+        allowlist_merkle_root: ctx.accounts.bonding_curve.allowlist.merkle_root,
+        allowlist_end_time: ctx.accounts.bonding_curve.allowlist.end_time,
     };
 
     //emit_cpi!(createEvent);
@@ -237,5 +415,285 @@ pub fn create(ctx: Context<Create>,
     msg!("MemecoinCreated:{}", serialized);
 
 
+    Ok(())
+}
+
+/// Accounts a dev buy moves SOL and tokens between, shared by the SPL token
+/// and Token-2022 create paths.
+pub(crate) struct DevBuy<'a, 'info> {
+    pub creator: &'a Signer<'info>,
+    pub mint: &'a InterfaceAccount<'info, token_interface::Mint>,
+    pub bonding_curve: &'a mut Account<'info, BondingCurve>,
+    pub bonding_curve_bump: u8,
+    pub bonding_curve_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub creator_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub creator_invite_account: &'a mut Account<'info, UserInviteStats>,
+    pub parent_invite_account: &'a mut Account<'info, UserInviteStats>,
+    pub fee_recipient_invite_account: &'a mut Account<'info, UserInviteStats>,
+    pub fee_account: &'a mut Account<'info, FeeAccount>,
+    pub vesting_escrow: Option<&'a mut Account<'info, VestingEscrow>>,
+    pub vesting_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub user_curve_stats: Option<&'a mut Account<'info, UserCurveStats>>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Creator's first purchase, made inside `create` so nobody can buy ahead of
+/// it. Priced and charged like `buy_exact_sol_in`, with `dev_buy_sol` fee
+/// inclusive, and not allowed to complete the curve. It is held to the launch
+/// and allowlist caps like any buy. The tokens go to the vesting escrow when
+/// one was set up, which vests what arrives after any transfer fee. Only SOL
+/// curves take a dev buy, quote asset curves are paid through their quote
+/// vault.
+pub(crate) fn dev_buy(
+    accounts: DevBuy,
+    dev_buy_sol: Option<u64>,
+    max_sol_cost: u64,
+    allowlist_proof: Option<AllowlistProof>,
+) -> Result<()> {
+    let Some(dev_buy_sol) = dev_buy_sol else {
+        require!(accounts.vesting_escrow.is_none(), CurveLaunchpadError::InvalidVestingEscrow);
+        return Ok(());
+    };
+    require!(dev_buy_sol > 0, CurveLaunchpadError::MinBuy);
+    require!(accounts.bonding_curve.is_native_quote(), CurveLaunchpadError::QuoteMintMismatch);
+    require!(accounts.creator_invite_account.is_init, CurveLaunchpadError::InviteAccountError);
+
+    let mut curve = accounts
+        .bonding_curve
+        .curve_model()
+        .ok_or(CurveLaunchpadError::InvalidCurveParams)?;
+    let token_amount = curve
+        .get_buy_token_amount(dev_buy_sol as u128, accounts.bonding_curve.fees.total() as u128)
+        .ok_or(CurveLaunchpadError::MinBuy)?;
+    let buy_result = curve
+        .apply_buy(token_amount)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+    require!(buy_result.token_amount > 0, CurveLaunchpadError::MinBuy);
+
+    //the creator's own launch buy pays no sniper fee
+    let fees = calculate_trade_fees(buy_result.sol_amount, &accounts.bonding_curve.fees, 0);
+    let buy_amount_with_fee = buy_result.sol_amount + fees.total_fee;
+    require!(
+        buy_amount_with_fee <= dev_buy_sol && buy_amount_with_fee <= max_sol_cost,
+        CurveLaunchpadError::MaxSOLCostExceeded,
+    );
+
+    let user_curve_stats = accounts
+        .user_curve_stats
+        .ok_or(CurveLaunchpadError::MissingUserCurveStats)?;
+    user_curve_stats.user = accounts.creator.key();
+    user_curve_stats.bonding_curve = accounts.bonding_curve.key();
+    record_launch_buy(
+        accounts.bonding_curve,
+        user_curve_stats,
+        &accounts.creator.key(),
+        &buy_result,
+        accounts.bonding_curve.create_time,
+        allowlist_proof,
+    )?;
+
+    // transfer SOL to the bonding curve and the fee account
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.clone(),
+            system_program::Transfer {
+                from: accounts.creator.to_account_info(),
+                to: accounts.bonding_curve.to_account_info(),
+            },
+        ),
+        buy_result.sol_amount,
+    )?;
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program,
+            system_program::Transfer {
+                from: accounts.creator.to_account_info(),
+                to: accounts.fee_account.to_account_info(),
+            },
+        ),
+        fees.total_fee,
+    )?;
+
+    accounts.fee_account.received += fees.total_fee;
+    let is_ok = accounts.fee_account.check(accounts.fee_account.get_lamports());
+    require!(is_ok, CurveLaunchpadError::FeeAccountStatusAbnormal);
+
+    //the creator fee of the creator's own buy would come straight back to them,
+    //so it goes to the protocol fee recipient instead
+    accounts.fee_recipient_invite_account.profit_claimable += fees.protocol_fee + fees.creator_fee;
+    accounts.parent_invite_account.profit_claimable += fees.invite_fee;
+
+    accounts.creator_invite_account.profit_to_parent += fees.invite_fee;
+    accounts.parent_invite_account.profit_from_child += fees.invite_fee;
+
+    let destination = match (&accounts.vesting_escrow, accounts.vesting_token_account) {
+        (Some(vesting_escrow), Some(vesting_token_account)) => {
+            require!(
+                vesting_escrow.beneficiary == accounts.creator.key()
+                    && vesting_escrow.token_account == vesting_token_account.key(),
+                CurveLaunchpadError::InvalidVestingEscrow
+            );
+            vesting_token_account.to_account_info()
+        }
+        (None, None) => accounts.creator_token_account.to_account_info(),
+        _ => return err!(CurveLaunchpadError::InvalidVestingEscrow),
+    };
+
+    let mint_key = accounts.mint.key();
+    let signer: [&[&[u8]]; 1] = [&[
+        BondingCurve::SEED_PREFIX,
+        mint_key.as_ref(),
+        &[accounts.bonding_curve_bump],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program,
+            TransferChecked {
+                from: accounts.bonding_curve_token_account.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                to: destination,
+                authority: accounts.bonding_curve.to_account_info(),
+            },
+            &signer,
+        ),
+        buy_result.token_amount,
+        accounts.mint.decimals,
+    )?;
+    let net_token_amount = buy_result.token_amount - transfer_fee(&accounts.mint.to_account_info(), buy_result.token_amount)?;
+
+    let bonding_curve = accounts.bonding_curve;
+    let reserves = curve.reserves();
+    bonding_curve.real_token_reserves -= buy_result.token_amount;
+    bonding_curve.real_sol_reserves = reserves.real_sol_reserves as u64;
+    bonding_curve.virtual_token_reserves = reserves.virtual_token_reserves as u64;
+    bonding_curve.virtual_sol_reserves = reserves.virtual_sol_reserves as u64;
+    bonding_curve.dev_buy_sol_amount = buy_result.sol_amount;
+    bonding_curve.dev_buy_token_amount = buy_result.token_amount;
+
+    require!(!bonding_curve.reached_graduation(), CurveLaunchpadError::DevBuyTooLarge);
+
+    if let Some(vesting_escrow) = accounts.vesting_escrow {
+        vesting_escrow.total_amount = net_token_amount;
+        vesting_escrow.start_time = bonding_curve.create_time;
+    }
+
+    Ok(())
+}
+
+/// Template a curve launches with: the passed template account, which must
+/// match `template_id`, or `Global`'s parameters when neither is given.
+pub(crate) fn resolve_curve_template(
+    curve_template: Option<&CurveTemplate>,
+    template_id: Option<u32>,
+    global: &Global,
+) -> Result<CurveTemplate> {
+    match (curve_template, template_id) {
+        (Some(curve_template), Some(template_id)) => {
+            require!(curve_template.id == template_id, CurveLaunchpadError::InvalidCurveTemplate);
+            require!(curve_template.enabled, CurveLaunchpadError::CurveTemplateDisabled);
+            Ok(curve_template.clone())
+        }
+        (None, None) => Ok(CurveTemplate::from_global(global)),
+        _ => err!(CurveLaunchpadError::InvalidCurveTemplate),
+    }
+}
+
+/// Opens a freshly minted curve for trading, shared by the SPL token and
+/// Token-2022 launch paths. Every launch parameter is copied from the
+/// template, nothing on the curve reads `Global`'s afterwards. The
+/// `protocol_allocation` minted to the protocol escrow is not for sale.
+/// With an `allowlist`, only its wallets can buy until the phase ends.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn init_bonding_curve(
+    bonding_curve: &mut BondingCurve,
+    global: &Global,
+    curve_template: &CurveTemplate,
+    creator: Pubkey,
+    mint: Pubkey,
+    curve_kind: CurveKind,
+    curve_params: [u64; 4],
+    protocol_allocation: u64,
+    allowlist: Option<AllowlistParams>,
+    create_time: u64,
+) -> Result<()> {
+    //quote asset curves keep the virtual reserves init_quote_vault set, in quote units,
+    //and have no SOL denominated target or SOL buy cap
+    bonding_curve.launch_protection = curve_template.launch_protection;
+    bonding_curve.sandwich_protection = curve_template.sandwich_protection;
+    if bonding_curve.is_native_quote() {
+        bonding_curve.virtual_sol_reserves = curve_template.virtual_sol_reserves;
+        bonding_curve.graduation_sol_target = curve_template.graduation_sol_target;
+    } else {
+        bonding_curve.launch_protection.max_buy_sol = 0;
+    }
+    bonding_curve.virtual_token_reserves = curve_template.virtual_token_reserves;
+    bonding_curve.real_token_reserves = curve_template.real_token_reserves;
+    bonding_curve.initial_real_token_reserves = curve_template.real_token_reserves;
+    bonding_curve.token_total_supply = curve_template.token_total_supply;
+    bonding_curve.template_id = curve_template.id;
+    bonding_curve.fees = curve_template.fees(global);
+    require!(
+        bonding_curve.fees.total() + bonding_curve.launch_protection.sniper_fee.start_basis_points < 10000,
+        CurveLaunchpadError::InvalidAmmParams
+    );
+    bonding_curve.carve_out_protocol_allocation(protocol_allocation)?;
+    bonding_curve.complete = false;
+    bonding_curve.status = CurveStatus::Trading;
+
+    bonding_curve.curve_kind = curve_kind;
+    bonding_curve.curve_params = curve_params;
+    if curve_kind != CurveKind::ConstantProduct {
+        //supply priced curves have no virtual liquidity
+        bonding_curve.virtual_token_reserves = bonding_curve.real_token_reserves;
+    }
+    require!(
+        bonding_curve.curve_model().is_some(),
+        CurveLaunchpadError::InvalidCurveParams
+    );
+
+    bonding_curve.pool_sol_amount = 0;
+    bonding_curve.pool_token_amount = 0;
+    bonding_curve.creator = creator;
+    bonding_curve.mint = mint;
+    bonding_curve.create_time = create_time;
+
+    if let Some(allowlist) = allowlist {
+        bonding_curve.allowlist = AllowlistPhase::new(&allowlist, create_time)?;
+    }
+
+    Ok(())
+}
+
+/// Records the protocol's share of a launch in its escrow. It vests on the
+/// terms `Global` has at create time and goes to the allocation recipient.
+/// Launches without a share need no escrow.
+pub(crate) fn init_protocol_allocation(
+    protocol_allocation: Option<&mut Account<VestingEscrow>>,
+    token_account: Option<Pubkey>,
+    global: &Global,
+    bonding_curve: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    create_time: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let (Some(protocol_allocation), Some(token_account)) = (protocol_allocation, token_account) else {
+        return err!(CurveLaunchpadError::MissingProtocolAllocation);
+    };
+
+    protocol_allocation.bonding_curve = bonding_curve;
+    protocol_allocation.mint = mint;
+    protocol_allocation.beneficiary = global.protocol_token_alloc_recipient;
+    protocol_allocation.token_account = token_account;
+    protocol_allocation.total_amount = amount;
+    protocol_allocation.claimed_amount = 0;
+    protocol_allocation.start_time = create_time;
+    protocol_allocation.cliff_seconds = global.protocol_token_alloc_cliff_seconds;
+    protocol_allocation.duration_seconds = global.protocol_token_alloc_vesting_seconds;
+
     Ok(())
 }
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    amm::cpmm,
+    state::{BondingCurve, Pool},
+    CurveLaunchpadError, PoolCreateEvent, DEFAULT_DECIMALS,
+};
+
+#[derive(Accounts)]
+pub struct CreatePool<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    #[account(
+        address = bonding_curve.mint.key()
+    )]
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        constraint = bonding_curve.is_native_quote() @ CurveLaunchpadError::QuoteMintMismatch,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [Pool::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        space = 8 + Pool::INIT_SPACE,
+    )]
+    pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program,
+        seeds=[Pool::SEED_PREFIX, mint.key().as_ref(), pool.key().as_ref()],
+        bump
+    )]
+    pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [Pool::LP_MINT_SEED_PREFIX, pool.key().as_ref()],
+        bump,
+        mint::decimals = DEFAULT_DECIMALS as u8,
+        mint::authority = pool,
+        mint::token_program = token_program,
+    )]
+    lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    rent: Sysvar<'info, Rent>,
+}
+
+/// Moves the liquidity snapshotted by a completed curve into a new pool.
+/// Anyone can crank it once the curve is complete. Pools are priced in SOL,
+/// so quote asset curves migrate through `withdraw` instead. The pool is
+/// seeded in this instruction, so the curve goes through `Migrating` to
+/// `Migrated` at once.
+pub fn create_pool(ctx: Context<CreatePool>) -> Result<()> {
+    ctx.accounts.bonding_curve.start_migration()?;
+
+    let pool_sol_amount = ctx.accounts.bonding_curve.pool_sol_amount;
+    let pool_token_amount = ctx.accounts.bonding_curve.pool_token_amount;
+
+    //transfer the pool tokens
+    let cpi_accounts = TransferChecked {
+        from: ctx
+            .accounts
+            .bonding_curve_token_account
+            .to_account_info()
+            .clone(),
+        mint: ctx.accounts.mint.to_account_info().clone(),
+        to: ctx.accounts.pool_token_account.to_account_info().clone(),
+        authority: ctx.accounts.bonding_curve.to_account_info().clone(),
+    };
+
+    let signer: [&[&[u8]]; 1] = [&[
+        BondingCurve::SEED_PREFIX,
+        ctx.accounts.mint.to_account_info().key.as_ref(),
+        &[ctx.bumps.bonding_curve],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &signer,
+        ),
+        pool_token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    //the pool is credited with what arrived after any transfer fee
+    ctx.accounts.pool_token_account.reload()?;
+    let pool_token_amount = ctx.accounts.pool_token_account.amount;
+
+    let lp_supply = cpmm::initial_lp_supply(pool_sol_amount, pool_token_amount)
+        .ok_or(CurveLaunchpadError::InsufficientLiquidity)?;
+
+    //transfer the pool SOL
+    **ctx.accounts.bonding_curve.to_account_info().try_borrow_mut_lamports()? -= pool_sol_amount;
+    **ctx.accounts.pool.to_account_info().try_borrow_mut_lamports()? += pool_sol_amount;
+
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.real_sol_reserves = bonding_curve
+        .real_sol_reserves
+        .checked_sub(pool_sol_amount)
+        .ok_or(CurveLaunchpadError::InsufficientSOL)?;
+    bonding_curve.finish_migration()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.mint = ctx.accounts.mint.key();
+    pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.creator = bonding_curve.creator;
+    pool.sol_reserves = pool_sol_amount;
+    pool.token_reserves = pool_token_amount;
+    pool.lp_supply = lp_supply;
+    pool.locked_lp_supply = lp_supply;
+    pool.create_time = current_timestamp;
+    pool.update_time = current_timestamp;
+
+    let pool_create_event = PoolCreateEvent {
+        mint: pool.mint.to_string(),
+        pool: pool.key().to_string(),
+        lp_mint: pool.lp_mint.to_string(),
+        sol_reserves: pool.sol_reserves,
+        token_reserves: pool.token_reserves,
+        locked_lp_supply: pool.locked_lp_supply,
+        timestamp: current_timestamp as i64,
+    };
+    let serialized = serde_json::to_string(&pool_create_event).unwrap();
+    msg!("poolcreatelog:{}", serialized);
+
+    Ok(())
+}
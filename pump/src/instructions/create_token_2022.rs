@@ -0,0 +1,522 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::program::{invoke, invoke_signed},
+    system_program::{self, CreateAccount, Transfer},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            metadata_pointer,
+            transfer_fee::{self, MAX_FEE_BASIS_POINTS},
+            ExtensionType,
+        },
+        instruction::AuthorityType,
+    },
+    token_interface::{
+        self, initialize_mint2, mint_to, InitializeMint2, Mint, MintTo, Token2022, TokenAccount,
+    },
+};
+use spl_pod::optional_keys::OptionalNonZeroPubkey;
+use spl_token_metadata_interface::state::TokenMetadata;
+
+use crate::{
+    dev_buy, init_bonding_curve, init_protocol_allocation, resolve_curve_template,
+    state::{
+        AllowlistParams, AllowlistProof, BondingCurve, CurveKind, CurveTemplate, FeeAccount, Global, UserCurveStats,
+        UserInviteStats, VestingEscrow,
+    },
+    CreateEvent, CurveLaunchpadError, DevBuy, DEFAULT_DECIMALS,
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct InitCreateAccountToken2022<'info> {
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    /// Template the curve will be created with, it sets the mint decimals
+    curve_template: Option<Box<Account<'info, CurveTemplate>>>,
+
+    /// CHECK: created as a Token-2022 mint in the handler
+    #[account(
+        mut,
+        seeds = [&seed.to_le_bytes()],
+        bump,
+    )]
+    mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [BondingCurve::SEED_PREFIX, mint.key().as_ref()],
+        bump,
+        space = 8 + BondingCurve::INIT_SPACE,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token2022>,
+}
+
+/// Opt-in transfer fee for a Token-2022 launch. The creator can withdraw what
+/// gets withheld, nobody can change the fee once the mint exists.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TransferFeeParams {
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+/// Token-2022 counterpart of `init_create_account`. The mint points its
+/// metadata at itself, `create_token_2022` writes the metadata.
+pub fn init_create_account_token_2022(
+    ctx: Context<InitCreateAccountToken2022>,
+    seed: u64,
+    transfer_fee: Option<TransferFeeParams>,
+) -> Result<()> {
+    if let Some(transfer_fee) = transfer_fee {
+        require!(
+            transfer_fee.transfer_fee_basis_points <= MAX_FEE_BASIS_POINTS,
+            CurveLaunchpadError::InvalidTransferFee,
+        );
+    }
+
+    //only this creator can set the curve up and create it
+    ctx.accounts.bonding_curve.creator = ctx.accounts.creator.key();
+
+    let mint = &ctx.accounts.mint;
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let token_program = &ctx.accounts.token_program;
+
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if transfer_fee.is_some() {
+        extensions.push(ExtensionType::TransferFeeConfig);
+    }
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)?;
+
+    let seed_bytes = seed.to_le_bytes();
+    let signer: [&[&[u8]]; 1] = [&[&seed_bytes, &[ctx.bumps.mint]]];
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.creator.to_account_info(),
+                to: mint.to_account_info(),
+            },
+            &signer,
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        token_program.key,
+    )?;
+
+    //extensions have to be initialized before the mint itself
+    invoke(
+        &metadata_pointer::instruction::initialize(
+            token_program.key,
+            mint.key,
+            Some(bonding_curve.key()),
+            Some(mint.key()),
+        )?,
+        &[mint.to_account_info()],
+    )?;
+
+    if let Some(transfer_fee) = transfer_fee {
+        invoke(
+            &transfer_fee::instruction::initialize_transfer_fee_config(
+                token_program.key,
+                mint.key,
+                None,
+                Some(&ctx.accounts.creator.key()),
+                transfer_fee.transfer_fee_basis_points,
+                transfer_fee.maximum_fee,
+            )?,
+            &[mint.to_account_info()],
+        )?;
+    }
+
+    initialize_mint2(
+        CpiContext::new(
+            token_program.to_account_info(),
+            InitializeMint2 {
+                mint: mint.to_account_info(),
+            },
+        ),
+        ctx.accounts
+            .curve_template
+            .as_ref()
+            .map_or(DEFAULT_DECIMALS as u8, |curve_template| curve_template.decimals),
+        &bonding_curve.key(),
+        None,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateToken2022<'info> {
+    #[account(
+        mut,
+        mint::authority = bonding_curve,
+        mint::token_program = token_program,
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.key().as_ref()],
+        bump,
+        has_one = creator @ CurveLaunchpadError::InvalidAuthority,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Needed when `Global` takes a protocol token allocation.
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + VestingEscrow::INIT_SPACE,
+        seeds = [VestingEscrow::PROTOCOL_SEED_PREFIX, bonding_curve.key().as_ref()],
+        bump,
+    )]
+    protocol_allocation: Option<Box<Account<'info, VestingEscrow>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = protocol_allocation,
+        token::token_program = token_program,
+        seeds=[VestingEscrow::PROTOCOL_SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    protocol_allocation_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    curve_template: Option<Box<Account<'info, CurveTemplate>>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,creator.key().as_ref()],
+        bump
+    )]
+    creator_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,creator_invite_account.parent.key().as_ref()],
+        bump
+    )]
+    parent_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,global.fee_recipient.key().as_ref()],
+        bump
+    )]
+    fee_recipient_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account: Box<Account<'info, FeeAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Set up by `init_vesting_escrow`, receives the dev-buy tokens instead
+    /// of `creator_token_account`.
+    #[account(
+        mut,
+        seeds = [VestingEscrow::SEED_PREFIX, bonding_curve.key().as_ref()],
+        bump,
+    )]
+    vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    #[account(mut)]
+    vesting_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Needed with a dev buy, which counts against the creator's launch and
+    /// allowlist caps like any other buy.
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + UserCurveStats::INIT_SPACE,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), creator.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Option<Box<Account<'info, UserCurveStats>>>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token2022>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Token-2022 counterpart of `create`. The name, symbol and uri live in the
+/// mint's TokenMetadata extension instead of a Metaplex metadata account,
+/// and are made immutable like the Metaplex ones.
+#[allow(clippy::too_many_arguments)]
+pub fn create_token_2022(ctx: Context<CreateToken2022>,
+    name: String,
+    symbol: String,
+    uri: String,
+    description: String,
+    website: String,
+    telegram: String,
+    twitter: String,
+    curve_kind: CurveKind,
+    curve_params: [u64; 4],
+    template_id: Option<u32>,
+    dev_buy_sol: Option<u64>,
+    max_sol_cost: u64,
+    allowlist: Option<AllowlistParams>,
+    allowlist_proof: Option<AllowlistProof>,
+) -> Result<()> {
+    //confirm program is initialized
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    let curve_template = resolve_curve_template(
+        ctx.accounts.curve_template.as_deref().map(|curve_template| &**curve_template),
+        template_id,
+        &ctx.accounts.global,
+    )?;
+    require!(
+        ctx.accounts.mint.decimals == curve_template.decimals,
+        CurveLaunchpadError::InvalidCurveTemplate
+    );
+
+    // Verify that the mint address ends with the configured suffix
+    require!(
+        ctx.accounts.global.mint_suffix_policy.matches(&ctx.accounts.mint.key()),
+        CurveLaunchpadError::InvalidMintAddress
+    );
+
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let bonding_curve_info = ctx.accounts.bonding_curve.to_account_info();
+    let token_program = &ctx.accounts.token_program;
+
+    let signer: [&[&[u8]]; 1] = [&[
+        BondingCurve::SEED_PREFIX,
+        mint_info.key.as_ref(),
+        &[ctx.bumps.bonding_curve],
+    ]];
+
+    //the metadata extension grows the mint, which has to be rent exempt for it first
+    let token_metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(bonding_curve_info.key()))?,
+        mint: mint_info.key(),
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+    let mint_len = mint_info.data_len() + token_metadata.tlv_size_of()?;
+    let rent_top_up = Rent::get()?
+        .minimum_balance(mint_len)
+        .saturating_sub(mint_info.lamports());
+    if rent_top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: mint_info.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+
+    invoke_signed(
+        &spl_token_metadata_interface::instruction::initialize(
+            token_program.key,
+            mint_info.key,
+            bonding_curve_info.key,
+            mint_info.key,
+            bonding_curve_info.key,
+            name.clone(),
+            symbol.clone(),
+            uri.clone(),
+        ),
+        &[mint_info.clone(), bonding_curve_info.clone()],
+        &signer,
+    )?;
+
+    //drop the update authority, same as a non-mutable Metaplex metadata
+    invoke_signed(
+        &spl_token_metadata_interface::instruction::update_authority(
+            token_program.key,
+            mint_info.key,
+            bonding_curve_info.key,
+            OptionalNonZeroPubkey::default(),
+        ),
+        &[mint_info.clone(), bonding_curve_info.clone()],
+        &signer,
+    )?;
+
+    let protocol_allocation = ctx
+        .accounts
+        .global
+        .protocol_token_allocation(curve_template.token_total_supply);
+
+    //mint tokens to bonding_curve_token_account, less the protocol allocation
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                authority: bonding_curve_info.clone(),
+                to: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                mint: mint_info.clone(),
+            },
+            &signer,
+        ),
+        curve_template.token_total_supply - protocol_allocation,
+    )?;
+    if protocol_allocation > 0 {
+        let protocol_allocation_token_account = ctx
+            .accounts
+            .protocol_allocation_token_account
+            .as_ref()
+            .ok_or(CurveLaunchpadError::MissingProtocolAllocation)?;
+        mint_to(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                MintTo {
+                    authority: bonding_curve_info.clone(),
+                    to: protocol_allocation_token_account.to_account_info(),
+                    mint: mint_info.clone(),
+                },
+                &signer,
+            ),
+            protocol_allocation,
+        )?;
+    }
+
+    //remove mint_authority
+    token_interface::set_authority(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_interface::SetAuthority {
+                current_authority: bonding_curve_info.clone(),
+                account_or_mint: mint_info.clone(),
+            },
+            &signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+
+    init_bonding_curve(
+        &mut ctx.accounts.bonding_curve,
+        &ctx.accounts.global,
+        &curve_template,
+        ctx.accounts.creator.key(),
+        ctx.accounts.mint.key(),
+        curve_kind,
+        curve_params,
+        protocol_allocation,
+        allowlist,
+        current_timestamp,
+    )?;
+    init_protocol_allocation(
+        ctx.accounts.protocol_allocation.as_deref_mut(),
+        ctx.accounts.protocol_allocation_token_account.as_ref().map(|token_account| token_account.key()),
+        &ctx.accounts.global,
+        ctx.accounts.bonding_curve.key(),
+        ctx.accounts.mint.key(),
+        protocol_allocation,
+        current_timestamp,
+    )?;
+
+    let accounts = &mut *ctx.accounts;
+    dev_buy(
+        DevBuy {
+            creator: &accounts.creator,
+            mint: &accounts.mint,
+            bonding_curve: &mut accounts.bonding_curve,
+            bonding_curve_bump: ctx.bumps.bonding_curve,
+            bonding_curve_token_account: &accounts.bonding_curve_token_account,
+            creator_token_account: &accounts.creator_token_account,
+            creator_invite_account: &mut accounts.creator_invite_account,
+            parent_invite_account: &mut accounts.parent_invite_account,
+            fee_recipient_invite_account: &mut accounts.fee_recipient_invite_account,
+            fee_account: &mut accounts.fee_account,
+            vesting_escrow: accounts.vesting_escrow.as_deref_mut(),
+            vesting_token_account: accounts.vesting_token_account.as_deref(),
+            user_curve_stats: accounts.user_curve_stats.as_deref_mut(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        },
+        dev_buy_sol,
+        max_sol_cost,
+        allowlist_proof,
+    )?;
+
+    let (vesting_cliff_seconds, vesting_duration_seconds) = ctx
+        .accounts
+        .vesting_escrow
+        .as_ref()
+        .map_or((0, 0), |vesting_escrow| (vesting_escrow.cliff_seconds, vesting_escrow.duration_seconds));
+
+    let create_event = CreateEvent {
+        name,
+        symbol,
+        uri,
+        mint: ctx.accounts.mint.key().to_string(),
+        memecoin_config: ctx.accounts.bonding_curve.key().to_string(),
+        creator: ctx.accounts.creator.key().to_string(),
+        created_time: current_timestamp,
+        destination: ctx.accounts.bonding_curve_token_account.key().to_string(),
+        description,
+        website,
+        telegram,
+        twitter,
+        decimal: curve_template.decimals,
+        dev_buy_sol_amount: ctx.accounts.bonding_curve.dev_buy_sol_amount,
+        dev_buy_token_amount: ctx.accounts.bonding_curve.dev_buy_token_amount,
+        vesting_cliff_seconds,
+        vesting_duration_seconds,
+        protocol_token_allocation: protocol_allocation,
+        allowlist_merkle_root: ctx.accounts.bonding_curve.allowlist.merkle_root,
+        allowlist_end_time: ctx.accounts.bonding_curve.allowlist.end_time,
+    };
+
+    let serialized = serde_json::to_string(&create_event).unwrap();
+
+    msg!("MemecoinCreated:{}", serialized);
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{CurveTemplate, FeeBasisPoints, Global, LaunchProtection, SandwichProtection},
+    CurveLaunchpadError,
+};
+
+#[derive(Accounts)]
+#[instruction(template_id: u32)]
+pub struct SetCurveTemplate<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CurveTemplate::INIT_SPACE,
+        seeds = [CurveTemplate::SEED_PREFIX, template_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    curve_template: Box<Account<'info, CurveTemplate>>,
+
+    system_program: Program<'info, System>,
+}
+
+/// Creates or updates a curve template. Curves already created from it keep
+/// the parameters they were created with.
+#[allow(clippy::too_many_arguments)]
+pub fn set_curve_template(
+    ctx: Context<SetCurveTemplate>,
+    template_id: u32,
+    enabled: bool,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    real_token_reserves: u64,
+    token_total_supply: u64,
+    decimals: u8,
+    fee_override: Option<FeeBasisPoints>,
+    graduation_sol_target: u64,
+    launch_protection: LaunchProtection,
+    sandwich_protection: SandwichProtection,
+) -> Result<()> {
+    require!(ctx.accounts.global.initialized, CurveLaunchpadError::NotInitialized);
+
+    let curve_template = &mut ctx.accounts.curve_template;
+    curve_template.id = template_id;
+    curve_template.enabled = enabled;
+    curve_template.virtual_sol_reserves = virtual_sol_reserves;
+    curve_template.virtual_token_reserves = virtual_token_reserves;
+    curve_template.real_token_reserves = real_token_reserves;
+    curve_template.token_total_supply = token_total_supply;
+    curve_template.decimals = decimals;
+    curve_template.fee_override = fee_override;
+    curve_template.graduation_sol_target = graduation_sol_target;
+    curve_template.launch_protection = launch_protection;
+    curve_template.sandwich_protection = sandwich_protection;
+    curve_template.validate()?;
+    curve_template.validate_protocol_allocation(&ctx.accounts.global)?;
+
+    msg!("Set curve template {} enabled: {}", template_id, enabled);
+
+    Ok(())
+}
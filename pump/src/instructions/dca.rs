@@ -0,0 +1,342 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{incinerator, system_instruction},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    quote_buy_exact_sol_in, settle_buy,
+    state::{AllowlistProof, BondingCurve, DcaVault, FeeAccount, Global, UserCurveStats, UserInviteStats},
+    CurveLaunchpadError, CurveTrade, DcaVaultEvent, SolQuote, SolSource, TradeQuote,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct OpenDcaVault<'info> {
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    #[account(address = bonding_curve.mint.key())]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        constraint = bonding_curve.is_native_quote() @ CurveLaunchpadError::QuoteMintMismatch,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DcaVault::INIT_SPACE,
+        seeds = [DcaVault::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref(), &id.to_le_bytes()],
+        bump,
+    )]
+    dca_vault: Box<Account<'info, DcaVault>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + UserCurveStats::INIT_SPACE,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Box<Account<'info, UserCurveStats>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Opens a DCA vault funded with `deposit` lamports. The first purchase is
+/// due right away, then one every `interval_seconds`. A due purchase waits
+/// while it would average above `max_price`.
+pub fn open_dca_vault(
+    ctx: Context<OpenDcaVault>,
+    id: u64,
+    deposit: u64,
+    interval_seconds: u64,
+    amount_per_interval: u64,
+    max_price: u128,
+) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+    DcaVault::validate_terms(deposit, interval_seconds, amount_per_interval, max_price)?;
+
+    let owner = &ctx.accounts.owner;
+    let dca_vault_info = ctx.accounts.dca_vault.to_account_info();
+    anchor_lang::solana_program::program::invoke(
+        &system_instruction::transfer(owner.key, dca_vault_info.key, deposit),
+        &[
+            owner.to_account_info(),
+            dca_vault_info,
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let dca_vault = &mut ctx.accounts.dca_vault;
+    dca_vault.owner = owner.key();
+    dca_vault.bonding_curve = ctx.accounts.bonding_curve.key();
+    dca_vault.mint = ctx.accounts.mint.key();
+    dca_vault.id = id;
+    dca_vault.balance = deposit;
+    dca_vault.interval_seconds = interval_seconds;
+    dca_vault.amount_per_interval = amount_per_interval;
+    dca_vault.max_price = max_price;
+    dca_vault.next_purchase_time = timestamp as u64;
+    dca_vault.purchases = 0;
+    dca_vault.total_sol_spent = 0;
+    dca_vault.total_tokens_bought = 0;
+
+    log_dca_vault(dca_vault, deposit, 0, timestamp, "openDca");
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDca<'info> {
+    #[account(mut)]
+    keeper: Signer<'info>,
+
+    /// CHECK: receives the vault's lamports when it closes
+    #[account(
+        mut,
+        address = dca_vault.owner,
+    )]
+    owner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        address = bonding_curve.mint.key()
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        constraint = bonding_curve.is_native_quote() @ CurveLaunchpadError::QuoteMintMismatch,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = bonding_curve,
+        seeds = [DcaVault::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref(), &dca_vault.id.to_le_bytes()],
+        bump,
+    )]
+    dca_vault: Box<Account<'info, DcaVault>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Box<Account<'info, UserCurveStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,owner.key().as_ref()],
+        bump
+    )]
+    user_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,user_invite_account.parent.key().as_ref()],
+        bump
+    )]
+    parent_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,global.fee_recipient.key().as_ref()],
+        bump
+    )]
+    fee_recipient_invite_account: Box<Account<'info,UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,bonding_curve.creator.key().as_ref()],
+        bump
+    )]
+    creator_fee_recipient_invite_account: Box<Account<'info,UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account : Box<Account<'info,FeeAccount>>,
+
+    /// CHECK: only receives sniper fees, needed when the curve burns them
+    #[account(
+        mut,
+        address = incinerator::ID,
+    )]
+    incinerator: Option<UncheckedAccount<'info>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless crank buying a due interval of a DCA vault through the same
+/// settlement as `buy_exact_sol_in`. The vault closes to its owner once it
+/// can't pay for another interval or the curve has completed. During an
+/// allowlist phase the keeper passes the owner's proof.
+pub fn execute_dca(ctx: Context<ExecuteDca>, hash: &str, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    //nothing left to buy, hand the balance back
+    if !ctx.accounts.bonding_curve.is_trading() {
+        return close_dca_vault(&ctx.accounts.dca_vault, ctx.accounts.owner.to_account_info(), timestamp);
+    }
+
+    require!(ctx.accounts.user_invite_account.is_init,CurveLaunchpadError::InviteAccountError);
+    require!(
+        ctx.accounts.dca_vault.is_due(timestamp as u64),
+        CurveLaunchpadError::DcaNotDue,
+    );
+
+    let quote = quote_buy_exact_sol_in(
+        &ctx.accounts.bonding_curve,
+        ctx.accounts.bonding_curve_token_account.amount,
+        &ctx.accounts.mint.to_account_info(),
+        ctx.accounts.dca_vault.amount_per_interval,
+        timestamp as u64,
+    )?;
+
+    //bounded by the owner's price, not by the spot price the keeper can move
+    require!(
+        ctx.accounts
+            .dca_vault
+            .honors_max_price(quote.buy_result.sol_amount, quote.buy_result.token_amount),
+        CurveLaunchpadError::DcaPriceExceeded,
+    );
+
+    let accounts = ctx.accounts;
+    let sol_source = SolSource::Escrow(accounts.dca_vault.to_account_info());
+    let mut trade = CurveTrade {
+        trader: accounts.owner.key(),
+        mint: &accounts.mint,
+        bonding_curve: &mut accounts.bonding_curve,
+        bonding_curve_bump: ctx.bumps.bonding_curve,
+        bonding_curve_token_account: &mut accounts.bonding_curve_token_account,
+        trader_token_account: &mut accounts.owner_token_account,
+        user_curve_stats: &mut accounts.user_curve_stats,
+        quote: TradeQuote::Sol(SolQuote {
+            user_invite_account: &mut accounts.user_invite_account,
+            parent_invite_account: &mut accounts.parent_invite_account,
+            fee_recipient_invite_account: &mut accounts.fee_recipient_invite_account,
+            creator_fee_recipient_invite_account: &mut accounts.creator_fee_recipient_invite_account,
+            fee_account: &mut accounts.fee_account,
+            incinerator: accounts.incinerator.as_ref().map(|incinerator| incinerator.to_account_info()),
+            system_program: accounts.system_program.to_account_info(),
+        }),
+        token_program: accounts.token_program.to_account_info(),
+    };
+
+    settle_buy(&mut trade, &sol_source, quote.curve.as_ref(), &quote.buy_result, &quote.fees, hash, allowlist_proof)?;
+
+    let sol_amount = quote.buy_result.sol_amount + quote.fees.total_fee;
+    let dca_vault = &mut accounts.dca_vault;
+    dca_vault.record_purchase(timestamp as u64, sol_amount, quote.buy_result.token_amount);
+
+    log_dca_vault(dca_vault, sol_amount, quote.buy_result.token_amount, timestamp, "dcaPurchase");
+
+    if dca_vault.is_empty() || !accounts.bonding_curve.is_trading() {
+        return close_dca_vault(&accounts.dca_vault, accounts.owner.to_account_info(), timestamp);
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawDcaVault<'info> {
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [DcaVault::SEED_PREFIX, dca_vault.bonding_curve.as_ref(), owner.key().as_ref(), &dca_vault.id.to_le_bytes()],
+        bump,
+    )]
+    dca_vault: Box<Account<'info, DcaVault>>,
+}
+
+/// Stops a DCA vault, returning its remaining balance to the owner.
+pub fn withdraw_dca_vault(ctx: Context<WithdrawDcaVault>) -> Result<()> {
+    close_dca_vault(
+        &ctx.accounts.dca_vault,
+        ctx.accounts.owner.to_account_info(),
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+fn close_dca_vault<'info>(dca_vault: &Account<'info, DcaVault>, owner: AccountInfo<'info>, timestamp: i64) -> Result<()> {
+    log_dca_vault(dca_vault, dca_vault.balance, 0, timestamp, "closeDca");
+
+    dca_vault.close(owner)
+}
+
+fn log_dca_vault(dca_vault: &Account<DcaVault>, sol_amount: u64, token_amount: u64, timestamp: i64, name: &str) {
+    let dca_event = DcaVaultEvent {
+        vault: dca_vault.key().to_string(),
+        owner: dca_vault.owner.to_string(),
+        mint: dca_vault.mint.to_string(),
+        sol_amount,
+        token_amount,
+        balance: dca_vault.balance,
+        purchases: dca_vault.purchases,
+        total_sol_spent: dca_vault.total_sol_spent,
+        total_tokens_bought: dca_vault.total_tokens_bought,
+        next_purchase_time: dca_vault.next_purchase_time,
+        timestamp,
+    };
+
+    let serialized = serde_json::to_string(&dca_event).unwrap();
+
+    msg!("{}:{}", name, serialized);
+}
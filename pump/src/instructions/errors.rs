@@ -13,6 +13,8 @@ pub enum CurveLaunchpadError {
     BondingCurveComplete,
     #[msg("Bonding Curve Not Complete")]
     BondingCurveNotComplete,
+    #[msg("Invalid bonding curve status")]
+    InvalidCurveStatus,
     #[msg("Insufficient Tokens")]
     InsufficientTokens,
     #[msg("Insufficient SOL")]
@@ -25,6 +27,16 @@ pub enum CurveLaunchpadError {
     MinBuy,
     #[msg("Min sell is 1 Token")]
     MinSell,
+    #[msg("Min Tokens Output Exceeded")]
+    MinTokensOutputExceeded,
+    #[msg("Max Tokens Input Exceeded")]
+    MaxTokensInputExceeded,
+    #[msg("Insufficient pool liquidity")]
+    InsufficientLiquidity,
+    #[msg("Invalid curve parameters")]
+    InvalidCurveParams,
+    #[msg("Invalid AMM parameters")]
+    InvalidAmmParams,
     #[msg("Invalid Fee Recipient")]
     InvalidFeeRecipient,
     #[msg("Invalid Withdraw Authority")]
@@ -44,5 +56,68 @@ pub enum CurveLaunchpadError {
 
     #[msg("Invite account error.")]
     InviteAccountError,
+
+    #[msg("Quote asset is not enabled.")]
+    QuoteAssetDisabled,
+    #[msg("Quote mint does not match the bonding curve.")]
+    QuoteMintMismatch,
+    #[msg("Insufficient quote asset balance.")]
+    InsufficientQuoteAsset,
+    #[msg("Transferred amount does not match the transfer fee.")]
+    TransferFeeMismatch,
+    #[msg("Mint suffix must be 1 to 9 base58 characters.")]
+    InvalidMintSuffix,
+    #[msg("Curve template does not match the requested id.")]
+    InvalidCurveTemplate,
+    #[msg("Curve template is not enabled.")]
+    CurveTemplateDisabled,
+    #[msg("Dev buy would complete the bonding curve.")]
+    DevBuyTooLarge,
+    #[msg("Vesting needs a duration of at least the cliff.")]
+    InvalidVestingTerms,
+    #[msg("Vesting escrow does not belong to this launch.")]
+    InvalidVestingEscrow,
+    #[msg("Nothing has vested yet.")]
+    NothingVested,
+    #[msg("Buy exceeds the per-wallet cap of the launch window.")]
+    LaunchBuyCapExceeded,
+    #[msg("Burning the sniper fee needs the incinerator account.")]
+    IncineratorRequired,
+    #[msg("Allowlist needs a merkle root and a duration.")]
+    InvalidAllowlist,
+    #[msg("Wallet is not on the allowlist.")]
+    NotAllowlisted,
+    #[msg("Buy exceeds the wallet's allowlist cap.")]
+    AllowlistCapExceeded,
+    #[msg("Can't sell in the same slot as a buy from the same wallet.")]
+    SameSlotBuySell,
+    #[msg("Price moved more than allowed within one slot.")]
+    SlotPriceImpactExceeded,
+    #[msg("Order needs an amount, a limit price and an expiry in the future.")]
+    InvalidOrder,
+    #[msg("Order has expired.")]
+    OrderExpired,
+    #[msg("Price has not reached the order's limit.")]
+    LimitPriceNotReached,
+    #[msg("DCA needs an interval, an amount covered by the deposit and a max price.")]
+    InvalidDcaTerms,
+    #[msg("DCA purchase is not due yet.")]
+    DcaNotDue,
+    #[msg("DCA purchase is above the vault's max price.")]
+    DcaPriceExceeded,
+    #[msg("Sell trigger needs tokens, a trigger price and a slippage bound of at most 100%.")]
+    InvalidSellTrigger,
+    #[msg("Price has not crossed the sell trigger.")]
+    TriggerPriceNotReached,
+    #[msg("Transfer fee can't be over 100%.")]
+    InvalidTransferFee,
+    #[msg("Protocol token allocation would leave the curve nothing to sell.")]
+    ProtocolAllocationTooLarge,
+    #[msg("Dev buy needs the creator's curve stats account.")]
+    MissingUserCurveStats,
+    #[msg("Protocol token allocation needs its escrow accounts.")]
+    MissingProtocolAllocation,
+    #[msg("Migration pool must be an existing program account.")]
+    InvalidMigrationPool,
     
 }
\ No newline at end of file
@@ -17,6 +17,17 @@ pub struct CreateEvent {
     pub telegram : String,
     pub twitter: String,
     pub decimal: u8,
+    /// Creator's purchase made in the same instruction, before fees.
+    pub dev_buy_sol_amount: u64,
+    pub dev_buy_token_amount: u64,
+    /// Vesting of the dev-buy tokens, both 0 when they went straight to the creator.
+    pub vesting_cliff_seconds: u64,
+    pub vesting_duration_seconds: u64,
+    /// Tokens minted to the protocol allocation escrow instead of the curve.
+    pub protocol_token_allocation: u64,
+    /// Allowlist phase, a zero root and end time when the curve has none.
+    pub allowlist_merkle_root: [u8; 32],
+    pub allowlist_end_time: u64,
 }
 
 
@@ -26,7 +37,10 @@ pub struct CreateEvent {
 pub struct TradeEvent {
     pub mint: String,
     pub sol_amount: u64,
+    /// Tokens sent by the curve on buys and by the user on sells.
     pub token_amount: u64,
+    /// `token_amount` less any Token-2022 transfer fee, what actually arrived.
+    pub net_token_amount: u64,
     pub is_buy: bool,
     pub user: String,
     pub timestamp: i64,
@@ -35,6 +49,17 @@ pub struct TradeEvent {
     pub real_sol_reserves: u64,
     pub real_token_reserves: u64,
 
+    pub spot_price: u128,
+    pub market_cap: u64,
+    pub graduation_progress_bps: u64,
+    pub sol_to_graduation: u64,
+    /// Fee rate the trade paid, the curve's fees plus any sniper fee.
+    pub fee_basis_points: u64,
+    /// How far the trade moved the spot price, in basis points of the price before it.
+    pub price_impact_bps: u64,
+
+    pub quote_mint: String,
+
     pub hash : String,
 }
 
@@ -44,6 +69,73 @@ pub struct CompleteEvent {
     pub mint: String,
     pub bonding_curve: String,
     pub timestamp: i64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub pool_sol_amount: u64,
+    pub pool_token_amount: u64,
+    pub quote_mint: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PoolCreateEvent {
+    pub mint: String,
+    pub pool: String,
+    pub lp_mint: String,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+    pub locked_lp_supply: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SwapEvent {
+    pub mint: String,
+    pub sol_amount: u64,
+    /// Tokens sent by the pool on buys and by the user on sells.
+    pub token_amount: u64,
+    /// `token_amount` less any Token-2022 transfer fee, what actually arrived.
+    pub net_token_amount: u64,
+    pub fee: u64,
+    pub is_buy: bool,
+    pub user: String,
+    pub timestamp: i64,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+    pub hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LiquidityEvent {
+    pub mint: String,
+    pub user: String,
+    pub is_add: bool,
+    pub lp_amount: u64,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+    pub lp_supply: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MigratedEvent {
+    pub mint: String,
+    /// External pool the withdrawn liquidity was seeded into.
+    pub pool: String,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WithdrawEvent {
+    pub mint: String,
+    pub withdraw_authority: String,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    /// `token_amount` less any Token-2022 transfer fee, what the authority received.
+    pub net_token_amount: u64,
+    pub timestamp: i64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -63,4 +155,108 @@ pub struct ClaimInviteProfitEvent {
     pub user: String,
     pub amount: u64,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClaimQuoteInviteProfitEvent {
+    pub user: String,
+    pub quote_mint: String,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetMintSuffixPolicyEvent {
+    pub authority: String,
+    pub enabled: bool,
+    pub case_sensitive: bool,
+    pub suffix: String,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClaimVestedEvent {
+    pub mint: String,
+    pub beneficiary: String,
+    pub amount: u64,
+    /// `amount` less any Token-2022 transfer fee, what the beneficiary received.
+    pub net_amount: u64,
+    pub claimed_amount: u64,
+    pub total_amount: u64,
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OrderEvent {
+    pub order: String,
+    pub owner: String,
+    pub mint: String,
+    pub is_buy: bool,
+    pub amount: u64,
+    pub limit_price: u128,
+    pub expiry: u64,
+    pub keeper_fee: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FillOrderEvent {
+    pub order: String,
+    pub owner: String,
+    pub keeper: String,
+    pub mint: String,
+    pub is_buy: bool,
+    /// SOL spent on a buy or received on a sell, fees included.
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub limit_price: u128,
+    pub keeper_fee: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DcaVaultEvent {
+    pub vault: String,
+    pub owner: String,
+    pub mint: String,
+    /// Deposited on open, spent on a purchase, refunded on close.
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub balance: u64,
+    pub purchases: u64,
+    pub total_sol_spent: u64,
+    pub total_tokens_bought: u64,
+    pub next_purchase_time: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SellTriggerEvent {
+    pub trigger: String,
+    pub owner: String,
+    pub mint: String,
+    pub is_stop_loss: bool,
+    pub token_amount: u64,
+    pub trigger_price: u128,
+    pub max_slippage_bps: u64,
+    pub keeper_fee: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FillSellTriggerEvent {
+    pub trigger: String,
+    pub owner: String,
+    pub keeper: String,
+    pub mint: String,
+    pub is_stop_loss: bool,
+    pub token_amount: u64,
+    /// SOL the owner received, after fees.
+    pub sol_amount: u64,
+    pub min_sol_output: u64,
+    pub spot_price: u128,
+    pub keeper_fee: u64,
+    pub timestamp: i64,
+}
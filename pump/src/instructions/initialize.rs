@@ -1,4 +1,7 @@
-use crate::{state::{FeeAccount, Global}, CurveLaunchpadError, DEFAULT_TOKEN_SUPPLY};
+use crate::{
+    state::{FeeAccount, Global, LaunchProtection, MintSuffixMode, MintSuffixPolicy, SandwichProtection},
+    CurveLaunchpadError, DEFAULT_MINT_SUFFIX, DEFAULT_TOKEN_SUPPLY,
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -8,6 +11,8 @@ pub struct Initialize<'info> {
 
     #[account(
         init,
+        space = 8 + Global::INIT_SPACE,
+        seeds = [Global::SEED_PREFIX],
         bump,
         payer = authority,
     )]
@@ -43,6 +48,11 @@ pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
     global.protocol_token_alloc_points = 50;
     global.protocol_token_alloc_recipient = *ctx.accounts.authority.to_account_info().key;
     global.invite_fee_basis_points = 15;
+    global.mint_suffix_policy = MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, DEFAULT_MINT_SUFFIX)?;
+    global.protocol_token_alloc_cliff_seconds = 0;
+    global.protocol_token_alloc_vesting_seconds = 0;
+    global.launch_protection = LaunchProtection::default();
+    global.sandwich_protection = SandwichProtection::default();
 
     msg!("Initialized global state");
 
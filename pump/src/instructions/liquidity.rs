@@ -0,0 +1,229 @@
+use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{amm::cpmm, gross_transfer_amount, state::Pool, transfer_fee, CurveLaunchpadError, LiquidityEvent};
+
+#[derive(Accounts)]
+pub struct Liquidity<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        address = pool.mint.key()
+    )]
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [Pool::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program,
+        seeds=[Pool::SEED_PREFIX, mint.key().as_ref(), pool.key().as_ref()],
+        bump
+    )]
+    pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [Pool::LP_MINT_SEED_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    user_lp_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Deposits SOL and tokens at the pool ratio for `lp_amount` LP tokens. The
+/// user pays any Token-2022 transfer fee on top of the tokens the pool needs.
+pub fn add_liquidity(ctx: Context<Liquidity>, lp_amount: u64, max_sol_amount: u64, max_token_amount: u64) -> Result<()> {
+    let (sol_amount, token_amount) = cpmm::deposit_amounts(
+        ctx.accounts.pool.sol_reserves,
+        ctx.accounts.pool.token_reserves,
+        ctx.accounts.pool.lp_supply,
+        lp_amount,
+    )
+    .ok_or(CurveLaunchpadError::InsufficientLiquidity)?;
+
+    require!(sol_amount <= max_sol_amount, CurveLaunchpadError::MaxSOLCostExceeded);
+    let gross_token_amount = gross_transfer_amount(&ctx.accounts.mint.to_account_info(), token_amount)?;
+    require!(gross_token_amount <= max_token_amount, CurveLaunchpadError::MaxTokensInputExceeded);
+
+    //transfer SOL to the pool
+    let transfer_instruction = system_instruction::transfer(
+        ctx.accounts.user.key,
+        ctx.accounts.pool.to_account_info().key,
+        sol_amount,
+    );
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &transfer_instruction,
+        &[
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[],
+    )?;
+
+    //transfer SPL to the pool
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.pool_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        gross_token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    //mint LP tokens
+    let signer: [&[&[u8]]; 1] = [&[
+        Pool::SEED_PREFIX,
+        ctx.accounts.mint.to_account_info().key.as_ref(),
+        &[ctx.bumps.pool],
+    ]];
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_token_account.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            &signer,
+        ),
+        lp_amount,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.sol_reserves += sol_amount;
+    pool.token_reserves += token_amount;
+    pool.lp_supply += lp_amount;
+    pool.update_time = Clock::get()?.unix_timestamp as u64;
+
+    log_liquidity_event(pool, &ctx.accounts.user, true, lp_amount, sol_amount, token_amount)
+}
+
+/// Burns `lp_amount` LP tokens for their share of the pool reserves.
+pub fn remove_liquidity(ctx: Context<Liquidity>, lp_amount: u64, min_sol_amount: u64, min_token_amount: u64) -> Result<()> {
+    // the locked supply is never minted, so it can't be burned here
+    let (sol_amount, token_amount) = cpmm::withdraw_amounts(
+        ctx.accounts.pool.sol_reserves,
+        ctx.accounts.pool.token_reserves,
+        ctx.accounts.pool.lp_supply,
+        lp_amount,
+    )
+    .ok_or(CurveLaunchpadError::InsufficientLiquidity)?;
+
+    require!(sol_amount >= min_sol_amount, CurveLaunchpadError::MinSOLOutputExceeded);
+    //the user only gets what's left after any transfer fee
+    let net_token_amount = token_amount - transfer_fee(&ctx.accounts.mint.to_account_info(), token_amount)?;
+    require!(net_token_amount >= min_token_amount, CurveLaunchpadError::MinTokensOutputExceeded);
+
+    //burn LP tokens
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    //transfer SPL back to user
+    let signer: [&[&[u8]]; 1] = [&[
+        Pool::SEED_PREFIX,
+        ctx.accounts.mint.to_account_info().key.as_ref(),
+        &[ctx.bumps.pool],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.pool_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            &signer,
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    //transfer SOL back to user
+    **ctx.accounts.pool.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+    **ctx.accounts.user.try_borrow_mut_lamports()? += sol_amount;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.sol_reserves -= sol_amount;
+    pool.token_reserves -= token_amount;
+    pool.lp_supply -= lp_amount;
+    pool.update_time = Clock::get()?.unix_timestamp as u64;
+
+    log_liquidity_event(pool, &ctx.accounts.user, false, lp_amount, sol_amount, token_amount)
+}
+
+fn log_liquidity_event(
+    pool: &Pool,
+    user: &Signer,
+    is_add: bool,
+    lp_amount: u64,
+    sol_amount: u64,
+    token_amount: u64,
+) -> Result<()> {
+    let liquidity_event = LiquidityEvent {
+        mint: pool.mint.to_string(),
+        user: user.key().to_string(),
+        is_add,
+        lp_amount,
+        sol_amount,
+        token_amount,
+        sol_reserves: pool.sol_reserves,
+        token_reserves: pool.token_reserves,
+        lp_supply: pool.lp_supply,
+        timestamp: pool.update_time as i64,
+    };
+    let serialized = serde_json::to_string(&liquidity_event).unwrap();
+    msg!("liquiditylog:{}", serialized);
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+    Discriminator,
+};
+
+use crate::{state::Global, CurveLaunchpadError};
+
+#[derive(Accounts)]
+pub struct MigrateGlobal<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    /// CHECK: may still have the smaller layout of an older version, checked in the handler
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX],
+        bump,
+        owner = crate::ID,
+    )]
+    global: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+/// Grows a `Global` created by an older version of the program to the current
+/// layout. The new fields get the defaults of `initialize`, so an existing
+/// deployment keeps enforcing the mint suffix, and the rest come out zeroed,
+/// which leaves what they configure off until the authority sets it.
+pub fn migrate_global(ctx: Context<MigrateGlobal>) -> Result<()> {
+    let global = ctx.accounts.global.to_account_info();
+
+    {
+        let data = global.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + 32 && data[..8] == Global::DISCRIMINATOR,
+            CurveLaunchpadError::NotInitialized,
+        );
+        //the authority is the first field in every layout
+        require!(
+            data[8..40] == ctx.accounts.authority.key().to_bytes(),
+            CurveLaunchpadError::InvalidAuthority,
+        );
+    }
+
+    let space = 8 + Global::INIT_SPACE;
+    let old_len = global.data_len();
+    if old_len >= space {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?.minimum_balance(space).saturating_sub(global.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: global.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+
+    global.realloc(space, true)?;
+
+    //the grown account has to read back as the current layout
+    let mut state = Global::try_deserialize(&mut &global.try_borrow_data()?[..])?;
+    state.fill_grown_fields(old_len)?;
+    state.try_serialize(&mut &mut global.try_borrow_mut_data()?[..])?;
+
+    msg!("Migrated global state");
+
+    Ok(())
+}
@@ -1,23 +1,51 @@
 pub mod initialize;
+pub mod migrate_global;
 pub mod errors;
 pub mod buy;
 pub mod sell;
 pub mod create;
+pub mod create_token_2022;
 pub mod set_params;
 pub mod constants;
 pub mod events;
 pub mod util;
 pub mod withdraw;
 pub mod user_invite;
+pub mod quote;
+pub mod create_pool;
+pub mod swap;
+pub mod liquidity;
+pub mod quote_asset;
+pub mod quote_asset_trade;
+pub mod curve_template;
+pub mod vesting;
+pub mod trade;
+pub mod order;
+pub mod dca;
+pub mod sell_trigger;
 
 pub use initialize::*;
+pub use migrate_global::*;
 pub use errors::*;
 pub use buy::*;
 pub use sell::*;
 pub use create::*;
+pub use create_token_2022::*;
 pub use set_params::*;
 pub use constants::*;
 pub use events::*;
 pub use util::*;
 pub use withdraw::*;
-pub use user_invite::*;
\ No newline at end of file
+pub use user_invite::*;
+pub use quote::*;
+pub use create_pool::*;
+pub use swap::*;
+pub use liquidity::*;
+pub use quote_asset::*;
+pub use quote_asset_trade::*;
+pub use curve_template::*;
+pub use vesting::*;
+pub use trade::*;
+pub use order::*;
+pub use dca::*;
+pub use sell_trigger::*;
\ No newline at end of file
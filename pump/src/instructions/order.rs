@@ -0,0 +1,581 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{incinerator, system_instruction},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    quote_buy_exact_sol_in, quote_sell, settle_buy, settle_sell,
+    state::{AllowlistProof, BondingCurve, FeeAccount, Global, Order, UserCurveStats, UserInviteStats},
+    CurveLaunchpadError, CurveTrade, FillOrderEvent, OrderEvent, SolQuote, SolSource, TradeQuote, TradeSide,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    #[account(address = bonding_curve.mint.key())]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        constraint = bonding_curve.is_native_quote() @ CurveLaunchpadError::QuoteMintMismatch,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Order::INIT_SPACE,
+        seeds = [Order::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref(), &id.to_le_bytes()],
+        bump,
+    )]
+    order: Box<Account<'info, Order>>,
+
+    /// Escrow of a sell order, left out for a buy.
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = order,
+        token::token_program = token_program,
+        seeds = [Order::SEED_PREFIX, mint.key().as_ref(), order.key().as_ref()],
+        bump,
+    )]
+    order_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Receives the tokens of a buy, sends those of a sell.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + UserCurveStats::INIT_SPACE,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Box<Account<'info, UserCurveStats>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Places a limit order: `amount` is the SOL a buy spends, fees included, or
+/// the tokens a sell sends to the curve. The escrow and `keeper_fee` are
+/// taken from the owner now and returned by `cancel_order`.
+pub fn place_order(
+    ctx: Context<PlaceOrder>,
+    id: u64,
+    side: TradeSide,
+    amount: u64,
+    limit_price: u128,
+    expiry: u64,
+    keeper_fee: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        amount > 0 && limit_price > 0 && expiry > timestamp as u64,
+        CurveLaunchpadError::InvalidOrder,
+    );
+
+    let owner = &ctx.accounts.owner;
+    let order_info = ctx.accounts.order.to_account_info();
+
+    //a buy escrows its SOL next to the keeper fee
+    let escrow_lamports = match side {
+        TradeSide::Buy => {
+            require!(ctx.accounts.order_token_account.is_none(), CurveLaunchpadError::InvalidOrder);
+            amount + keeper_fee
+        }
+        TradeSide::Sell => keeper_fee,
+    };
+
+    if escrow_lamports > 0 {
+        anchor_lang::solana_program::program::invoke(
+            &system_instruction::transfer(owner.key, order_info.key, escrow_lamports),
+            &[
+                owner.to_account_info(),
+                order_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    let mut escrow_amount = amount;
+    let mut token_account = Pubkey::default();
+    if side == TradeSide::Sell {
+        let order_token_account = ctx
+            .accounts
+            .order_token_account
+            .as_mut()
+            .ok_or(CurveLaunchpadError::InvalidOrder)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: order_token_account.to_account_info(),
+                    authority: owner.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        //the order sells what it actually holds, after any transfer fee
+        order_token_account.reload()?;
+        escrow_amount = order_token_account.amount;
+        token_account = order_token_account.key();
+    }
+
+    let order = &mut ctx.accounts.order;
+    order.owner = owner.key();
+    order.bonding_curve = ctx.accounts.bonding_curve.key();
+    order.mint = ctx.accounts.mint.key();
+    order.id = id;
+    order.side = side;
+    order.amount = escrow_amount;
+    order.limit_price = limit_price;
+    order.expiry = expiry;
+    order.keeper_fee = keeper_fee;
+    order.token_account = token_account;
+
+    log_order(order, timestamp, "placeOrder");
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOrder<'info> {
+    #[account(mut)]
+    keeper: Signer<'info>,
+
+    /// CHECK: receives the SOL of a sell and the order's remaining lamports
+    #[account(
+        mut,
+        address = order.owner,
+    )]
+    owner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        address = bonding_curve.mint.key()
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        constraint = bonding_curve.is_native_quote() @ CurveLaunchpadError::QuoteMintMismatch,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = bonding_curve,
+        seeds = [Order::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref(), &order.id.to_le_bytes()],
+        bump,
+        close = owner,
+    )]
+    order: Box<Account<'info, Order>>,
+
+    #[account(
+        mut,
+        address = order.token_account,
+    )]
+    order_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Box<Account<'info, UserCurveStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,owner.key().as_ref()],
+        bump
+    )]
+    user_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,user_invite_account.parent.key().as_ref()],
+        bump
+    )]
+    parent_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,global.fee_recipient.key().as_ref()],
+        bump
+    )]
+    fee_recipient_invite_account: Box<Account<'info,UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,bonding_curve.creator.key().as_ref()],
+        bump
+    )]
+    creator_fee_recipient_invite_account: Box<Account<'info,UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account : Box<Account<'info,FeeAccount>>,
+
+    /// CHECK: only receives sniper fees, needed when the curve burns them
+    #[account(
+        mut,
+        address = incinerator::ID,
+    )]
+    incinerator: Option<UncheckedAccount<'info>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless crank filling an order through the same settlement as
+/// `buy_exact_sol_in` and `sell`, once the spot price has crossed its limit.
+/// The keeper gets the order's `keeper_fee` and the owner the rest of its
+/// lamports. During an allowlist phase the keeper passes the owner's proof.
+pub fn execute_order(ctx: Context<ExecuteOrder>, hash: &str, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    require!(ctx.accounts.user_invite_account.is_init,CurveLaunchpadError::InviteAccountError);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let order = &ctx.accounts.order;
+    require!(!order.is_expired(timestamp as u64), CurveLaunchpadError::OrderExpired);
+
+    let spot_price = ctx.accounts.bonding_curve.spot_price().unwrap_or_default();
+    require!(order.is_crossed(spot_price), CurveLaunchpadError::LimitPriceNotReached);
+
+    let order_info = order.to_account_info();
+    let order_key = order.key();
+    let order_bump = ctx.bumps.order;
+    let order_side = order.side;
+    let order_amount = order.amount;
+    let keeper_fee = order.keeper_fee;
+    let owner_key = order.owner;
+    let id_bytes = order.id.to_le_bytes();
+
+    let accounts = ctx.accounts;
+    let mint_info = accounts.mint.to_account_info();
+    let bonding_curve_key = accounts.bonding_curve.key();
+    let owner_info = accounts.owner.to_account_info();
+    let token_program = accounts.token_program.to_account_info();
+
+    let (sol_amount, token_amount) = match order_side {
+        TradeSide::Buy => {
+            let quote = quote_buy_exact_sol_in(
+                &accounts.bonding_curve,
+                accounts.bonding_curve_token_account.amount,
+                &mint_info,
+                order_amount,
+                timestamp as u64,
+            )?;
+            require!(
+                accounts.order.honors_limit(quote.buy_result.sol_amount, quote.buy_result.token_amount),
+                CurveLaunchpadError::LimitPriceNotReached,
+            );
+
+            let mut trade = CurveTrade {
+                trader: owner_key,
+                mint: &accounts.mint,
+                bonding_curve: &mut accounts.bonding_curve,
+                bonding_curve_bump: ctx.bumps.bonding_curve,
+                bonding_curve_token_account: &mut accounts.bonding_curve_token_account,
+                trader_token_account: &mut accounts.owner_token_account,
+                user_curve_stats: &mut accounts.user_curve_stats,
+                quote: TradeQuote::Sol(SolQuote {
+                    user_invite_account: &mut accounts.user_invite_account,
+                    parent_invite_account: &mut accounts.parent_invite_account,
+                    fee_recipient_invite_account: &mut accounts.fee_recipient_invite_account,
+                    creator_fee_recipient_invite_account: &mut accounts.creator_fee_recipient_invite_account,
+                    fee_account: &mut accounts.fee_account,
+                    incinerator: accounts.incinerator.as_ref().map(|incinerator| incinerator.to_account_info()),
+                    system_program: accounts.system_program.to_account_info(),
+                }),
+                token_program: token_program.clone(),
+            };
+
+            let sol_source = SolSource::Escrow(order_info.clone());
+            settle_buy(&mut trade, &sol_source, quote.curve.as_ref(), &quote.buy_result, &quote.fees, hash, allowlist_proof)?;
+
+            (quote.buy_result.sol_amount + quote.fees.total_fee, quote.buy_result.token_amount)
+        }
+        TradeSide::Sell => {
+            let quote = quote_sell(&accounts.bonding_curve, &mint_info, order_amount, timestamp as u64)?;
+            require!(
+                accounts.order.honors_limit(quote.sell_result.sol_amount, quote.sell_result.token_amount),
+                CurveLaunchpadError::LimitPriceNotReached,
+            );
+
+            let order_token_account = accounts
+                .order_token_account
+                .as_mut()
+                .ok_or(CurveLaunchpadError::InvalidOrder)?;
+
+            let mut trade = CurveTrade {
+                trader: owner_key,
+                mint: &accounts.mint,
+                bonding_curve: &mut accounts.bonding_curve,
+                bonding_curve_bump: ctx.bumps.bonding_curve,
+                bonding_curve_token_account: &mut accounts.bonding_curve_token_account,
+                trader_token_account: order_token_account,
+                user_curve_stats: &mut accounts.user_curve_stats,
+                quote: TradeQuote::Sol(SolQuote {
+                    user_invite_account: &mut accounts.user_invite_account,
+                    parent_invite_account: &mut accounts.parent_invite_account,
+                    fee_recipient_invite_account: &mut accounts.fee_recipient_invite_account,
+                    creator_fee_recipient_invite_account: &mut accounts.creator_fee_recipient_invite_account,
+                    fee_account: &mut accounts.fee_account,
+                    incinerator: accounts.incinerator.as_ref().map(|incinerator| incinerator.to_account_info()),
+                    system_program: accounts.system_program.to_account_info(),
+                }),
+                token_program: token_program.clone(),
+            };
+
+            let signer: [&[&[u8]]; 1] = [&[
+                Order::SEED_PREFIX,
+                bonding_curve_key.as_ref(),
+                owner_key.as_ref(),
+                &id_bytes,
+                &[order_bump],
+            ]];
+
+            settle_sell(
+                &mut trade,
+                order_info.clone(),
+                &signer,
+                owner_info,
+                quote.curve.as_ref(),
+                &quote.sell_result,
+                order_amount,
+                &quote.fees,
+                hash,
+            )?;
+
+            close_order_token_account(accounts, &signer)?;
+
+            (quote.sell_result.sol_amount - quote.fees.total_fee, order_amount)
+        }
+    };
+
+    **order_info.try_borrow_mut_lamports()? -= keeper_fee;
+    **accounts.keeper.try_borrow_mut_lamports()? += keeper_fee;
+
+    let fill_event = FillOrderEvent {
+        order: order_key.to_string(),
+        owner: owner_key.to_string(),
+        keeper: accounts.keeper.key().to_string(),
+        mint: accounts.mint.key().to_string(),
+        is_buy: order_side == TradeSide::Buy,
+        sol_amount,
+        token_amount,
+        limit_price: accounts.order.limit_price,
+        keeper_fee,
+        timestamp,
+    };
+
+    let serialized = serde_json::to_string(&fill_event).unwrap();
+
+    msg!("fillOrder:{}", serialized);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    #[account(address = order.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [Order::SEED_PREFIX, order.bonding_curve.as_ref(), owner.key().as_ref(), &order.id.to_le_bytes()],
+        bump,
+        close = owner,
+    )]
+    order: Box<Account<'info, Order>>,
+
+    #[account(
+        mut,
+        address = order.token_account,
+    )]
+    order_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    token_program: Interface<'info, TokenInterface>,
+}
+
+/// Returns an order's escrow and keeper fee to its owner, at any time.
+pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    let bonding_curve_key = order.bonding_curve;
+    let owner_key = order.owner;
+    let id_bytes = order.id.to_le_bytes();
+    let signer: [&[&[u8]]; 1] = [&[
+        Order::SEED_PREFIX,
+        bonding_curve_key.as_ref(),
+        owner_key.as_ref(),
+        &id_bytes,
+        &[ctx.bumps.order],
+    ]];
+
+    if order.side == TradeSide::Sell {
+        let order_token_account = ctx
+            .accounts
+            .order_token_account
+            .as_ref()
+            .ok_or(CurveLaunchpadError::InvalidOrder)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: order_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: order.to_account_info(),
+                },
+                &signer,
+            ),
+            order_token_account.amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: order_token_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &signer,
+        ))?;
+    }
+
+    log_order(order, Clock::get()?.unix_timestamp, "cancelOrder");
+
+    Ok(())
+}
+
+/// Closes a filled sell order's escrow. Tokens anyone sent to it on top of
+/// the order would block the close, so they go back to the owner first.
+fn close_order_token_account(accounts: &ExecuteOrder, signer: &[&[&[u8]]]) -> Result<()> {
+    let order_token_account = accounts
+        .order_token_account
+        .as_ref()
+        .ok_or(CurveLaunchpadError::InvalidOrder)?;
+
+    //settle_sell reloaded the balance after selling the order amount
+    if order_token_account.amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: order_token_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.owner_token_account.to_account_info(),
+                    authority: accounts.order.to_account_info(),
+                },
+                signer,
+            ),
+            order_token_account.amount,
+            accounts.mint.decimals,
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: order_token_account.to_account_info(),
+            destination: accounts.owner.to_account_info(),
+            authority: accounts.order.to_account_info(),
+        },
+        signer,
+    ))
+}
+
+fn log_order(order: &Account<Order>, timestamp: i64, name: &str) {
+    let order_event = OrderEvent {
+        order: order.key().to_string(),
+        owner: order.owner.to_string(),
+        mint: order.mint.to_string(),
+        is_buy: order.side == TradeSide::Buy,
+        amount: order.amount,
+        limit_price: order.limit_price,
+        expiry: order.expiry,
+        keeper_fee: order.keeper_fee,
+        timestamp,
+    };
+
+    let serialized = serde_json::to_string(&order_event).unwrap();
+
+    msg!("{}:{}", name, serialized);
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    quote_buy, quote_sell,
+    state::BondingCurve,
+    CurveLaunchpadError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// Result of `quote`, written with `set_return_data` so it can be read through
+/// CPI or from a simulated transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct QuoteResult {
+    pub side: TradeSide,
+    /// Tokens sent by the curve on a buy or by the seller on a sell.
+    pub token_amount: u64,
+    /// Tokens reaching the buyer on a buy or the curve on a sell, after any
+    /// Token-2022 transfer fee.
+    pub net_token_amount: u64,
+    /// SOL moved by the curve itself, before fees.
+    pub sol_amount: u64,
+    /// SOL the user pays on a buy or receives on a sell, fees included.
+    pub sol_amount_with_fee: u64,
+    pub total_fee: u64,
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub invite_fee: u64,
+    /// Launch protection's decaying fee, part of `total_fee`.
+    pub sniper_fee: u64,
+    /// Lamports per token base unit, scaled by `amm::math::ONE`.
+    pub spot_price_before: u128,
+    pub spot_price_after: u128,
+    /// Average execution price against `spot_price_before`.
+    pub price_impact_bps: u64,
+    pub completes_curve: bool,
+}
+
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, bonding_curve.mint.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        address = bonding_curve.mint.key()
+    )]
+    mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn quote(ctx: Context<Quote>, side: TradeSide, token_amount: u64) -> Result<QuoteResult> {
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let now = Clock::get()?.unix_timestamp as u64;
+
+    let (curve, token_amount, net_token_amount, sol_amount, fees) = match side {
+        TradeSide::Buy => {
+            require!(token_amount > 0, CurveLaunchpadError::MinBuy);
+            //there is no curve token account here, apply_buy caps at the real token reserves
+            let quote = quote_buy(bonding_curve, u64::MAX, &mint_info, token_amount, now)?;
+            let buy_result = quote.buy_result;
+            (quote.curve, buy_result.token_amount, quote.net_token_amount, buy_result.sol_amount, quote.fees)
+        }
+        TradeSide::Sell => {
+            require!(token_amount > 0, CurveLaunchpadError::MinSell);
+            let quote = quote_sell(bonding_curve, &mint_info, token_amount, now)?;
+            let sell_result = quote.sell_result;
+            (quote.curve, token_amount, sell_result.token_amount, sell_result.sol_amount, quote.fees)
+        }
+    };
+
+    //impact is measured on the untouched curve, for the tokens the curve actually moves
+    let curve_before = bonding_curve
+        .curve_model()
+        .ok_or(CurveLaunchpadError::InvalidCurveParams)?;
+    let spot_price_before = curve_before.spot_price().ok_or(CurveLaunchpadError::InvalidCurveParams)?;
+    let impact_token_amount = match side {
+        TradeSide::Buy => token_amount,
+        TradeSide::Sell => net_token_amount,
+    };
+    let price_impact_bps = curve_before
+        .price_impact_bps(impact_token_amount as u128, side == TradeSide::Buy)
+        .unwrap_or(u64::MAX);
+
+    let spot_price_after = curve.spot_price().ok_or(CurveLaunchpadError::InvalidCurveParams)?;
+
+    let reserves = curve.reserves();
+    let graduation_sol_target = bonding_curve.graduation_sol_target as u128;
+    let reaches_graduation = reserves.real_token_reserves == 0
+        || (graduation_sol_target > 0 && reserves.real_sol_reserves >= graduation_sol_target);
+
+    let sol_amount_with_fee = match side {
+        TradeSide::Buy => sol_amount + fees.total_fee,
+        TradeSide::Sell => sol_amount - fees.total_fee,
+    };
+
+    let result = QuoteResult {
+        side,
+        token_amount,
+        net_token_amount,
+        sol_amount,
+        sol_amount_with_fee,
+        total_fee: fees.total_fee,
+        protocol_fee: fees.protocol_fee,
+        creator_fee: fees.creator_fee,
+        invite_fee: fees.invite_fee,
+        sniper_fee: fees.sniper_fee,
+        spot_price_before,
+        spot_price_after,
+        price_impact_bps,
+        completes_curve: side == TradeSide::Buy && reaches_graduation,
+    };
+
+    Ok(result)
+}
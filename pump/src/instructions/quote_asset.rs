@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{
+    state::{BondingCurve, FeeAccount, Global, QuoteAsset, QuoteFeeLedger},
+    ClaimQuoteInviteProfitEvent, CurveLaunchpadError,
+};
+
+#[derive(Accounts)]
+pub struct SetQuoteAsset<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + QuoteAsset::INIT_SPACE,
+        seeds = [QuoteAsset::SEED_PREFIX, quote_mint.key().as_ref()],
+        bump,
+    )]
+    quote_asset: Box<Account<'info, QuoteAsset>>,
+
+    #[account(
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account: Box<Account<'info, FeeAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = fee_account,
+        seeds=[FeeAccount::SEED_PREFIX, quote_mint.key().as_ref()],
+        bump
+    )]
+    fee_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+/// Adds a mint to the quote asset allowlist, or updates it. Disabling a quote
+/// asset only stops new curves from using it, existing curves keep trading.
+pub fn set_quote_asset(ctx: Context<SetQuoteAsset>, enabled: bool, initial_virtual_reserves: u64) -> Result<()> {
+    require!(ctx.accounts.global.initialized, CurveLaunchpadError::NotInitialized);
+    require!(initial_virtual_reserves > 0, CurveLaunchpadError::InvalidAmmParams);
+
+    let quote_asset = &mut ctx.accounts.quote_asset;
+    quote_asset.mint = ctx.accounts.quote_mint.key();
+    quote_asset.enabled = enabled;
+    quote_asset.initial_virtual_reserves = initial_virtual_reserves;
+
+    msg!("Set quote asset {} enabled: {}", quote_asset.mint, enabled);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitQuoteVault<'info> {
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        has_one = creator @ CurveLaunchpadError::InvalidAuthority,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [QuoteAsset::SEED_PREFIX, quote_mint.key().as_ref()],
+        bump,
+        constraint = quote_asset.enabled @ CurveLaunchpadError::QuoteAssetDisabled,
+    )]
+    quote_asset: Box<Account<'info, QuoteAsset>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = quote_mint,
+        token::authority = bonding_curve,
+        seeds=[BondingCurve::SEED_PREFIX, quote_mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+/// Prices a curve in an allowlisted quote asset. It goes between
+/// `init_create_account` and `create`, curves that skip it are priced in SOL.
+/// Only the creator recorded by `init_create_account` can call it. The
+/// template's SOL graduation target and SOL buy cap don't carry over.
+pub fn init_quote_vault(ctx: Context<InitQuoteVault>) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+
+    //the curve must not be created yet
+    require!(bonding_curve.create_time == 0, CurveLaunchpadError::InvalidCurveStatus);
+    require!(bonding_curve.is_native_quote(), CurveLaunchpadError::QuoteMintMismatch);
+
+    bonding_curve.quote_mint = ctx.accounts.quote_mint.key();
+    bonding_curve.virtual_sol_reserves = ctx.accounts.quote_asset.initial_virtual_reserves;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct QuoteInviteClaimAccount<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds=[QuoteFeeLedger::SEED_PREFIX, quote_mint.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    user_quote_ledger: Box<Account<'info, QuoteFeeLedger>>,
+
+    #[account(
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account: Box<Account<'info, FeeAccount>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = fee_account,
+        seeds=[FeeAccount::SEED_PREFIX, quote_mint.key().as_ref()],
+        bump
+    )]
+    fee_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = user,
+    )]
+    user_quote_account: Box<Account<'info, TokenAccount>>,
+
+    token_program: Program<'info, Token>,
+}
+
+pub fn claim_quote_invite_profit(ctx: Context<QuoteInviteClaimAccount>) -> Result<()> {
+    let claim_amount = ctx.accounts.user_quote_ledger.profit_claimable;
+    require!(claim_amount > 0, CurveLaunchpadError::NotClaimableFee);
+    ctx.accounts.user_quote_ledger.profit_claimable = 0;
+    ctx.accounts.user_quote_ledger.profit_claim_accumulated += claim_amount;
+
+    require!(
+        ctx.accounts.fee_quote_vault.amount >= claim_amount,
+        CurveLaunchpadError::FeeAccountStatusAbnormal
+    );
+
+    let signer: [&[&[u8]]; 1] = [&[FeeAccount::SEED_PREFIX, &[ctx.bumps.fee_account]]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_quote_vault.to_account_info(),
+                to: ctx.accounts.user_quote_account.to_account_info(),
+                authority: ctx.accounts.fee_account.to_account_info(),
+            },
+            &signer,
+        ),
+        claim_amount,
+    )?;
+
+    let claim_event = ClaimQuoteInviteProfitEvent {
+        user: ctx.accounts.user.key().to_string(),
+        quote_mint: ctx.accounts.quote_mint.key().to_string(),
+        amount: claim_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    };
+
+    let serialized = serde_json::to_string(&claim_event).unwrap();
+
+    msg!("claimQuoteInviteProfit:{}", serialized);
+
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
+};
+
+use crate::{
+    quote_buy, quote_sell, settle_buy, settle_sell,
+    state::{AllowlistProof, BondingCurve, FeeAccount, Global, QuoteFeeLedger, UserCurveStats, UserInviteStats},
+    CurveLaunchpadError, CurveTrade, QuoteLedger, SolSource, TokenQuote, TradeQuote,
+};
+
+#[derive(Accounts)]
+pub struct QuoteAssetTrade<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        address = bonding_curve.mint.key()
+    )]
+    mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        constraint = bonding_curve.quote_mint == quote_mint.key() @ CurveLaunchpadError::QuoteMintMismatch,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    user_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(mut)]
+    quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = bonding_curve,
+        seeds=[BondingCurve::SEED_PREFIX, quote_mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = user,
+    )]
+    user_quote_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserCurveStats::INIT_SPACE,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Box<Account<'info, UserCurveStats>>,
+
+    #[account(
+        seeds=[UserInviteStats::SEED_PREFIX,user.key().as_ref()],
+        bump
+    )]
+    user_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    /// CHECK: quote ledger of the user, created on its first credit
+    #[account(
+        mut,
+        seeds=[QuoteFeeLedger::SEED_PREFIX, quote_mint.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    user_quote_ledger: UncheckedAccount<'info>,
+
+    /// CHECK: quote ledger of the user's inviter, created on its first credit
+    #[account(
+        mut,
+        seeds=[QuoteFeeLedger::SEED_PREFIX, quote_mint.key().as_ref(), user_invite_account.parent.key().as_ref()],
+        bump
+    )]
+    parent_quote_ledger: UncheckedAccount<'info>,
+
+    /// CHECK: quote ledger of the protocol fee recipient, created on its first credit
+    #[account(
+        mut,
+        seeds=[QuoteFeeLedger::SEED_PREFIX, quote_mint.key().as_ref(), global.fee_recipient.key().as_ref()],
+        bump
+    )]
+    fee_recipient_quote_ledger: UncheckedAccount<'info>,
+
+    /// CHECK: quote ledger of the curve's creator, created on its first credit
+    #[account(
+        mut,
+        seeds=[QuoteFeeLedger::SEED_PREFIX, quote_mint.key().as_ref(), bonding_curve.creator.key().as_ref()],
+        bump
+    )]
+    creator_quote_ledger: UncheckedAccount<'info>,
+
+    #[account(
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account: Box<Account<'info, FeeAccount>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = fee_account,
+        seeds=[FeeAccount::SEED_PREFIX, quote_mint.key().as_ref()],
+        bump
+    )]
+    fee_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    quote_token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// `buy` for curves priced in a quote asset. The cost and the fee are paid in
+/// quote tokens, the fee goes to the quote fee vault.
+pub fn buy_with_quote_asset(
+    ctx: Context<QuoteAssetTrade>,
+    token_amount: u64,
+    max_quote_cost: u64,
+    hash: &str,
+    allowlist_proof: Option<AllowlistProof>,
+) -> Result<()> {
+    //bonding curve is still trading
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    require!(ctx.accounts.user_invite_account.is_init,CurveLaunchpadError::InviteAccountError);
+
+    //bonding curve has enough tokens
+    require!(
+        ctx.accounts.bonding_curve.real_token_reserves >= token_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    require!(token_amount > 0, CurveLaunchpadError::MinBuy,);
+
+    let quote = quote_buy(
+        &ctx.accounts.bonding_curve,
+        ctx.accounts.bonding_curve_token_account.amount,
+        &ctx.accounts.mint.to_account_info(),
+        token_amount,
+        Clock::get()?.unix_timestamp as u64,
+    )?;
+    let buy_amount_with_fee = quote.buy_result.sol_amount + quote.fees.total_fee;
+
+    require!(
+        buy_amount_with_fee <= max_quote_cost,
+        CurveLaunchpadError::MaxSOLCostExceeded,
+    );
+
+    require!(
+        ctx.accounts.user_quote_account.amount >= buy_amount_with_fee,
+        CurveLaunchpadError::InsufficientQuoteAsset,
+    );
+
+    let sol_source = SolSource::QuoteToken {
+        account: ctx.accounts.user_quote_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let mut trade = curve_trade(ctx.accounts, &ctx.bumps);
+
+    settle_buy(&mut trade, &sol_source, quote.curve.as_ref(), &quote.buy_result, &quote.fees, hash, allowlist_proof)
+}
+
+/// `sell` for curves priced in a quote asset. The proceeds are paid out of the
+/// curve's quote vault, less the fee which goes to the quote fee vault.
+pub fn sell_with_quote_asset(ctx: Context<QuoteAssetTrade>, token_amount: u64, min_quote_output: u64, hash: &str) -> Result<()> {
+    //check if bonding curve is still trading
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    require!(ctx.accounts.user_invite_account.is_init,CurveLaunchpadError::InviteAccountError);
+
+    //confirm user has enough tokens
+    require!(
+        ctx.accounts.user_token_account.amount >= token_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    //confirm bonding curve has enough tokens
+    require!(
+        ctx.accounts.bonding_curve_token_account.amount >= token_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    require!(token_amount > 0, CurveLaunchpadError::MinSell,);
+
+    let quote = quote_sell(
+        &ctx.accounts.bonding_curve,
+        &ctx.accounts.mint.to_account_info(),
+        token_amount,
+        Clock::get()?.unix_timestamp as u64,
+    )?;
+    let sell_amount_minus_fee = quote.sell_result.sol_amount - quote.fees.total_fee;
+
+    require!(
+        sell_amount_minus_fee >= min_quote_output,
+        CurveLaunchpadError::MinSOLOutputExceeded,
+    );
+
+    let user = ctx.accounts.user.to_account_info();
+    let user_quote_account = ctx.accounts.user_quote_account.to_account_info();
+    let mut trade = curve_trade(ctx.accounts, &ctx.bumps);
+
+    settle_sell(
+        &mut trade,
+        user,
+        &[],
+        user_quote_account,
+        quote.curve.as_ref(),
+        &quote.sell_result,
+        token_amount,
+        &quote.fees,
+        hash,
+    )
+}
+
+fn curve_trade<'a, 'info>(accounts: &'a mut QuoteAssetTrade<'info>, bumps: &QuoteAssetTradeBumps) -> CurveTrade<'a, 'info> {
+    let quote = TokenQuote {
+        quote_mint: accounts.quote_mint.to_account_info(),
+        bonding_curve_quote_vault: accounts.bonding_curve_quote_vault.to_account_info(),
+        fee_quote_vault: accounts.fee_quote_vault.to_account_info(),
+        user_ledger: QuoteLedger {
+            key: accounts.user.key(),
+            account: accounts.user_quote_ledger.to_account_info(),
+            bump: bumps.user_quote_ledger,
+        },
+        parent_ledger: QuoteLedger {
+            key: accounts.user_invite_account.parent,
+            account: accounts.parent_quote_ledger.to_account_info(),
+            bump: bumps.parent_quote_ledger,
+        },
+        fee_recipient_ledger: QuoteLedger {
+            key: accounts.global.fee_recipient,
+            account: accounts.fee_recipient_quote_ledger.to_account_info(),
+            bump: bumps.fee_recipient_quote_ledger,
+        },
+        creator_ledger: QuoteLedger {
+            key: accounts.bonding_curve.creator,
+            account: accounts.creator_quote_ledger.to_account_info(),
+            bump: bumps.creator_quote_ledger,
+        },
+        payer: accounts.user.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        quote_token_program: accounts.quote_token_program.to_account_info(),
+    };
+
+    CurveTrade {
+        trader: accounts.user.key(),
+        mint: &accounts.mint,
+        bonding_curve: &mut accounts.bonding_curve,
+        bonding_curve_bump: bumps.bonding_curve,
+        bonding_curve_token_account: &mut accounts.bonding_curve_token_account,
+        trader_token_account: &mut accounts.user_token_account,
+        user_curve_stats: &mut accounts.user_curve_stats,
+        quote: TradeQuote::Token(Box::new(quote)),
+        token_program: accounts.token_program.to_account_info(),
+    }
+}
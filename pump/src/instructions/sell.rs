@@ -1,8 +1,8 @@
 use crate::{
-    amm, calculate_fee, state::{BondingCurve, FeeAccount, Global, UserInviteStats}, CurveLaunchpadError, TradeEvent
+    amm::{CurveModel, SellResult}, calculate_trade_fees, gross_transfer_amount, quote_sell, settle_sell, state::{BondingCurve, FeeAccount, Global, UserCurveStats, UserInviteStats}, CurveLaunchpadError, CurveTrade, SolQuote, TradeFees, TradeQuote
 };
-use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_lang::{prelude::*, solana_program::incinerator};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct Sell<'info> {
@@ -20,12 +20,13 @@ pub struct Sell<'info> {
         mut,
         address = bonding_curve.mint.key()
     )]
-    mint: Account<'info, Mint>,
+    mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
         bump,
+        constraint = bonding_curve.is_native_quote() @ CurveLaunchpadError::QuoteMintMismatch,
     )]
     bonding_curve: Box<Account<'info, BondingCurve>>,
 
@@ -33,17 +34,28 @@ pub struct Sell<'info> {
         mut,
         token::mint = mint,
         token::authority = bonding_curve,
+        token::token_program = token_program,
         seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
         bump
     )]
-    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    user_token_account: Box<Account<'info, TokenAccount>>,
+    user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserCurveStats::INIT_SPACE,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Box<Account<'info, UserCurveStats>>,
 
 
 
@@ -84,16 +96,22 @@ pub struct Sell<'info> {
     )]
     fee_account : Box<Account<'info,FeeAccount>>,
 
+    /// CHECK: only receives sniper fees, needed when the curve burns them
+    #[account(
+        mut,
+        address = incinerator::ID,
+    )]
+    incinerator: Option<UncheckedAccount<'info>>,
 
     system_program: Program<'info, System>,
 
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64 , hash : &str) -> Result<()> {
-    //check if bonding curve is complete
+    //check if bonding curve is still trading
     require!(
-        !ctx.accounts.bonding_curve.complete,
+        ctx.accounts.bonding_curve.is_trading(),
         CurveLaunchpadError::BondingCurveComplete,
     );
 
@@ -114,21 +132,14 @@ pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64 , hash :
 
     require!(token_amount > 0, CurveLaunchpadError::MinSell,);
 
-    let mut amm = amm::amm::AMM::new(
-        ctx.accounts.bonding_curve.virtual_sol_reserves as u128,
-        ctx.accounts.bonding_curve.virtual_token_reserves as u128,
-        ctx.accounts.bonding_curve.real_sol_reserves as u128,
-        ctx.accounts.bonding_curve.real_token_reserves as u128,
-        ctx.accounts.global.initial_virtual_token_reserves as u128,
-    );
-
-    let sell_result = amm.apply_sell(token_amount as u128).unwrap();
-    let fee = calculate_fee(
-        sell_result.sol_amount,
-        ctx.accounts.global.fee_basis_points + ctx.accounts.global.creator_fee_basis_points + ctx.accounts.global.invite_fee_basis_points,
-    );
+    let quote = quote_sell(
+        &ctx.accounts.bonding_curve,
+        &ctx.accounts.mint.to_account_info(),
+        token_amount,
+        Clock::get()?.unix_timestamp as u64,
+    )?;
     //the fee is subtracted from the sol amount to confirm the user minimum sol output is met
-    let sell_amount_minus_fee = sell_result.sol_amount - fee;
+    let sell_amount_minus_fee = quote.sell_result.sol_amount - quote.fees.total_fee;
 
     //confirm min sol output is greater than sol output
     require!(
@@ -136,82 +147,96 @@ pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64 , hash :
         CurveLaunchpadError::MinSOLOutputExceeded,
     );
 
-    //transfer SPL
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info().clone(),
-        to: ctx
-            .accounts
-            .bonding_curve_token_account
-            .to_account_info()
-            .clone(),
-        authority: ctx.accounts.user.to_account_info().clone(),
-    };
-
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            &[],
-        ),
-        sell_result.token_amount,
-    )?;
-
-
+    process_sell(ctx, quote.curve.as_ref(), &quote.sell_result, token_amount, &quote.fees, hash)
+}
 
-    //transfer SOL back to user
-    let from_account = &ctx.accounts.bonding_curve;
-    let to_account = &ctx.accounts.user;
-    let fee_account = &ctx.accounts.fee_account;
+pub fn sell_exact_sol_out(ctx: Context<Sell>, sol_amount: u64, max_tokens_in: u64 , hash : &str) -> Result<()> {
+    //check if bonding curve is still trading
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
+    );
 
-    **from_account.to_account_info().try_borrow_mut_lamports()? -= sell_result.sol_amount;
-    **to_account.try_borrow_mut_lamports()? += sell_amount_minus_fee;
-    **fee_account.to_account_info().try_borrow_mut_lamports()? += fee;
+    require!(ctx.accounts.user_invite_account.is_init,CurveLaunchpadError::InviteAccountError);
 
+    require!(sol_amount > 0, CurveLaunchpadError::MinSell,);
 
+    let mut curve = ctx
+        .accounts
+        .bonding_curve
+        .curve_model()
+        .ok_or(CurveLaunchpadError::InvalidCurveParams)?;
 
-    ctx.accounts.fee_account.received += fee;
-    let is_ok = ctx.accounts.fee_account.check(ctx.accounts.fee_account.get_lamports());
-    require!(is_ok,CurveLaunchpadError::FeeAccountStatusAbnormal);
+    let sniper_fee_basis_points = ctx
+        .accounts
+        .bonding_curve
+        .sniper_fee_basis_points(Clock::get()?.unix_timestamp as u64);
+    let total_fee_basis_points = ctx.accounts.bonding_curve.fees.total() + sniper_fee_basis_points;
 
-    
-    let protocol_fee = calculate_fee(sell_result.sol_amount, ctx.accounts.global.fee_basis_points);
-    let creator_fee = calculate_fee(sell_result.sol_amount, ctx.accounts.global.creator_fee_basis_points);
-    let invite_fee = calculate_fee(sell_result.sol_amount, ctx.accounts.global.invite_fee_basis_points);
+    //the sol amount is what the user receives, so the curve has to pay it plus the fee
+    let token_amount = curve
+        .get_sell_token_amount(sol_amount as u128, total_fee_basis_points as u128)
+        .ok_or(CurveLaunchpadError::InsufficientSOL)? as u64;
 
+    //the user sends enough for token_amount to arrive after any transfer fee
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let gross_token_amount = gross_transfer_amount(&mint_info, token_amount)?;
 
-    ctx.accounts.fee_recipient_invite_account.profit_claimable += protocol_fee;
-    ctx.accounts.creator_fee_recipient_invite_account.profit_claimable += creator_fee;
-    ctx.accounts.parent_invite_account.profit_claimable += invite_fee;
+    require!(
+        gross_token_amount <= max_tokens_in,
+        CurveLaunchpadError::MaxTokensInputExceeded,
+    );
 
-    ctx.accounts.user_invite_account.profit_to_parent += invite_fee;
-    ctx.accounts.parent_invite_account.profit_from_child += invite_fee;
+    //confirm user has enough tokens
+    require!(
+        ctx.accounts.user_token_account.amount >= gross_token_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
 
+    //confirm bonding curve has enough tokens
+    require!(
+        ctx.accounts.bonding_curve_token_account.amount >= token_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
 
+    let sell_result = curve
+        .apply_sell(token_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientSOL)?;
+    let fees = calculate_trade_fees(sell_result.sol_amount, &ctx.accounts.bonding_curve.fees, sniper_fee_basis_points);
+    let sell_amount_minus_fee = sell_result.sol_amount - fees.total_fee;
 
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
-    bonding_curve.real_token_reserves = amm.real_token_reserves as u64;
-    bonding_curve.real_sol_reserves = amm.real_sol_reserves as u64;
-    bonding_curve.virtual_token_reserves = amm.virtual_token_reserves as u64;
-    bonding_curve.virtual_sol_reserves = amm.virtual_sol_reserves as u64;
+    require!(
+        sell_amount_minus_fee >= sol_amount,
+        CurveLaunchpadError::MinSOLOutputExceeded,
+    );
 
-    let trade_event = TradeEvent {
-        mint: ctx.accounts.mint.to_account_info().key().to_string(),
-        sol_amount: sell_result.sol_amount,
-        token_amount: sell_result.token_amount,
-        is_buy: false,
-        user: ctx.accounts.user.to_account_info().key().to_string(),
-        timestamp: Clock::get()?.unix_timestamp,
-        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
-        virtual_token_reserves: bonding_curve.virtual_token_reserves,
-        real_sol_reserves: bonding_curve.real_sol_reserves,
-        real_token_reserves: bonding_curve.real_token_reserves,
+    process_sell(ctx, curve.as_ref(), &sell_result, gross_token_amount, &fees, hash)
+}
 
-        hash: hash.to_string(),
+/// `gross_token_amount` is what the user sends, `sell_result` is priced on
+/// what the curve receives after any transfer fee.
+fn process_sell(ctx: Context<Sell>, curve: &dyn CurveModel, sell_result: &SellResult, gross_token_amount: u64, fees: &TradeFees, hash : &str) -> Result<()> {
+    let accounts = ctx.accounts;
+    let user = accounts.user.to_account_info();
+    let mut trade = CurveTrade {
+        trader: accounts.user.key(),
+        mint: &accounts.mint,
+        bonding_curve: &mut accounts.bonding_curve,
+        bonding_curve_bump: ctx.bumps.bonding_curve,
+        bonding_curve_token_account: &mut accounts.bonding_curve_token_account,
+        trader_token_account: &mut accounts.user_token_account,
+        user_curve_stats: &mut accounts.user_curve_stats,
+        quote: TradeQuote::Sol(SolQuote {
+            user_invite_account: &mut accounts.user_invite_account,
+            parent_invite_account: &mut accounts.parent_invite_account,
+            fee_recipient_invite_account: &mut accounts.fee_recipient_invite_account,
+            creator_fee_recipient_invite_account: &mut accounts.creator_fee_recipient_invite_account,
+            fee_account: &mut accounts.fee_account,
+            incinerator: accounts.incinerator.as_ref().map(|incinerator| incinerator.to_account_info()),
+            system_program: accounts.system_program.to_account_info(),
+        }),
+        token_program: accounts.token_program.to_account_info(),
     };
 
-    let serialized = serde_json::to_string(&trade_event).unwrap();
-
-    msg!("tradelog:{}", serialized);
-
-    Ok(())
+    settle_sell(&mut trade, user.clone(), &[], user, curve, sell_result, gross_token_amount, fees, hash)
 }
@@ -0,0 +1,519 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{incinerator, system_instruction},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    quote_sell, settle_sell,
+    state::{BondingCurve, FeeAccount, Global, SellTrigger, TriggerDirection, UserCurveStats, UserInviteStats},
+    CurveLaunchpadError, CurveTrade, FillSellTriggerEvent, SellTriggerEvent, SolQuote, TradeQuote,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct PlaceSellTrigger<'info> {
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    #[account(address = bonding_curve.mint.key())]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        constraint = bonding_curve.is_native_quote() @ CurveLaunchpadError::QuoteMintMismatch,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SellTrigger::INIT_SPACE,
+        seeds = [SellTrigger::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref(), &id.to_le_bytes()],
+        bump,
+    )]
+    sell_trigger: Box<Account<'info, SellTrigger>>,
+
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = sell_trigger,
+        token::token_program = token_program,
+        seeds = [SellTrigger::SEED_PREFIX, mint.key().as_ref(), sell_trigger.key().as_ref()],
+        bump,
+    )]
+    sell_trigger_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + UserCurveStats::INIT_SPACE,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Box<Account<'info, UserCurveStats>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+/// Escrows `token_amount` tokens and the `keeper_fee` behind a stop-loss or
+/// take-profit at `trigger_price`.
+pub fn place_sell_trigger(
+    ctx: Context<PlaceSellTrigger>,
+    id: u64,
+    direction: TriggerDirection,
+    token_amount: u64,
+    trigger_price: u128,
+    max_slippage_bps: u64,
+    keeper_fee: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+    SellTrigger::validate_terms(token_amount, trigger_price, max_slippage_bps)?;
+
+    let owner = &ctx.accounts.owner;
+    if keeper_fee > 0 {
+        let sell_trigger_info = ctx.accounts.sell_trigger.to_account_info();
+        anchor_lang::solana_program::program::invoke(
+            &system_instruction::transfer(owner.key, sell_trigger_info.key, keeper_fee),
+            &[
+                owner.to_account_info(),
+                sell_trigger_info,
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.sell_trigger_token_account.to_account_info(),
+                authority: owner.to_account_info(),
+            },
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    //the trigger sells what it actually holds, after any transfer fee
+    ctx.accounts.sell_trigger_token_account.reload()?;
+
+    let sell_trigger = &mut ctx.accounts.sell_trigger;
+    sell_trigger.owner = owner.key();
+    sell_trigger.bonding_curve = ctx.accounts.bonding_curve.key();
+    sell_trigger.mint = ctx.accounts.mint.key();
+    sell_trigger.id = id;
+    sell_trigger.direction = direction;
+    sell_trigger.token_amount = ctx.accounts.sell_trigger_token_account.amount;
+    sell_trigger.trigger_price = trigger_price;
+    sell_trigger.max_slippage_bps = max_slippage_bps;
+    sell_trigger.keeper_fee = keeper_fee;
+    sell_trigger.token_account = ctx.accounts.sell_trigger_token_account.key();
+
+    log_sell_trigger(sell_trigger, Clock::get()?.unix_timestamp, "placeTrigger");
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSellTrigger<'info> {
+    #[account(mut)]
+    keeper: Signer<'info>,
+
+    /// CHECK: receives the SOL of the sell and the trigger's lamports
+    #[account(
+        mut,
+        address = sell_trigger.owner,
+    )]
+    owner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        address = bonding_curve.mint.key()
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        constraint = bonding_curve.is_native_quote() @ CurveLaunchpadError::QuoteMintMismatch,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = bonding_curve,
+        seeds = [SellTrigger::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref(), &sell_trigger.id.to_le_bytes()],
+        bump,
+        close = owner,
+    )]
+    sell_trigger: Box<Account<'info, SellTrigger>>,
+
+    #[account(
+        mut,
+        address = sell_trigger.token_account,
+    )]
+    sell_trigger_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Gets whatever is left in the trigger's escrow after the sell
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [UserCurveStats::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    user_curve_stats: Box<Account<'info, UserCurveStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,owner.key().as_ref()],
+        bump
+    )]
+    user_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,user_invite_account.parent.key().as_ref()],
+        bump
+    )]
+    parent_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,global.fee_recipient.key().as_ref()],
+        bump
+    )]
+    fee_recipient_invite_account: Box<Account<'info,UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,bonding_curve.creator.key().as_ref()],
+        bump
+    )]
+    creator_fee_recipient_invite_account: Box<Account<'info,UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account : Box<Account<'info,FeeAccount>>,
+
+    /// CHECK: only receives sniper fees, needed when the curve burns them
+    #[account(
+        mut,
+        address = incinerator::ID,
+    )]
+    incinerator: Option<UncheckedAccount<'info>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless crank selling a trigger's tokens through the same
+/// settlement as `sell` once the spot price has crossed its trigger price.
+/// The keeper gets the trigger's `keeper_fee`, the owner the SOL.
+pub fn execute_sell_trigger(ctx: Context<ExecuteSellTrigger>, hash: &str) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    require!(ctx.accounts.user_invite_account.is_init,CurveLaunchpadError::InviteAccountError);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let sell_trigger = &ctx.accounts.sell_trigger;
+    let spot_price = ctx.accounts.bonding_curve.spot_price().unwrap_or_default();
+    require!(
+        sell_trigger.is_triggered(spot_price),
+        CurveLaunchpadError::TriggerPriceNotReached,
+    );
+
+    let quote = quote_sell(
+        &ctx.accounts.bonding_curve,
+        &ctx.accounts.mint.to_account_info(),
+        sell_trigger.token_amount,
+        timestamp as u64,
+    )?;
+
+    //the slippage bound is taken against the trigger price, with the fee in force now
+    let fee_basis_points = ctx.accounts.bonding_curve.fees.total()
+        + ctx.accounts.bonding_curve.sniper_fee_basis_points(timestamp as u64);
+    let min_sol_output = sell_trigger.min_sol_output(quote.sell_result.token_amount, fee_basis_points);
+    let sol_amount = quote.sell_result.sol_amount - quote.fees.total_fee;
+    require!(
+        sol_amount >= min_sol_output,
+        CurveLaunchpadError::MinSOLOutputExceeded,
+    );
+
+    let sell_trigger_info = sell_trigger.to_account_info();
+    let sell_trigger_key = sell_trigger.key();
+    let owner_key = sell_trigger.owner;
+    let token_amount = sell_trigger.token_amount;
+    let keeper_fee = sell_trigger.keeper_fee;
+    let is_stop_loss = sell_trigger.direction == TriggerDirection::StopLoss;
+    let id_bytes = sell_trigger.id.to_le_bytes();
+
+    let accounts = ctx.accounts;
+    let bonding_curve_key = accounts.bonding_curve.key();
+    let signer: [&[&[u8]]; 1] = [&[
+        SellTrigger::SEED_PREFIX,
+        bonding_curve_key.as_ref(),
+        owner_key.as_ref(),
+        &id_bytes,
+        &[ctx.bumps.sell_trigger],
+    ]];
+
+    let mut trade = CurveTrade {
+        trader: owner_key,
+        mint: &accounts.mint,
+        bonding_curve: &mut accounts.bonding_curve,
+        bonding_curve_bump: ctx.bumps.bonding_curve,
+        bonding_curve_token_account: &mut accounts.bonding_curve_token_account,
+        trader_token_account: &mut accounts.sell_trigger_token_account,
+        user_curve_stats: &mut accounts.user_curve_stats,
+        quote: TradeQuote::Sol(SolQuote {
+            user_invite_account: &mut accounts.user_invite_account,
+            parent_invite_account: &mut accounts.parent_invite_account,
+            fee_recipient_invite_account: &mut accounts.fee_recipient_invite_account,
+            creator_fee_recipient_invite_account: &mut accounts.creator_fee_recipient_invite_account,
+            fee_account: &mut accounts.fee_account,
+            incinerator: accounts.incinerator.as_ref().map(|incinerator| incinerator.to_account_info()),
+            system_program: accounts.system_program.to_account_info(),
+        }),
+        token_program: accounts.token_program.to_account_info(),
+    };
+
+    settle_sell(
+        &mut trade,
+        sell_trigger_info.clone(),
+        &signer,
+        accounts.owner.to_account_info(),
+        quote.curve.as_ref(),
+        &quote.sell_result,
+        token_amount,
+        &quote.fees,
+        hash,
+    )?;
+
+    //tokens anyone sent to the escrow on top of the trigger would block the
+    //close, settle_sell reloaded the balance so they go back to the owner
+    let leftover_amount = accounts.sell_trigger_token_account.amount;
+    if leftover_amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.sell_trigger_token_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.owner_token_account.to_account_info(),
+                    authority: sell_trigger_info.clone(),
+                },
+                &signer,
+            ),
+            leftover_amount,
+            accounts.mint.decimals,
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: accounts.sell_trigger_token_account.to_account_info(),
+            destination: accounts.owner.to_account_info(),
+            authority: sell_trigger_info.clone(),
+        },
+        &signer,
+    ))?;
+
+    **sell_trigger_info.try_borrow_mut_lamports()? -= keeper_fee;
+    **accounts.keeper.try_borrow_mut_lamports()? += keeper_fee;
+
+    let fill_event = FillSellTriggerEvent {
+        trigger: sell_trigger_key.to_string(),
+        owner: owner_key.to_string(),
+        keeper: accounts.keeper.key().to_string(),
+        mint: accounts.mint.key().to_string(),
+        is_stop_loss,
+        token_amount,
+        sol_amount,
+        min_sol_output,
+        spot_price,
+        keeper_fee,
+        timestamp,
+    };
+
+    let serialized = serde_json::to_string(&fill_event).unwrap();
+
+    msg!("fillTrigger:{}", serialized);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelSellTrigger<'info> {
+    /// The owner, or anyone once the curve has completed.
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    /// CHECK: gets the tokens and the trigger's lamports back
+    #[account(
+        mut,
+        address = sell_trigger.owner,
+    )]
+    owner: UncheckedAccount<'info>,
+
+    #[account(address = sell_trigger.mint)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = sell_trigger.bonding_curve)]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [SellTrigger::SEED_PREFIX, bonding_curve.key().as_ref(), owner.key().as_ref(), &sell_trigger.id.to_le_bytes()],
+        bump,
+        close = owner,
+    )]
+    sell_trigger: Box<Account<'info, SellTrigger>>,
+
+    #[account(
+        mut,
+        address = sell_trigger.token_account,
+    )]
+    sell_trigger_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Returns a trigger's tokens to its owner. Only the owner can cancel while
+/// the curve trades. Once it has completed the trigger can never fire, so
+/// anyone can refund it and a keeper doing so earns the `keeper_fee`.
+pub fn cancel_sell_trigger(ctx: Context<CancelSellTrigger>) -> Result<()> {
+    let is_owner = ctx.accounts.authority.key() == ctx.accounts.owner.key();
+    require!(
+        is_owner || !ctx.accounts.bonding_curve.is_trading(),
+        CurveLaunchpadError::InvalidAuthority,
+    );
+
+    let sell_trigger = &ctx.accounts.sell_trigger;
+    let bonding_curve_key = sell_trigger.bonding_curve;
+    let owner_key = sell_trigger.owner;
+    let id_bytes = sell_trigger.id.to_le_bytes();
+    let signer: [&[&[u8]]; 1] = [&[
+        SellTrigger::SEED_PREFIX,
+        bonding_curve_key.as_ref(),
+        owner_key.as_ref(),
+        &id_bytes,
+        &[ctx.bumps.sell_trigger],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.sell_trigger_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: sell_trigger.to_account_info(),
+            },
+            &signer,
+        ),
+        ctx.accounts.sell_trigger_token_account.amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.sell_trigger_token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: sell_trigger.to_account_info(),
+        },
+        &signer,
+    ))?;
+
+    if !is_owner {
+        let keeper_fee = sell_trigger.keeper_fee;
+        **sell_trigger.to_account_info().try_borrow_mut_lamports()? -= keeper_fee;
+        **ctx.accounts.authority.try_borrow_mut_lamports()? += keeper_fee;
+    }
+
+    log_sell_trigger(sell_trigger, Clock::get()?.unix_timestamp, "cancelTrigger");
+
+    Ok(())
+}
+
+fn log_sell_trigger(sell_trigger: &Account<SellTrigger>, timestamp: i64, name: &str) {
+    let trigger_event = SellTriggerEvent {
+        trigger: sell_trigger.key().to_string(),
+        owner: sell_trigger.owner.to_string(),
+        mint: sell_trigger.mint.to_string(),
+        is_stop_loss: sell_trigger.direction == TriggerDirection::StopLoss,
+        token_amount: sell_trigger.token_amount,
+        trigger_price: sell_trigger.trigger_price,
+        max_slippage_bps: sell_trigger.max_slippage_bps,
+        keeper_fee: sell_trigger.keeper_fee,
+        timestamp,
+    };
+
+    let serialized = serde_json::to_string(&trigger_event).unwrap();
+
+    msg!("{}:{}", name, serialized);
+}
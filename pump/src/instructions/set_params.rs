@@ -1,4 +1,7 @@
-use crate::{state::Global, CurveLaunchpadError};
+use crate::{
+    state::{CurveTemplate, Global, LaunchProtection, MintSuffixMode, MintSuffixPolicy, SandwichProtection},
+    CurveLaunchpadError, SetMintSuffixPolicyEvent,
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -9,6 +12,7 @@ pub struct SetParams<'info> {
         seeds = [Global::SEED_PREFIX],
         bump,
     )]
+    global: Box<Account<'info, Global>>,
 
     #[account(mut)]
     authority: Signer<'info>,
@@ -28,10 +32,20 @@ pub fn set_amm_params(
     let global = &mut ctx.accounts.global;
     require!(global.initialized, CurveLaunchpadError::NotInitialized);
 
+    //the curve can never sell its whole virtual token reserve, and needs a price to start from
+    require!(
+        initial_virtual_sol_reserves > 0
+            && initial_real_token_reserves > 0
+            && initial_real_token_reserves < initial_virtual_token_reserves
+            && initial_real_token_reserves <= initial_token_supply,
+        CurveLaunchpadError::InvalidAmmParams
+    );
+
     global.initial_virtual_token_reserves = initial_virtual_token_reserves;
     global.initial_virtual_sol_reserves = initial_virtual_sol_reserves;
     global.initial_real_token_reserves = initial_real_token_reserves;
     global.initial_token_supply = initial_token_supply;
+    CurveTemplate::from_global(global).validate_protocol_allocation(global)?;
 
     Ok(())
 }
@@ -47,8 +61,16 @@ pub fn set_fee_params(
     let global = &mut ctx.accounts.global;
     require!(global.initialized, CurveLaunchpadError::NotInitialized);
 
+    //pool swaps and curves without a fee override take these fees out of the trade
+    require!(
+        (fee_basis_points as u128 + creator_fee_basis_points as u128 + invite_fee_basis_points as u128) < 10000,
+        CurveLaunchpadError::InvalidAmmParams
+    );
+
     global.fee_recipient = fee_recipient;
     global.fee_basis_points = fee_basis_points;
+    global.creator_fee_basis_points = creator_fee_basis_points;
+    global.withdraw_authority = withdraw_authority;
     global.invite_fee_basis_points = invite_fee_basis_points;
 
     Ok(())
@@ -62,4 +84,92 @@ pub fn set_protocol_fee_address(ctx: Context<SetParams>,protocol_token_alloc_rec
     ctx.accounts.global.fee_recipient = fee_recipient;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Sets the share of each new launch's supply reserved for the protocol and
+/// how it vests. Curves that already exist keep their allocation. The share
+/// is checked against `Global`'s launch parameters and against every curve
+/// template passed in the remaining accounts.
+pub fn set_protocol_token_alloc(
+    ctx: Context<SetParams>,
+    protocol_token_alloc_points: u64,
+    cliff_seconds: u64,
+    vesting_seconds: u64,
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    require!(global.initialized, CurveLaunchpadError::NotInitialized);
+    require!(protocol_token_alloc_points < 10000, CurveLaunchpadError::ProtocolAllocationTooLarge);
+    require!(cliff_seconds <= vesting_seconds, CurveLaunchpadError::InvalidVestingTerms);
+
+    global.protocol_token_alloc_points = protocol_token_alloc_points;
+    //`set_amm_params` checks it once the global launch parameters are set
+    if global.initial_real_token_reserves > 0 {
+        CurveTemplate::from_global(global).validate_protocol_allocation(global)?;
+    }
+    for curve_template in ctx.remaining_accounts {
+        require_keys_eq!(*curve_template.owner, crate::ID, CurveLaunchpadError::InvalidCurveTemplate);
+        CurveTemplate::try_deserialize(&mut &curve_template.try_borrow_data()?[..])?
+            .validate_protocol_allocation(global)?;
+    }
+    global.protocol_token_alloc_cliff_seconds = cliff_seconds;
+    global.protocol_token_alloc_vesting_seconds = vesting_seconds;
+
+    msg!(
+        "Set protocol token allocation to {} points, cliff {}s, vesting {}s",
+        protocol_token_alloc_points,
+        cliff_seconds,
+        vesting_seconds
+    );
+
+    Ok(())
+}
+
+/// Sets the launch protection of curves created without a template, curves
+/// that already exist keep theirs.
+pub fn set_launch_protection(ctx: Context<SetParams>, launch_protection: LaunchProtection) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    require!(global.initialized, CurveLaunchpadError::NotInitialized);
+
+    launch_protection.validate()?;
+    global.launch_protection = launch_protection;
+
+    msg!("Set launch protection {:?}", launch_protection);
+
+    Ok(())
+}
+
+/// Sets the sandwich protection of curves created without a template, curves
+/// that already exist keep theirs.
+pub fn set_sandwich_protection(ctx: Context<SetParams>, sandwich_protection: SandwichProtection) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    require!(global.initialized, CurveLaunchpadError::NotInitialized);
+
+    global.sandwich_protection = sandwich_protection;
+
+    msg!("Set sandwich protection {:?}", sandwich_protection);
+
+    Ok(())
+}
+
+/// Sets the suffix `create` and `create_token_2022` require of mint addresses.
+/// Curves that already exist are not affected.
+pub fn set_mint_suffix_policy(ctx: Context<SetParams>, mode: MintSuffixMode, suffix: String) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    require!(global.initialized, CurveLaunchpadError::NotInitialized);
+
+    global.mint_suffix_policy = MintSuffixPolicy::new(mode, &suffix)?;
+
+    let policy_event = SetMintSuffixPolicyEvent {
+        authority: ctx.accounts.authority.key().to_string(),
+        enabled: mode != MintSuffixMode::Disabled,
+        case_sensitive: mode == MintSuffixMode::CaseSensitive,
+        suffix: global.mint_suffix_policy.suffix_str(),
+        timestamp: Clock::get()?.unix_timestamp,
+    };
+
+    let serialized = serde_json::to_string(&policy_event).unwrap();
+
+    msg!("setMintSuffixPolicy:{}", serialized);
+
+    Ok(())
+}
@@ -0,0 +1,276 @@
+use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    amm::cpmm,
+    calculate_trade_fees, transfer_fee,
+    state::{FeeAccount, Global, Pool, UserInviteStats},
+    CurveLaunchpadError, SwapEvent,
+};
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        address = pool.mint.key()
+    )]
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [Pool::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program,
+        seeds=[Pool::SEED_PREFIX, mint.key().as_ref(), pool.key().as_ref()],
+        bump
+    )]
+    pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,user.key().as_ref()],
+        bump
+    )]
+    user_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,user_invite_account.parent.key().as_ref()],
+        bump
+    )]
+    parent_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,global.fee_recipient.key().as_ref()],
+        bump
+    )]
+    fee_recipient_invite_account: Box<Account<'info,UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,pool.creator.key().as_ref()],
+        bump
+    )]
+    creator_fee_recipient_invite_account: Box<Account<'info,UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account : Box<Account<'info,FeeAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Swaps against a graduated curve's pool. Buys spend `amount_in` SOL, sells
+/// spend `amount_in` tokens. The fee is always taken in SOL, out of the SOL
+/// going in on buys and out of the SOL coming out on sells. Sells are priced
+/// on the tokens that reach the pool after any Token-2022 transfer fee.
+pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64, is_buy: bool, hash: &str) -> Result<()> {
+    require!(ctx.accounts.user_invite_account.is_init,CurveLaunchpadError::InviteAccountError);
+
+    let sol_reserves = ctx.accounts.pool.sol_reserves;
+    let token_reserves = ctx.accounts.pool.token_reserves;
+
+    let (sol_amount, token_amount, net_token_amount, fees) = if is_buy {
+        require!(amount_in > 0, CurveLaunchpadError::MinBuy);
+
+        let fees = calculate_trade_fees(amount_in, &ctx.accounts.global.trade_fee_basis_points(), 0);
+        let sol_amount = amount_in
+            .checked_sub(fees.total_fee)
+            .ok_or(CurveLaunchpadError::InsufficientSOL)?;
+        let token_amount = cpmm::swap_output(sol_reserves, token_reserves, sol_amount)
+            .ok_or(CurveLaunchpadError::InsufficientLiquidity)?;
+
+        //the user only gets what's left after any transfer fee
+        let net_token_amount = token_amount - transfer_fee(&ctx.accounts.mint.to_account_info(), token_amount)?;
+        require!(
+            net_token_amount >= min_amount_out,
+            CurveLaunchpadError::MinTokensOutputExceeded,
+        );
+
+        (sol_amount, token_amount, net_token_amount, fees)
+    } else {
+        require!(amount_in > 0, CurveLaunchpadError::MinSell);
+
+        let net_token_amount = amount_in - transfer_fee(&ctx.accounts.mint.to_account_info(), amount_in)?;
+        let sol_amount = cpmm::swap_output(token_reserves, sol_reserves, net_token_amount)
+            .ok_or(CurveLaunchpadError::InsufficientLiquidity)?;
+        let fees = calculate_trade_fees(sol_amount, &ctx.accounts.global.trade_fee_basis_points(), 0);
+
+        let sol_amount_out = sol_amount
+            .checked_sub(fees.total_fee)
+            .ok_or(CurveLaunchpadError::InsufficientSOL)?;
+        require!(
+            sol_amount_out >= min_amount_out,
+            CurveLaunchpadError::MinSOLOutputExceeded,
+        );
+
+        (sol_amount, amount_in, net_token_amount, fees)
+    };
+
+    let from_account = &ctx.accounts.user;
+
+    if is_buy {
+        //check if the user has enough SOL
+        require!(
+            ctx.accounts.user.lamports() >= amount_in,
+            CurveLaunchpadError::InsufficientSOL,
+        );
+
+        // transfer SOL to the pool and fee account
+        let transfer_instruction =
+            system_instruction::transfer(from_account.key, ctx.accounts.pool.to_account_info().key, sol_amount);
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_instruction,
+            &[
+                from_account.to_account_info(),
+                ctx.accounts.pool.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[],
+        )?;
+
+        let transfer_instruction =
+            system_instruction::transfer(from_account.key, ctx.accounts.fee_account.to_account_info().key, fees.total_fee);
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_instruction,
+            &[
+                from_account.to_account_info(),
+                ctx.accounts.fee_account.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[],
+        )?;
+
+        //transfer SPL
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.pool_token_account.to_account_info().clone(),
+            mint: ctx.accounts.mint.to_account_info().clone(),
+            to: ctx.accounts.user_token_account.to_account_info().clone(),
+            authority: ctx.accounts.pool.to_account_info().clone(),
+        };
+
+        let signer: [&[&[u8]]; 1] = [&[
+            Pool::SEED_PREFIX,
+            ctx.accounts.mint.to_account_info().key.as_ref(),
+            &[ctx.bumps.pool],
+        ]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                &signer,
+            ),
+            token_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    } else {
+        let pool_balance_before = ctx.accounts.pool_token_account.amount;
+
+        //transfer SPL
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.user_token_account.to_account_info().clone(),
+            mint: ctx.accounts.mint.to_account_info().clone(),
+            to: ctx.accounts.pool_token_account.to_account_info().clone(),
+            authority: ctx.accounts.user.to_account_info().clone(),
+        };
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+            ),
+            token_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        ctx.accounts.pool_token_account.reload()?;
+        require!(
+            ctx.accounts.pool_token_account.amount == pool_balance_before + net_token_amount,
+            CurveLaunchpadError::TransferFeeMismatch,
+        );
+
+        //transfer SOL back to user
+        **ctx.accounts.pool.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+        **ctx.accounts.user.try_borrow_mut_lamports()? += sol_amount - fees.total_fee;
+        **ctx.accounts.fee_account.to_account_info().try_borrow_mut_lamports()? += fees.total_fee;
+    }
+
+    ctx.accounts.fee_account.received += fees.total_fee;
+    let is_ok = ctx.accounts.fee_account.check(ctx.accounts.fee_account.get_lamports());
+    require!(is_ok,CurveLaunchpadError::FeeAccountStatusAbnormal);
+
+    ctx.accounts.fee_recipient_invite_account.profit_claimable += fees.protocol_fee;
+    ctx.accounts.creator_fee_recipient_invite_account.profit_claimable += fees.creator_fee;
+    ctx.accounts.parent_invite_account.profit_claimable += fees.invite_fee;
+
+    ctx.accounts.user_invite_account.profit_to_parent += fees.invite_fee;
+    ctx.accounts.parent_invite_account.profit_from_child += fees.invite_fee;
+
+    let curr_time = Clock::get()?.unix_timestamp;
+
+    let pool = &mut ctx.accounts.pool;
+    if is_buy {
+        pool.sol_reserves += sol_amount;
+        pool.token_reserves -= token_amount;
+    } else {
+        pool.sol_reserves -= sol_amount;
+        pool.token_reserves += net_token_amount;
+    }
+    pool.update_time = curr_time as u64;
+
+    let swap_event = SwapEvent {
+        mint: pool.mint.to_string(),
+        sol_amount,
+        token_amount,
+        net_token_amount,
+        fee: fees.total_fee,
+        is_buy,
+        user: ctx.accounts.user.key().to_string(),
+        timestamp: curr_time,
+        sol_reserves: pool.sol_reserves,
+        token_reserves: pool.token_reserves,
+        hash: hash.to_string(),
+    };
+
+    let serialized = serde_json::to_string(&swap_event).unwrap();
+
+    msg!("swaplog:{}", serialized);
+
+    Ok(())
+}
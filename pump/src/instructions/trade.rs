@@ -0,0 +1,763 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::system_instruction,
+    system_program::{self, Allocate, Assign, CreateAccount},
+};
+use anchor_spl::{
+    token::{self, Burn, Transfer},
+    token_interface::{self, Mint, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    amm::{BuyResult, CurveModel, SellResult},
+    calculate_trade_fees, credit_sniper_fee, transfer_fee,
+    state::{
+        AllowlistProof, BondingCurve, FeeAccount, QuoteFeeLedger, QuoteLedgerCredit, SniperFeeDestination,
+        UserCurveStats, UserInviteStats, price_move_bps,
+    },
+    CompleteEvent, CurveLaunchpadError, TradeEvent, TradeFees, WSOL_MINT_ADDRESS,
+};
+
+/// Accounts a curve trade settles against. `buy` and `sell` fill them
+/// from the signing user, the cranks from the owner they trade for.
+pub struct CurveTrade<'a, 'info> {
+    pub trader: Pubkey,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub bonding_curve: &'a mut Account<'info, BondingCurve>,
+    pub bonding_curve_bump: u8,
+    pub bonding_curve_token_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+    /// Receives the tokens of a buy, sends those of a sell.
+    pub trader_token_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub user_curve_stats: &'a mut Account<'info, UserCurveStats>,
+    /// Where the quote side of the trade and its fees settle.
+    pub quote: TradeQuote<'a, 'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Quote side of a curve trade.
+pub enum TradeQuote<'a, 'info> {
+    /// Native SOL, held as lamports by the curve.
+    Sol(SolQuote<'a, 'info>),
+    /// An allowlisted SPL mint, held in the curve's quote vault.
+    Token(Box<TokenQuote<'info>>),
+}
+
+/// Fee accounts of a SOL curve. Fees are kept in the fee account and
+/// credited to `UserInviteStats`, sniper fees to burn go to the incinerator.
+pub struct SolQuote<'a, 'info> {
+    pub user_invite_account: &'a mut Account<'info, UserInviteStats>,
+    pub parent_invite_account: &'a mut Account<'info, UserInviteStats>,
+    pub fee_recipient_invite_account: &'a mut Account<'info, UserInviteStats>,
+    pub creator_fee_recipient_invite_account: &'a mut Account<'info, UserInviteStats>,
+    pub fee_account: &'a mut Account<'info, FeeAccount>,
+    pub incinerator: Option<AccountInfo<'info>>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Vaults of a quote asset curve. Fees are kept in the quote fee vault and
+/// credited to the quote ledgers, sniper fees to burn are burned.
+pub struct TokenQuote<'info> {
+    pub quote_mint: AccountInfo<'info>,
+    pub bonding_curve_quote_vault: AccountInfo<'info>,
+    pub fee_quote_vault: AccountInfo<'info>,
+    pub user_ledger: QuoteLedger<'info>,
+    pub parent_ledger: QuoteLedger<'info>,
+    pub fee_recipient_ledger: QuoteLedger<'info>,
+    pub creator_ledger: QuoteLedger<'info>,
+    /// Pays the rent of the ledgers the trade creates.
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub quote_token_program: AccountInfo<'info>,
+}
+
+/// A quote ledger and the key it belongs to, created on its first credit.
+pub struct QuoteLedger<'info> {
+    pub key: Pubkey,
+    pub account: AccountInfo<'info>,
+    pub bump: u8,
+}
+
+/// Where the SOL, or the quote tokens, paying for a buy come from.
+pub enum SolSource<'info> {
+    /// A system account signing the transaction.
+    Wallet(AccountInfo<'info>),
+    /// An escrow owned by this program, debited directly.
+    Escrow(AccountInfo<'info>),
+    /// A quote token account and its owner signing for it, on quote asset curves.
+    QuoteToken {
+        account: AccountInfo<'info>,
+        authority: AccountInfo<'info>,
+    },
+}
+
+impl<'info> SolSource<'info> {
+    /// `program` is the system program for SOL and the quote token program
+    /// for quote tokens.
+    fn transfer(&self, to: &AccountInfo<'info>, program: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        match self {
+            SolSource::Wallet(from) => {
+                anchor_lang::solana_program::program::invoke(
+                    &system_instruction::transfer(from.key, to.key, amount),
+                    &[from.clone(), to.clone(), program.clone()],
+                )?;
+            }
+            SolSource::Escrow(from) => {
+                **from.try_borrow_mut_lamports()? -= amount;
+                **to.try_borrow_mut_lamports()? += amount;
+            }
+            SolSource::QuoteToken { account, authority } => {
+                token::transfer(
+                    CpiContext::new(
+                        program.clone(),
+                        Transfer {
+                            from: account.clone(),
+                            to: to.clone(),
+                            authority: authority.clone(),
+                        },
+                    ),
+                    amount,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, 'info> SolQuote<'a, 'info> {
+    /// Credits the fee parts and the sniper fee, returns the part to burn.
+    fn credit_fees(&mut self, fees: &TradeFees, sniper_fee_destination: SniperFeeDestination) -> u64 {
+        let burned_fee = credit_sniper_fee(
+            fees.sniper_fee,
+            sniper_fee_destination,
+            self.fee_recipient_invite_account,
+            self.creator_fee_recipient_invite_account,
+        );
+
+        self.fee_recipient_invite_account.profit_claimable += fees.protocol_fee;
+        self.creator_fee_recipient_invite_account.profit_claimable += fees.creator_fee;
+        self.parent_invite_account.profit_claimable += fees.invite_fee;
+
+        self.user_invite_account.profit_to_parent += fees.invite_fee;
+        self.parent_invite_account.profit_from_child += fees.invite_fee;
+
+        burned_fee
+    }
+
+    /// Records the fee that reached the fee account.
+    fn keep_fee(&mut self, kept_fee: u64) -> Result<()> {
+        self.fee_account.received += kept_fee;
+        let is_ok = self.fee_account.check(self.fee_account.get_lamports());
+        require!(is_ok,CurveLaunchpadError::FeeAccountStatusAbnormal);
+        Ok(())
+    }
+
+    fn incinerator(&self) -> Result<&AccountInfo<'info>> {
+        Ok(self
+            .incinerator
+            .as_ref()
+            .ok_or(CurveLaunchpadError::IncineratorRequired)?)
+    }
+}
+
+impl<'info> TokenQuote<'info> {
+    /// Credits the fee parts and the sniper fee to the quote ledgers, the same
+    /// way `SolQuote` credits them to `UserInviteStats`, and returns the part to
+    /// burn. The four ledgers often alias, a creator trading their own curve
+    /// say, so the credits are merged per ledger and each is written once.
+    fn credit_fees(&self, fees: &TradeFees, sniper_fee_destination: SniperFeeDestination) -> Result<u64> {
+        let (protocol_sniper_fee, creator_sniper_fee, burned_fee) = match sniper_fee_destination {
+            SniperFeeDestination::Protocol => (fees.sniper_fee, 0, 0),
+            SniperFeeDestination::Creator => (0, fees.sniper_fee, 0),
+            SniperFeeDestination::Burn => (0, 0, fees.sniper_fee),
+        };
+
+        let ledgers = [&self.user_ledger, &self.parent_ledger, &self.fee_recipient_ledger, &self.creator_ledger];
+        let credits = [
+            (ledgers[0].key, QuoteLedgerCredit { profit_to_parent: fees.invite_fee, ..Default::default() }),
+            (
+                ledgers[1].key,
+                QuoteLedgerCredit { profit_from_child: fees.invite_fee, profit_claimable: fees.invite_fee, ..Default::default() },
+            ),
+            (
+                ledgers[2].key,
+                QuoteLedgerCredit { profit_claimable: fees.protocol_fee + protocol_sniper_fee, ..Default::default() },
+            ),
+            (
+                ledgers[3].key,
+                QuoteLedgerCredit { profit_claimable: fees.creator_fee + creator_sniper_fee, ..Default::default() },
+            ),
+        ];
+
+        for (index, credit) in QuoteLedgerCredit::merge_by_key(&credits) {
+            self.credit_ledger(ledgers[index], &credit)?;
+        }
+
+        Ok(burned_fee)
+    }
+
+    fn credit_ledger(&self, ledger: &QuoteLedger<'info>, credit: &QuoteLedgerCredit) -> Result<()> {
+        let quote_mint = self.quote_mint.key();
+
+        let mut quote_ledger = if ledger.account.owner == &system_program::ID {
+            self.create_ledger(ledger)?;
+            QuoteFeeLedger {
+                key: ledger.key,
+                quote_mint,
+                profit_from_child: 0,
+                profit_to_parent: 0,
+                profit_claimable: 0,
+                profit_claim_accumulated: 0,
+            }
+        } else {
+            require_keys_eq!(*ledger.account.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+            QuoteFeeLedger::try_deserialize(&mut &ledger.account.try_borrow_data()?[..])?
+        };
+
+        quote_ledger.init_if_needed(ledger.key, quote_mint);
+        quote_ledger.credit(credit);
+        let mut data = ledger.account.try_borrow_mut_data()?;
+        quote_ledger.try_serialize(&mut &mut data[..])
+    }
+
+    /// Creates a quote ledger paid by the payer, like `init` would, also when
+    /// someone has already sent lamports to its address.
+    fn create_ledger(&self, ledger: &QuoteLedger<'info>) -> Result<()> {
+        let quote_mint = self.quote_mint.key();
+        let signer: [&[&[u8]]; 1] = [&[QuoteFeeLedger::SEED_PREFIX, quote_mint.as_ref(), ledger.key.as_ref(), &[ledger.bump]]];
+        let space = 8 + QuoteFeeLedger::INIT_SPACE;
+        let rent = Rent::get()?.minimum_balance(space);
+
+        if ledger.account.lamports() == 0 {
+            return system_program::create_account(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    CreateAccount {
+                        from: self.payer.clone(),
+                        to: ledger.account.clone(),
+                    },
+                    &signer,
+                ),
+                rent,
+                space as u64,
+                &crate::ID,
+            );
+        }
+
+        let top_up = rent.saturating_sub(ledger.account.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.clone(),
+                    system_program::Transfer {
+                        from: self.payer.clone(),
+                        to: ledger.account.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                self.system_program.clone(),
+                Allocate {
+                    account_to_allocate: ledger.account.clone(),
+                },
+                &signer,
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                self.system_program.clone(),
+                Assign {
+                    account_to_assign: ledger.account.clone(),
+                },
+                &signer,
+            ),
+            &crate::ID,
+        )
+    }
+}
+
+/// Priced buy of a curve, ready to settle.
+pub struct BuyQuote {
+    pub curve: Box<dyn CurveModel>,
+    pub buy_result: BuyResult,
+    pub fees: TradeFees,
+    /// Tokens reaching the buyer, after any transfer fee.
+    pub net_token_amount: u64,
+}
+
+/// Prices a buy of `token_amount`, capped at the curve's token balance, like `buy`.
+pub fn quote_buy(
+    bonding_curve: &BondingCurve,
+    bonding_curve_token_balance: u64,
+    mint: &AccountInfo,
+    token_amount: u64,
+    now: u64,
+) -> Result<BuyQuote> {
+    let mut curve = bonding_curve
+        .curve_model()
+        .ok_or(CurveLaunchpadError::InvalidCurveParams)?;
+
+    let sniper_fee_basis_points = bonding_curve.sniper_fee_basis_points(now);
+
+    let targe_token_amount = token_amount.min(bonding_curve_token_balance);
+    let buy_result = curve
+        .apply_buy(targe_token_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+    let net_token_amount = buy_result.token_amount - transfer_fee(mint, buy_result.token_amount)?;
+
+    let fees = calculate_trade_fees(buy_result.sol_amount, &bonding_curve.fees, sniper_fee_basis_points);
+
+    Ok(BuyQuote { curve, buy_result, fees, net_token_amount })
+}
+
+/// Prices a buy spending `sol_amount`, fees included, like `buy_exact_sol_in`.
+pub fn quote_buy_exact_sol_in(
+    bonding_curve: &BondingCurve,
+    bonding_curve_token_balance: u64,
+    mint: &AccountInfo,
+    sol_amount: u64,
+    now: u64,
+) -> Result<BuyQuote> {
+    let mut curve = bonding_curve
+        .curve_model()
+        .ok_or(CurveLaunchpadError::InvalidCurveParams)?;
+
+    let sniper_fee_basis_points = bonding_curve.sniper_fee_basis_points(now);
+    let total_fee_basis_points = bonding_curve.fees.total() + sniper_fee_basis_points;
+
+    //the sol amount is fee-inclusive, so the curve only sees what is left after the fee
+    let token_amount = curve
+        .get_buy_token_amount(sol_amount as u128, total_fee_basis_points as u128)
+        .ok_or(CurveLaunchpadError::MinBuy)? as u64;
+
+    let targe_token_amount = token_amount.min(bonding_curve_token_balance);
+    let buy_result = curve
+        .apply_buy(targe_token_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+    let net_token_amount = buy_result.token_amount - transfer_fee(mint, buy_result.token_amount)?;
+
+    let fees = calculate_trade_fees(buy_result.sol_amount, &bonding_curve.fees, sniper_fee_basis_points);
+    require!(
+        buy_result.sol_amount + fees.total_fee <= sol_amount,
+        CurveLaunchpadError::MaxSOLCostExceeded,
+    );
+
+    Ok(BuyQuote { curve, buy_result, fees, net_token_amount })
+}
+
+/// Priced sell of a curve, ready to settle.
+pub struct SellQuote {
+    pub curve: Box<dyn CurveModel>,
+    pub sell_result: SellResult,
+    pub fees: TradeFees,
+}
+
+/// Prices a sell of `token_amount` sent by the seller, like `sell`.
+pub fn quote_sell(bonding_curve: &BondingCurve, mint: &AccountInfo, token_amount: u64, now: u64) -> Result<SellQuote> {
+    let mut curve = bonding_curve
+        .curve_model()
+        .ok_or(CurveLaunchpadError::InvalidCurveParams)?;
+
+    //the curve only buys back what arrives after any transfer fee
+    let net_token_amount = token_amount - transfer_fee(mint, token_amount)?;
+
+    let sniper_fee_basis_points = bonding_curve.sniper_fee_basis_points(now);
+    let sell_result = curve
+        .apply_sell(net_token_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientSOL)?;
+    let fees = calculate_trade_fees(sell_result.sol_amount, &bonding_curve.fees, sniper_fee_basis_points);
+
+    Ok(SellQuote { curve, sell_result, fees })
+}
+
+/// Counts a buy against the buyer's cap while the launch window is open, and
+/// lets only allowlisted wallets buy until the allowlist phase ends.
+pub(crate) fn record_launch_buy(
+    bonding_curve: &BondingCurve,
+    user_curve_stats: &mut UserCurveStats,
+    buyer: &Pubkey,
+    buy_result: &BuyResult,
+    now: u64,
+    allowlist_proof: Option<AllowlistProof>,
+) -> Result<()> {
+    let launch_protection = bonding_curve.launch_protection;
+    if launch_protection.is_active(bonding_curve.create_time, now) {
+        user_curve_stats.record_launch_buy(&launch_protection, buy_result.sol_amount, buy_result.token_amount)?;
+    }
+
+    let allowlist = bonding_curve.allowlist;
+    if allowlist.is_active(now) {
+        let allowlist_proof = allowlist_proof.ok_or(CurveLaunchpadError::NotAllowlisted)?;
+        allowlist.verify(buyer, &allowlist_proof)?;
+        user_curve_stats.record_allowlist_buy(allowlist_proof.max_buy_sol, buy_result.sol_amount)?;
+    }
+
+    Ok(())
+}
+
+/// Moves a priced buy: SOL, or quote tokens, from `sol_source` to the curve
+/// and the fees, tokens to the trader, then updates the curve and completes
+/// it if needed.
+pub fn settle_buy<'info>(
+    trade: &mut CurveTrade<'_, 'info>,
+    sol_source: &SolSource<'info>,
+    curve: &dyn CurveModel,
+    buy_result: &BuyResult,
+    fees: &TradeFees,
+    hash: &str,
+    allowlist_proof: Option<AllowlistProof>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let curr_time = clock.unix_timestamp;
+    let user_curve_stats = &mut trade.user_curve_stats;
+    user_curve_stats.user = trade.trader;
+    user_curve_stats.bonding_curve = trade.bonding_curve.key();
+
+    let sandwich_protection = trade.bonding_curve.sandwich_protection;
+    user_curve_stats.record_trade(&sandwich_protection, clock.slot, true)?;
+    let price_before = trade.bonding_curve.spot_price().unwrap_or_default();
+
+    record_launch_buy(
+        trade.bonding_curve,
+        user_curve_stats,
+        &trade.trader,
+        buy_result,
+        curr_time as u64,
+        allowlist_proof,
+    )?;
+
+    let sniper_fee_destination = trade.bonding_curve.launch_protection.sniper_fee.destination;
+    match (&mut trade.quote, sol_source) {
+        (TradeQuote::Sol(sol), SolSource::Wallet(_) | SolSource::Escrow(_)) => {
+            // transfer SOL to bonding curve
+            sol_source.transfer(&trade.bonding_curve.to_account_info(), &sol.system_program, buy_result.sol_amount)?;
+
+            let burned_fee = sol.credit_fees(fees, sniper_fee_destination);
+            if burned_fee > 0 {
+                sol_source.transfer(sol.incinerator()?, &sol.system_program, burned_fee)?;
+            }
+
+            //transfer SOL to fee account
+            let kept_fee = fees.total_fee - burned_fee;
+            sol_source.transfer(&sol.fee_account.to_account_info(), &sol.system_program, kept_fee)?;
+            sol.keep_fee(kept_fee)?;
+        }
+        (TradeQuote::Token(quote), SolSource::QuoteToken { account, authority }) => {
+            //transfer the quote asset to the bonding curve's vault
+            sol_source.transfer(&quote.bonding_curve_quote_vault, &quote.quote_token_program, buy_result.sol_amount)?;
+
+            let burned_fee = quote.credit_fees(fees, sniper_fee_destination)?;
+            if burned_fee > 0 {
+                token::burn(
+                    CpiContext::new(
+                        quote.quote_token_program.clone(),
+                        Burn {
+                            mint: quote.quote_mint.clone(),
+                            from: account.clone(),
+                            authority: authority.clone(),
+                        },
+                    ),
+                    burned_fee,
+                )?;
+            }
+
+            //transfer the quote asset to the fee vault
+            sol_source.transfer(&quote.fee_quote_vault, &quote.quote_token_program, fees.total_fee - burned_fee)?;
+        }
+        _ => return err!(CurveLaunchpadError::QuoteMintMismatch),
+    }
+
+    //transfer SPL
+    let curve_balance_before = trade.bonding_curve_token_account.amount;
+    let user_balance_before = trade.trader_token_account.amount;
+
+    let cpi_accounts = TransferChecked {
+        from: trade.bonding_curve_token_account.to_account_info(),
+        mint: trade.mint.to_account_info(),
+        to: trade.trader_token_account.to_account_info(),
+        authority: trade.bonding_curve.to_account_info(),
+    };
+
+    let mint_key = trade.mint.key();
+    let signer: [&[&[u8]]; 1] = [&[
+        BondingCurve::SEED_PREFIX,
+        mint_key.as_ref(),
+        &[trade.bonding_curve_bump],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            trade.token_program.clone(),
+            cpi_accounts,
+            &signer,
+        ),
+        buy_result.token_amount,
+        trade.mint.decimals,
+    )?;
+
+    //a transfer fee is withheld from what the user receives, read the actual amounts
+    trade.bonding_curve_token_account.reload()?;
+    trade.trader_token_account.reload()?;
+    let sent_token_amount = curve_balance_before - trade.bonding_curve_token_account.amount;
+    let net_token_amount = trade.trader_token_account.amount - user_balance_before;
+
+    //apply the buy to the bonding curve
+    let bonding_curve = &mut trade.bonding_curve;
+    let reserves = curve.reserves();
+    bonding_curve.real_token_reserves = bonding_curve
+        .real_token_reserves
+        .checked_sub(sent_token_amount)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+    bonding_curve.real_sol_reserves = reserves.real_sol_reserves as u64;
+    bonding_curve.virtual_token_reserves = reserves.virtual_token_reserves as u64;
+    bonding_curve.virtual_sol_reserves = reserves.virtual_sol_reserves as u64;
+
+    let price_after = bonding_curve.spot_price().unwrap_or_default();
+    bonding_curve
+        .slot_trade
+        .record(&sandwich_protection, clock.slot, price_before, price_after)?;
+
+    bonding_curve.update_time = curr_time as u64;
+
+    log_trade(
+        trade,
+        buy_result.sol_amount,
+        sent_token_amount,
+        net_token_amount,
+        true,
+        price_move_bps(price_before, price_after),
+        curr_time,
+        hash,
+    );
+
+    let bonding_curve = &mut trade.bonding_curve;
+    if bonding_curve.reached_graduation() {
+        // whatever the buyer didn't take stays behind for the pool
+        let pool_token_amount = trade.bonding_curve_token_account.amount;
+        let pool_sol_amount = bonding_curve.real_sol_reserves;
+        bonding_curve.complete_curve(pool_sol_amount, pool_token_amount)?;
+
+        let complete_event = CompleteEvent {
+            user: trade.trader.to_string(),
+            mint: trade.mint.key().to_string(),
+            bonding_curve: bonding_curve.key().to_string(),
+            timestamp: curr_time,
+            virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+            virtual_token_reserves: bonding_curve.virtual_token_reserves,
+            token_total_supply: bonding_curve.token_total_supply,
+            pool_sol_amount: bonding_curve.pool_sol_amount,
+            pool_token_amount: bonding_curve.pool_token_amount,
+            quote_mint: event_quote_mint(bonding_curve),
+        };
+        let serialized = serde_json::to_string(&complete_event).unwrap();
+        msg!("completelog:{}", serialized);
+    }
+
+    msg!("bonding_curve: {:?}", curve);
+
+    Ok(())
+}
+
+/// Moves a priced sell: `gross_token_amount` from the trader, signed by
+/// `token_authority`, then SOL less the fees to `sol_destination`, which is
+/// the trader's quote token account on quote asset curves. `sell_result` is
+/// priced on what the curve receives after any transfer fee.
+#[allow(clippy::too_many_arguments)]
+pub fn settle_sell<'info>(
+    trade: &mut CurveTrade<'_, 'info>,
+    token_authority: AccountInfo<'info>,
+    token_authority_seeds: &[&[&[u8]]],
+    sol_destination: AccountInfo<'info>,
+    curve: &dyn CurveModel,
+    sell_result: &SellResult,
+    gross_token_amount: u64,
+    fees: &TradeFees,
+    hash: &str,
+) -> Result<()> {
+    let sell_amount_minus_fee = sell_result.sol_amount - fees.total_fee;
+
+    let slot = Clock::get()?.slot;
+    let user_curve_stats = &mut trade.user_curve_stats;
+    user_curve_stats.user = trade.trader;
+    user_curve_stats.bonding_curve = trade.bonding_curve.key();
+
+    let sandwich_protection = trade.bonding_curve.sandwich_protection;
+    user_curve_stats.record_trade(&sandwich_protection, slot, false)?;
+    let price_before = trade.bonding_curve.spot_price().unwrap_or_default();
+
+    //transfer SPL
+    let curve_balance_before = trade.bonding_curve_token_account.amount;
+
+    let cpi_accounts = TransferChecked {
+        from: trade.trader_token_account.to_account_info(),
+        mint: trade.mint.to_account_info(),
+        to: trade.bonding_curve_token_account.to_account_info(),
+        authority: token_authority,
+    };
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            trade.token_program.clone(),
+            cpi_accounts,
+            token_authority_seeds,
+        ),
+        gross_token_amount,
+        trade.mint.decimals,
+    )?;
+
+    trade.bonding_curve_token_account.reload()?;
+    trade.trader_token_account.reload()?;
+    let net_token_amount = trade.bonding_curve_token_account.amount - curve_balance_before;
+    require!(
+        net_token_amount == sell_result.token_amount,
+        CurveLaunchpadError::TransferFeeMismatch,
+    );
+
+    let sniper_fee_destination = trade.bonding_curve.launch_protection.sniper_fee.destination;
+    match &mut trade.quote {
+        TradeQuote::Sol(sol) => {
+            let burned_fee = sol.credit_fees(fees, sniper_fee_destination);
+            let kept_fee = fees.total_fee - burned_fee;
+
+            //transfer SOL back to user
+            let from_account = trade.bonding_curve.to_account_info();
+            let fee_account = sol.fee_account.to_account_info();
+
+            **from_account.try_borrow_mut_lamports()? -= sell_result.sol_amount;
+            **sol_destination.try_borrow_mut_lamports()? += sell_amount_minus_fee;
+            **fee_account.try_borrow_mut_lamports()? += kept_fee;
+            if burned_fee > 0 {
+                **sol.incinerator()?.try_borrow_mut_lamports()? += burned_fee;
+            }
+
+            sol.keep_fee(kept_fee)?;
+        }
+        TradeQuote::Token(quote) => {
+            let burned_fee = quote.credit_fees(fees, sniper_fee_destination)?;
+
+            //transfer the quote asset back to the user, the fee vault and the burn
+            let mint_key = trade.mint.key();
+            let signer: [&[&[u8]]; 1] = [&[
+                BondingCurve::SEED_PREFIX,
+                mint_key.as_ref(),
+                &[trade.bonding_curve_bump],
+            ]];
+            let bonding_curve_info = trade.bonding_curve.to_account_info();
+
+            for (to, amount) in [
+                (&sol_destination, sell_amount_minus_fee),
+                (&quote.fee_quote_vault, fees.total_fee - burned_fee),
+            ] {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        quote.quote_token_program.clone(),
+                        Transfer {
+                            from: quote.bonding_curve_quote_vault.clone(),
+                            to: to.clone(),
+                            authority: bonding_curve_info.clone(),
+                        },
+                        &signer,
+                    ),
+                    amount,
+                )?;
+            }
+
+            if burned_fee > 0 {
+                token::burn(
+                    CpiContext::new_with_signer(
+                        quote.quote_token_program.clone(),
+                        Burn {
+                            mint: quote.quote_mint.clone(),
+                            from: quote.bonding_curve_quote_vault.clone(),
+                            authority: bonding_curve_info,
+                        },
+                        &signer,
+                    ),
+                    burned_fee,
+                )?;
+            }
+        }
+    }
+
+    let bonding_curve = &mut trade.bonding_curve;
+    let reserves = curve.reserves();
+    bonding_curve.real_token_reserves = bonding_curve
+        .real_token_reserves
+        .checked_add(net_token_amount)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+    bonding_curve.real_sol_reserves = reserves.real_sol_reserves as u64;
+    bonding_curve.virtual_token_reserves = reserves.virtual_token_reserves as u64;
+    bonding_curve.virtual_sol_reserves = reserves.virtual_sol_reserves as u64;
+
+    let price_after = bonding_curve.spot_price().unwrap_or_default();
+    bonding_curve
+        .slot_trade
+        .record(&sandwich_protection, slot, price_before, price_after)?;
+
+    let curr_time = Clock::get()?.unix_timestamp;
+    log_trade(
+        trade,
+        sell_result.sol_amount,
+        gross_token_amount,
+        net_token_amount,
+        false,
+        price_move_bps(price_before, price_after),
+        curr_time,
+        hash,
+    );
+
+    Ok(())
+}
+
+/// Quote mint events report, wrapped SOL for SOL curves.
+fn event_quote_mint(bonding_curve: &BondingCurve) -> String {
+    if bonding_curve.is_native_quote() {
+        WSOL_MINT_ADDRESS.to_string()
+    } else {
+        bonding_curve.quote_mint.to_string()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn log_trade(
+    trade: &CurveTrade,
+    sol_amount: u64,
+    token_amount: u64,
+    net_token_amount: u64,
+    is_buy: bool,
+    price_impact_bps: u64,
+    timestamp: i64,
+    hash: &str,
+) {
+    let bonding_curve = &trade.bonding_curve;
+
+    let trade_event = TradeEvent {
+        mint: trade.mint.key().to_string(),
+        sol_amount,
+        token_amount,
+        net_token_amount,
+        is_buy,
+        user: trade.trader.to_string(),
+        timestamp,
+        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+        virtual_token_reserves: bonding_curve.virtual_token_reserves,
+        real_sol_reserves: bonding_curve.real_sol_reserves,
+        real_token_reserves: bonding_curve.real_token_reserves,
+        spot_price: bonding_curve.spot_price().unwrap_or_default(),
+        market_cap: bonding_curve.market_cap().unwrap_or_default(),
+        graduation_progress_bps: bonding_curve.graduation_progress_bps(),
+        sol_to_graduation: bonding_curve.sol_to_graduation().unwrap_or_default(),
+        fee_basis_points: bonding_curve.fees.total() + bonding_curve.sniper_fee_basis_points(timestamp as u64),
+        price_impact_bps,
+        quote_mint: event_quote_mint(bonding_curve),
+        hash: hash.to_string(),
+    };
+
+    let serialized = serde_json::to_string(&trade_event).unwrap();
+
+    msg!("tradelog:{}", serialized);
+}
@@ -1,4 +1,15 @@
 
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+};
+
+use crate::{
+    state::{FeeBasisPoints, SniperFeeDestination, UserInviteStats},
+    CurveLaunchpadError,
+};
+
 pub fn calculate_fee(
     amount: u64,
     fee_basis_points: u64,
@@ -6,6 +17,89 @@ pub fn calculate_fee(
     amount * fee_basis_points / 10000
 }
 
+/// Fee charged on a curve trade. `total_fee` is what the trader pays, the
+/// parts are what gets credited to the protocol, creator and inviter, plus
+/// the sniper fee that goes wherever the curve's launch protection says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeFees {
+    pub total_fee: u64,
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub invite_fee: u64,
+    pub sniper_fee: u64,
+}
+
+pub fn calculate_trade_fees(
+    amount: u64,
+    fees: &FeeBasisPoints,
+    sniper_fee_basis_points: u64,
+) -> TradeFees {
+    TradeFees {
+        total_fee: calculate_fee(amount, fees.total() + sniper_fee_basis_points),
+        protocol_fee: calculate_fee(amount, fees.protocol),
+        creator_fee: calculate_fee(amount, fees.creator),
+        invite_fee: calculate_fee(amount, fees.invite),
+        sniper_fee: calculate_fee(amount, sniper_fee_basis_points),
+    }
+}
+
+/// Credits the sniper fee of a trade to its destination, returns the part
+/// to burn instead of keeping in the fee account.
+pub fn credit_sniper_fee(
+    sniper_fee: u64,
+    destination: SniperFeeDestination,
+    fee_recipient_invite_account: &mut UserInviteStats,
+    creator_invite_account: &mut UserInviteStats,
+) -> u64 {
+    match destination {
+        SniperFeeDestination::Protocol => {
+            fee_recipient_invite_account.profit_claimable += sniper_fee;
+            0
+        }
+        SniperFeeDestination::Creator => {
+            creator_invite_account.profit_claimable += sniper_fee;
+            0
+        }
+        SniperFeeDestination::Burn => sniper_fee,
+    }
+}
+
+/// Token-2022 transfer fee withheld from a transfer of `amount`. Classic SPL
+/// mints and Token-2022 mints without the extension charge nothing.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    epoch_transfer_fee(&data, Clock::get()?.epoch, amount, false)
+}
+
+/// Gross amount to transfer so that `net_amount` arrives after the fee.
+pub fn gross_transfer_amount(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(net_amount);
+    }
+    let data = mint.try_borrow_data()?;
+    let fee = epoch_transfer_fee(&data, Clock::get()?.epoch, net_amount, true)?;
+    net_amount
+        .checked_add(fee)
+        .ok_or(CurveLaunchpadError::TransferFeeMismatch.into())
+}
+
+/// Fee on a pre-fee `amount`, or on a post-fee one when `inverse` is set.
+fn epoch_transfer_fee(mint_data: &[u8], epoch: u64, amount: u64, inverse: bool) -> Result<u64> {
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_data)?;
+    let Ok(config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(0);
+    };
+    let fee = if inverse {
+        config.calculate_inverse_epoch_fee(epoch, amount)
+    } else {
+        config.calculate_epoch_fee(epoch, amount)
+    };
+    fee.ok_or(CurveLaunchpadError::TransferFeeMismatch.into())
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -19,4 +113,52 @@ mod tests {
         assert_eq!(calculate_fee(1000, 50), 5); //0.5% fee
         assert_eq!(calculate_fee(100, 0), 0); //0% fee
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_calculate_trade_fees() {
+        let fees = FeeBasisPoints { protocol: 50, creator: 35, invite: 15 };
+
+        let trade_fees = calculate_trade_fees(1_000_000, &fees, 0);
+        assert_eq!(trade_fees.total_fee, 10_000);
+        assert_eq!(trade_fees.sniper_fee, 0);
+
+        let trade_fees = calculate_trade_fees(1_000_000, &fees, 2000);
+        assert_eq!(trade_fees.total_fee, 210_000);
+        assert_eq!(trade_fees.protocol_fee, 5_000);
+        assert_eq!(trade_fees.sniper_fee, 200_000);
+    }
+
+    fn mint_data(transfer_fee_basis_points: Option<u16>) -> Vec<u8> {
+        use spl_token_2022::extension::{ExtensionType, StateWithExtensionsMut};
+
+        let extensions = match transfer_fee_basis_points {
+            Some(_) => vec![ExtensionType::TransferFeeConfig],
+            None => vec![],
+        };
+        let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions).unwrap();
+        let mut data = vec![0; len];
+        let mut mint = StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data).unwrap();
+        if let Some(basis_points) = transfer_fee_basis_points {
+            let config = mint.init_extension::<TransferFeeConfig>(true).unwrap();
+            config.newer_transfer_fee.transfer_fee_basis_points = basis_points.into();
+            config.newer_transfer_fee.maximum_fee = u64::MAX.into();
+        }
+        mint.base.decimals = 6;
+        mint.base.is_initialized = true;
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+        data
+    }
+
+    #[test]
+    fn test_epoch_transfer_fee() {
+        let data = mint_data(Some(100)); //1% fee
+        assert_eq!(epoch_transfer_fee(&data, 0, 10_000, false).unwrap(), 100);
+        //9_900 arrives after the fee on 10_000
+        assert_eq!(epoch_transfer_fee(&data, 0, 9_900, true).unwrap(), 100);
+
+        let data = mint_data(None);
+        assert_eq!(epoch_transfer_fee(&data, 0, 10_000, false).unwrap(), 0);
+        assert_eq!(epoch_transfer_fee(&data, 0, 10_000, true).unwrap(), 0);
+    }
+}
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    transfer_fee,
+    state::{BondingCurve, Global, VestingEscrow},
+    ClaimVestedEvent, CurveLaunchpadError,
+};
+
+#[derive(Accounts)]
+pub struct InitVestingEscrow<'info> {
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        has_one = creator @ CurveLaunchpadError::InvalidAuthority,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + VestingEscrow::INIT_SPACE,
+        seeds = [VestingEscrow::SEED_PREFIX, bonding_curve.key().as_ref()],
+        bump,
+    )]
+    vesting_escrow: Box<Account<'info, VestingEscrow>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = vesting_escrow,
+        token::token_program = token_program,
+        seeds=[VestingEscrow::SEED_PREFIX, mint.key().as_ref(), vesting_escrow.key().as_ref()],
+        bump
+    )]
+    vesting_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    rent: Sysvar<'info, Rent>,
+}
+
+/// Locks the dev buy of a curve behind a vesting schedule. It goes between
+/// `init_create_account` and `create`, which then pays the dev-buy tokens
+/// into the escrow and starts the schedule at the curve's create time. Only
+/// the creator recorded by `init_create_account` can set it up.
+pub fn init_vesting_escrow(ctx: Context<InitVestingEscrow>, cliff_seconds: u64, duration_seconds: u64) -> Result<()> {
+    //the curve must not be created yet
+    require!(ctx.accounts.bonding_curve.create_time == 0, CurveLaunchpadError::InvalidCurveStatus);
+    VestingEscrow::validate_terms(cliff_seconds, duration_seconds)?;
+
+    let vesting_escrow = &mut ctx.accounts.vesting_escrow;
+    vesting_escrow.bonding_curve = ctx.accounts.bonding_curve.key();
+    vesting_escrow.mint = ctx.accounts.mint.key();
+    vesting_escrow.beneficiary = ctx.accounts.creator.key();
+    vesting_escrow.token_account = ctx.accounts.vesting_token_account.key();
+    vesting_escrow.total_amount = 0;
+    vesting_escrow.claimed_amount = 0;
+    vesting_escrow.start_time = 0;
+    vesting_escrow.cliff_seconds = cliff_seconds;
+    vesting_escrow.duration_seconds = duration_seconds;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    beneficiary: Signer<'info>,
+
+    #[account(
+        address = vesting_escrow.mint,
+        mint::token_program = token_program,
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = beneficiary,
+        seeds = [VestingEscrow::SEED_PREFIX, vesting_escrow.bonding_curve.as_ref()],
+        bump,
+    )]
+    vesting_escrow: Box<Account<'info, VestingEscrow>>,
+
+    #[account(
+        mut,
+        address = vesting_escrow.token_account,
+    )]
+    vesting_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    beneficiary_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let claim_amount = ctx.accounts.vesting_escrow.claimable_amount(timestamp.max(0) as u64);
+    require!(claim_amount > 0, CurveLaunchpadError::NothingVested);
+
+    let signer: [&[&[u8]]; 1] = [&[
+        VestingEscrow::SEED_PREFIX,
+        ctx.accounts.vesting_escrow.bonding_curve.as_ref(),
+        &[ctx.bumps.vesting_escrow],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vesting_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                authority: ctx.accounts.vesting_escrow.to_account_info(),
+            },
+            &signer,
+        ),
+        claim_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+    let net_amount = claim_amount - transfer_fee(&ctx.accounts.mint.to_account_info(), claim_amount)?;
+
+    let vesting_escrow = &mut ctx.accounts.vesting_escrow;
+    vesting_escrow.claimed_amount += claim_amount;
+
+    let claim_event = ClaimVestedEvent {
+        mint: vesting_escrow.mint.to_string(),
+        beneficiary: vesting_escrow.beneficiary.to_string(),
+        amount: claim_amount,
+        net_amount,
+        claimed_amount: vesting_escrow.claimed_amount,
+        total_amount: vesting_escrow.total_amount,
+        cliff_seconds: vesting_escrow.cliff_seconds,
+        duration_seconds: vesting_escrow.duration_seconds,
+        timestamp,
+    };
+
+    let serialized = serde_json::to_string(&claim_event).unwrap();
+
+    msg!("claimVested:{}", serialized);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimProtocolAllocation<'info> {
+    #[account(
+        mut,
+        address = global.protocol_token_alloc_recipient @ CurveLaunchpadError::InvalidAuthority,
+    )]
+    recipient: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        address = protocol_allocation.mint,
+        mint::token_program = token_program,
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [VestingEscrow::PROTOCOL_SEED_PREFIX, protocol_allocation.bonding_curve.as_ref()],
+        bump,
+    )]
+    protocol_allocation: Box<Account<'info, VestingEscrow>>,
+
+    #[account(
+        mut,
+        address = protocol_allocation.token_account,
+    )]
+    protocol_allocation_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Pays the vested part of a curve's protocol token allocation to the
+/// current `protocol_token_alloc_recipient`, so rotating the recipient also
+/// moves the allocations not claimed yet.
+pub fn claim_protocol_allocation(ctx: Context<ClaimProtocolAllocation>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let claim_amount = ctx.accounts.protocol_allocation.claimable_amount(timestamp.max(0) as u64);
+    require!(claim_amount > 0, CurveLaunchpadError::NothingVested);
+
+    let signer: [&[&[u8]]; 1] = [&[
+        VestingEscrow::PROTOCOL_SEED_PREFIX,
+        ctx.accounts.protocol_allocation.bonding_curve.as_ref(),
+        &[ctx.bumps.protocol_allocation],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.protocol_allocation_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.protocol_allocation.to_account_info(),
+            },
+            &signer,
+        ),
+        claim_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+    let net_amount = claim_amount - transfer_fee(&ctx.accounts.mint.to_account_info(), claim_amount)?;
+
+    let protocol_allocation = &mut ctx.accounts.protocol_allocation;
+    protocol_allocation.claimed_amount += claim_amount;
+
+    let claim_event = ClaimVestedEvent {
+        mint: protocol_allocation.mint.to_string(),
+        beneficiary: ctx.accounts.recipient.key().to_string(),
+        amount: claim_amount,
+        net_amount,
+        claimed_amount: protocol_allocation.claimed_amount,
+        total_amount: protocol_allocation.total_amount,
+        cliff_seconds: protocol_allocation.cliff_seconds,
+        duration_seconds: protocol_allocation.duration_seconds,
+        timestamp,
+    };
+
+    let serialized = serde_json::to_string(&claim_event).unwrap();
+
+    msg!("claimProtocolAllocation:{}", serialized);
+
+    Ok(())
+}
@@ -0,0 +1,335 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, SyncNative, Token, TokenAccount, Transfer},
+    token_interface::{self, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    state::{BondingCurve, CurveStatus, FeeAccount, Global, UserInviteStats},
+    ClaimInviteProfitEvent, CurveLaunchpadError, MigratedEvent, WithdrawEvent, WSOL_MINT_ADDRESS,
+};
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    withdraw_authority: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+        has_one = withdraw_authority @ CurveLaunchpadError::InvalidWithdrawAuthority,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        address = bonding_curve.mint.key(),
+        mint::token_program = token_program,
+    )]
+    mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds=[BondingCurve::SEED_PREFIX, mint.key().as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = withdraw_authority,
+        associated_token::mint = mint,
+        associated_token::authority = withdraw_authority,
+        associated_token::token_program = token_program,
+    )]
+    withdraw_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Wrapped SOL account of the withdraw authority, or its account of the
+    /// quote mint for a quote asset curve.
+    #[account(
+        mut,
+        token::authority = withdraw_authority,
+    )]
+    withdraw_quote_account: Box<Account<'info, TokenAccount>>,
+
+    /// Only for a quote asset curve, where it holds the curve's quote reserves.
+    #[account(
+        mut,
+        token::mint = bonding_curve.quote_mint,
+        token::authority = bonding_curve,
+        seeds=[BondingCurve::SEED_PREFIX, bonding_curve.quote_mint.as_ref(), bonding_curve.key().as_ref()],
+        bump
+    )]
+    bonding_curve_quote_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    /// Program of the wrapped SOL or quote accounts.
+    quote_token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Starts migrating a completed curve by handing the liquidity it snapshotted
+/// to the withdraw authority, to seed an external pool with instead of
+/// `create_pool`. The curve stays `Migrating` until the authority calls
+/// `finish_migration` with the pool it seeded.
+/// The SOL lands in the authority's wrapped SOL account and still needs
+/// `wsol_sync_native` before it can be spent. A quote asset curve pays its
+/// quote reserves into the authority's account of the quote mint instead,
+/// this is the only way those curves migrate. A Token-2022 transfer fee is
+/// withheld from the tokens on the way, the event reports what arrived.
+pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+    require!(
+        !matches!(ctx.accounts.bonding_curve.status, CurveStatus::Migrating | CurveStatus::Migrated),
+        CurveLaunchpadError::AlreadyWithdraw,
+    );
+
+    require!(
+        ctx.accounts.bonding_curve.status == CurveStatus::Completed,
+        CurveLaunchpadError::BondingCurveNotComplete,
+    );
+
+    if ctx.accounts.bonding_curve.is_native_quote() {
+        require_keys_eq!(
+            ctx.accounts.withdraw_quote_account.mint,
+            Pubkey::from_str(WSOL_MINT_ADDRESS).unwrap(),
+            CurveLaunchpadError::WrongWSOLMint
+        );
+    } else {
+        require_keys_eq!(
+            ctx.accounts.withdraw_quote_account.mint,
+            ctx.accounts.bonding_curve.quote_mint,
+            CurveLaunchpadError::QuoteMintMismatch
+        );
+        require!(
+            ctx.accounts.bonding_curve_quote_vault.is_some(),
+            CurveLaunchpadError::QuoteMintMismatch
+        );
+    }
+
+    ctx.accounts.bonding_curve.start_migration()?;
+
+    let sol_amount = ctx.accounts.bonding_curve.pool_sol_amount;
+    let token_amount = ctx.accounts.bonding_curve.pool_token_amount;
+
+    //transfer SPL
+    let withdraw_balance_before = ctx.accounts.withdraw_token_account.amount;
+    let cpi_accounts = TransferChecked {
+        from: ctx
+            .accounts
+            .bonding_curve_token_account
+            .to_account_info()
+            .clone(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.withdraw_token_account.to_account_info().clone(),
+        authority: ctx.accounts.bonding_curve.to_account_info().clone(),
+    };
+
+    let signer: [&[&[u8]]; 1] = [&[
+        BondingCurve::SEED_PREFIX,
+        ctx.accounts.mint.to_account_info().key.as_ref(),
+        &[ctx.bumps.bonding_curve],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &signer,
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    //a transfer fee is withheld from what the authority receives
+    ctx.accounts.withdraw_token_account.reload()?;
+    let net_token_amount = ctx.accounts.withdraw_token_account.amount - withdraw_balance_before;
+
+    //transfer SOL, or the quote asset
+    match &ctx.accounts.bonding_curve_quote_vault {
+        Some(bonding_curve_quote_vault) if !ctx.accounts.bonding_curve.is_native_quote() => {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.quote_token_program.to_account_info(),
+                    Transfer {
+                        from: bonding_curve_quote_vault.to_account_info(),
+                        to: ctx.accounts.withdraw_quote_account.to_account_info(),
+                        authority: ctx.accounts.bonding_curve.to_account_info(),
+                    },
+                    &signer,
+                ),
+                sol_amount,
+            )?;
+        }
+        _ => {
+            **ctx.accounts.bonding_curve.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+            **ctx.accounts.withdraw_quote_account.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+        }
+    }
+
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    //what the external pool can be seeded with
+    bonding_curve.pool_token_amount = net_token_amount;
+    bonding_curve.real_sol_reserves = bonding_curve
+        .real_sol_reserves
+        .checked_sub(sol_amount)
+        .ok_or(CurveLaunchpadError::InsufficientSOL)?;
+
+    let withdraw_event = WithdrawEvent {
+        mint: ctx.accounts.mint.to_account_info().key().to_string(),
+        withdraw_authority: ctx.accounts.withdraw_authority.key().to_string(),
+        sol_amount,
+        token_amount,
+        net_token_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    };
+
+    let serialized = serde_json::to_string(&withdraw_event).unwrap();
+
+    msg!("withdrawlog:{}", serialized);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinishMigration<'info> {
+    withdraw_authority: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+        has_one = withdraw_authority @ CurveLaunchpadError::InvalidWithdrawAuthority,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, bonding_curve.mint.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    /// CHECK: the external pool, owned by its DEX program
+    #[account(
+        constraint = !pool.data_is_empty() && *pool.owner != System::id() && *pool.owner != crate::ID @ CurveLaunchpadError::InvalidMigrationPool,
+    )]
+    pool: UncheckedAccount<'info>,
+}
+
+/// Marks a curve withdrawn for an external pool as migrated. This is the
+/// withdraw authority's attestation that it seeded `pool` with the liquidity
+/// `withdraw` handed out: an external DEX's vaults can't be tied to its pool
+/// here, so only `pool` being an existing program account is checked, and
+/// indexers should weigh `migratedlog` as coming from the authority.
+pub fn finish_migration(ctx: Context<FinishMigration>) -> Result<()> {
+    let pool = ctx.accounts.pool.key();
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.finish_migration()?;
+
+    let migrated_event = MigratedEvent {
+        mint: bonding_curve.mint.to_string(),
+        pool: pool.to_string(),
+        timestamp: Clock::get()?.unix_timestamp,
+    };
+
+    let serialized = serde_json::to_string(&migrated_event).unwrap();
+
+    msg!("migratedlog:{}", serialized);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WsolSyncNative<'info> {
+    #[account(mut)]
+    wsol_account: Box<Account<'info, TokenAccount>>,
+
+    token_program: Program<'info, Token>,
+}
+
+pub fn wsol_sync_native(ctx: Context<WsolSyncNative>) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.wsol_account.mint,
+        Pubkey::from_str(WSOL_MINT_ADDRESS).unwrap(),
+        CurveLaunchpadError::WrongWSOLMint
+    );
+
+    token::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.wsol_account.to_account_info(),
+        },
+    ))
+}
+
+#[derive(Accounts)]
+pub struct ProtocolFeeCollect<'info> {
+    withdraw_authority: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+        has_one = withdraw_authority @ CurveLaunchpadError::InvalidWithdrawAuthority,
+        has_one = fee_recipient @ CurveLaunchpadError::InvalidFeeRecipient,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    /// CHECK: checked against global.fee_recipient
+    #[account(mut)]
+    fee_recipient: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds=[UserInviteStats::SEED_PREFIX,fee_recipient.key().as_ref()],
+        bump
+    )]
+    fee_recipient_invite_account: Box<Account<'info, UserInviteStats>>,
+
+    #[account(
+        mut,
+        seeds=[FeeAccount::SEED_PREFIX],
+        bump
+    )]
+    fee_account : Box<Account<'info,FeeAccount>>,
+}
+
+/// Pays the protocol fees claimable by the fee recipient out to it. Signed by
+/// the withdraw authority, so the fees also reach a fee recipient that can't
+/// sign `claim_invite_profit` itself.
+pub fn protocol_fee_collect(ctx: Context<ProtocolFeeCollect>) -> Result<()> {
+    let claim_amount = ctx.accounts.fee_recipient_invite_account.profit_claimable;
+    require!(claim_amount > 0,CurveLaunchpadError::NotClaimableFee);
+    ctx.accounts.fee_recipient_invite_account.profit_claimable = 0;
+    ctx.accounts.fee_recipient_invite_account.profit_claim_accumulated += claim_amount;
+
+    **ctx.accounts.fee_account.to_account_info().try_borrow_mut_lamports()? -= claim_amount;
+    **ctx.accounts.fee_recipient.try_borrow_mut_lamports()? += claim_amount;
+
+    ctx.accounts.fee_account.sent += claim_amount;
+    let is_ok = ctx.accounts.fee_account.check(ctx.accounts.fee_account.get_lamports());
+    require!(is_ok,CurveLaunchpadError::FeeAccountStatusAbnormal);
+
+    let claim_event = ClaimInviteProfitEvent{
+        user: ctx.accounts.fee_recipient.key().to_string(),
+        amount: claim_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    };
+
+    let serialized = serde_json::to_string(&claim_event).unwrap();
+
+    msg!("protocolFeeCollect:{}", serialized);
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
 use instructions::*;
+use state::{
+    AllowlistParams, AllowlistProof, CurveKind, FeeBasisPoints, LaunchProtection, MintSuffixMode,
+    SandwichProtection, TriggerDirection,
+};
 
 pub mod instructions;
 pub mod state;
@@ -8,6 +12,7 @@ pub mod amm;
 
 declare_id!("8VmiQfMyGSeksAkHLuXYhpXccsqhkPavH26g1BTFjpmg");
 
+#[allow(clippy::too_many_arguments)]
 #[program]
 pub mod curve_launchpad {
 
@@ -17,6 +22,10 @@ pub mod curve_launchpad {
         initialize::initialize(ctx)
     }
 
+    pub fn migrate_global(ctx: Context<MigrateGlobal>) -> Result<()> {
+        migrate_global::migrate_global(ctx)
+    }
+
     pub fn init_invite_account(ctx:Context<InitInviteAccount>,parent: Pubkey) ->Result<()>{
         user_invite::init_invite(ctx, parent)
     }
@@ -36,18 +45,166 @@ pub mod curve_launchpad {
         description: String,
         website: String,
         telegram: String,
-        twitter: String,) -> Result<()> {
-        create::create(ctx, name, symbol, uri,description,website,telegram,twitter)
+        twitter: String,
+        curve_kind: CurveKind,
+        curve_params: [u64; 4],
+        template_id: Option<u32>,
+        dev_buy_sol: Option<u64>,
+        max_sol_cost: u64,
+        allowlist: Option<AllowlistParams>,
+        allowlist_proof: Option<AllowlistProof>,) -> Result<()> {
+        create::create(ctx, name, symbol, uri,description,website,telegram,twitter,curve_kind,curve_params,template_id,dev_buy_sol,max_sol_cost,allowlist,allowlist_proof)
+    }
+
+    pub fn init_vesting_escrow(ctx: Context<InitVestingEscrow>, cliff_seconds: u64, duration_seconds: u64) -> Result<()> {
+        vesting::init_vesting_escrow(ctx, cliff_seconds, duration_seconds)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        vesting::claim_vested(ctx)
+    }
+
+    pub fn claim_protocol_allocation(ctx: Context<ClaimProtocolAllocation>) -> Result<()> {
+        vesting::claim_protocol_allocation(ctx)
+    }
+
+    pub fn init_create_account_token_2022(ctx: Context<InitCreateAccountToken2022>, seed: u64, transfer_fee: Option<TransferFeeParams>) -> Result<()> {
+        create_token_2022::init_create_account_token_2022(ctx, seed, transfer_fee)
+    }
+
+    pub fn create_token_2022(ctx: Context<CreateToken2022>,
+        name: String,
+        symbol: String,
+        uri: String,
+        description: String,
+        website: String,
+        telegram: String,
+        twitter: String,
+        curve_kind: CurveKind,
+        curve_params: [u64; 4],
+        template_id: Option<u32>,
+        dev_buy_sol: Option<u64>,
+        max_sol_cost: u64,
+        allowlist: Option<AllowlistParams>,
+        allowlist_proof: Option<AllowlistProof>,) -> Result<()> {
+        create_token_2022::create_token_2022(ctx, name, symbol, uri, description, website, telegram, twitter, curve_kind, curve_params, template_id, dev_buy_sol, max_sol_cost, allowlist, allowlist_proof)
+    }
+
+    pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64 , hash : String, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+        buy::buy(ctx, token_amount, max_sol_cost,&hash, allowlist_proof)
     }
 
-    pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64 , hash : String) -> Result<()> {
-        buy::buy(ctx, token_amount, max_sol_cost,&hash)
+    pub fn buy_exact_sol_in(ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64 , hash : String, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+        buy::buy_exact_sol_in(ctx, sol_amount, min_tokens_out,&hash, allowlist_proof)
     }
 
     pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64 , hash : String) -> Result<()> {
         sell::sell(ctx, token_amount, min_sol_output,&hash)
     }
 
+    pub fn sell_exact_sol_out(ctx: Context<Sell>, sol_amount: u64, max_tokens_in: u64 , hash : String) -> Result<()> {
+        sell::sell_exact_sol_out(ctx, sol_amount, max_tokens_in,&hash)
+    }
+
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        id: u64,
+        side: TradeSide,
+        amount: u64,
+        limit_price: u128,
+        expiry: u64,
+        keeper_fee: u64,
+    ) -> Result<()> {
+        order::place_order(ctx, id, side, amount, limit_price, expiry, keeper_fee)
+    }
+
+    pub fn execute_order(ctx: Context<ExecuteOrder>, hash : String, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+        order::execute_order(ctx, &hash, allowlist_proof)
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        order::cancel_order(ctx)
+    }
+
+    pub fn open_dca_vault(
+        ctx: Context<OpenDcaVault>,
+        id: u64,
+        deposit: u64,
+        interval_seconds: u64,
+        amount_per_interval: u64,
+        max_price: u128,
+    ) -> Result<()> {
+        dca::open_dca_vault(ctx, id, deposit, interval_seconds, amount_per_interval, max_price)
+    }
+
+    pub fn execute_dca(ctx: Context<ExecuteDca>, hash : String, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+        dca::execute_dca(ctx, &hash, allowlist_proof)
+    }
+
+    pub fn withdraw_dca_vault(ctx: Context<WithdrawDcaVault>) -> Result<()> {
+        dca::withdraw_dca_vault(ctx)
+    }
+
+    pub fn place_sell_trigger(
+        ctx: Context<PlaceSellTrigger>,
+        id: u64,
+        direction: TriggerDirection,
+        token_amount: u64,
+        trigger_price: u128,
+        max_slippage_bps: u64,
+        keeper_fee: u64,
+    ) -> Result<()> {
+        sell_trigger::place_sell_trigger(ctx, id, direction, token_amount, trigger_price, max_slippage_bps, keeper_fee)
+    }
+
+    pub fn execute_sell_trigger(ctx: Context<ExecuteSellTrigger>, hash : String) -> Result<()> {
+        sell_trigger::execute_sell_trigger(ctx, &hash)
+    }
+
+    pub fn cancel_sell_trigger(ctx: Context<CancelSellTrigger>) -> Result<()> {
+        sell_trigger::cancel_sell_trigger(ctx)
+    }
+
+    pub fn quote(ctx: Context<Quote>, side: TradeSide, token_amount: u64) -> Result<QuoteResult> {
+        quote::quote(ctx, side, token_amount)
+    }
+
+    pub fn create_pool(ctx: Context<CreatePool>) -> Result<()> {
+        create_pool::create_pool(ctx)
+    }
+
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64, is_buy: bool, hash : String) -> Result<()> {
+        swap::swap(ctx, amount_in, min_amount_out, is_buy, &hash)
+    }
+
+    pub fn add_liquidity(ctx: Context<Liquidity>, lp_amount: u64, max_sol_amount: u64, max_token_amount: u64) -> Result<()> {
+        liquidity::add_liquidity(ctx, lp_amount, max_sol_amount, max_token_amount)
+    }
+
+    pub fn remove_liquidity(ctx: Context<Liquidity>, lp_amount: u64, min_sol_amount: u64, min_token_amount: u64) -> Result<()> {
+        liquidity::remove_liquidity(ctx, lp_amount, min_sol_amount, min_token_amount)
+    }
+
+    pub fn set_quote_asset(ctx: Context<SetQuoteAsset>, enabled: bool, initial_virtual_reserves: u64) -> Result<()> {
+        quote_asset::set_quote_asset(ctx, enabled, initial_virtual_reserves)
+    }
+
+    pub fn init_quote_vault(ctx: Context<InitQuoteVault>) -> Result<()> {
+        quote_asset::init_quote_vault(ctx)
+    }
+
+    pub fn buy_with_quote_asset(ctx: Context<QuoteAssetTrade>, token_amount: u64, max_quote_cost: u64, hash : String, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+        quote_asset_trade::buy_with_quote_asset(ctx, token_amount, max_quote_cost, &hash, allowlist_proof)
+    }
+
+    pub fn sell_with_quote_asset(ctx: Context<QuoteAssetTrade>, token_amount: u64, min_quote_output: u64, hash : String) -> Result<()> {
+        quote_asset_trade::sell_with_quote_asset(ctx, token_amount, min_quote_output, &hash)
+    }
+
+    pub fn claim_quote_invite_profit(ctx: Context<QuoteInviteClaimAccount>) -> Result<()> {
+        quote_asset::claim_quote_invite_profit(ctx)
+    }
+
     pub fn protocol_fee_collect(ctx: Context<ProtocolFeeCollect>) -> Result<()>{
         withdraw::protocol_fee_collect(ctx)
     }
@@ -60,6 +217,10 @@ pub mod curve_launchpad {
         withdraw::withdraw(ctx)
     }
 
+    pub fn finish_migration(ctx: Context<FinishMigration>) -> Result<()> {
+        withdraw::finish_migration(ctx)
+    }
+
     pub fn set_amm_params(
         ctx: Context<SetParams>,
         initial_virtual_token_reserves: u64,
@@ -106,5 +267,56 @@ pub mod curve_launchpad {
         )
     }
 
+    pub fn set_protocol_token_alloc(
+        ctx: Context<SetParams>,
+        protocol_token_alloc_points: u64,
+        cliff_seconds: u64,
+        vesting_seconds: u64,
+    ) -> Result<()> {
+        set_params::set_protocol_token_alloc(ctx, protocol_token_alloc_points, cliff_seconds, vesting_seconds)
+    }
+
+    pub fn set_launch_protection(ctx: Context<SetParams>, launch_protection: LaunchProtection) -> Result<()> {
+        set_params::set_launch_protection(ctx, launch_protection)
+    }
+
+    pub fn set_sandwich_protection(ctx: Context<SetParams>, sandwich_protection: SandwichProtection) -> Result<()> {
+        set_params::set_sandwich_protection(ctx, sandwich_protection)
+    }
+
+    pub fn set_mint_suffix_policy(ctx: Context<SetParams>, mode: MintSuffixMode, suffix: String) -> Result<()> {
+        set_params::set_mint_suffix_policy(ctx, mode, suffix)
+    }
+
+    pub fn set_curve_template(
+        ctx: Context<SetCurveTemplate>,
+        template_id: u32,
+        enabled: bool,
+        virtual_sol_reserves: u64,
+        virtual_token_reserves: u64,
+        real_token_reserves: u64,
+        token_total_supply: u64,
+        decimals: u8,
+        fee_override: Option<FeeBasisPoints>,
+        graduation_sol_target: u64,
+        launch_protection: LaunchProtection,
+        sandwich_protection: SandwichProtection,
+    ) -> Result<()> {
+        curve_template::set_curve_template(
+            ctx,
+            template_id,
+            enabled,
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            real_token_reserves,
+            token_total_supply,
+            decimals,
+            fee_override,
+            graduation_sol_target,
+            launch_protection,
+            sandwich_protection,
+        )
+    }
+
 
 }
@@ -0,0 +1,107 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+use crate::CurveLaunchpadError;
+
+/// Community-first phase of a curve: until `end_time` only the wallets in
+/// the merkle tree can buy. Leaves are `keccak(wallet || max_buy_sol)`, with
+/// `max_buy_sol` little-endian and 0 for no cap, and pairs are hashed sorted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct AllowlistPhase {
+    pub merkle_root: [u8; 32],
+    pub end_time: u64,
+}
+
+/// What the creator commits at create.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AllowlistParams {
+    pub merkle_root: [u8; 32],
+    pub duration_seconds: u64,
+}
+
+/// A buyer's leaf and the proof of it against the curve's root.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AllowlistProof {
+    pub max_buy_sol: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl AllowlistPhase {
+    pub fn new(params: &AllowlistParams, create_time: u64) -> Result<Self> {
+        require!(
+            params.merkle_root != [0; 32] && params.duration_seconds > 0,
+            CurveLaunchpadError::InvalidAllowlist
+        );
+
+        Ok(AllowlistPhase {
+            merkle_root: params.merkle_root,
+            end_time: create_time.saturating_add(params.duration_seconds),
+        })
+    }
+
+    pub fn is_active(&self, now: u64) -> bool {
+        now < self.end_time
+    }
+
+    /// Checks `user` is in the tree with the cap the proof claims.
+    pub fn verify(&self, user: &Pubkey, allowlist_proof: &AllowlistProof) -> Result<()> {
+        let mut node = Self::leaf(user, allowlist_proof.max_buy_sol);
+        for sibling in allowlist_proof.proof.iter() {
+            node = if node <= *sibling {
+                keccak::hashv(&[&node, sibling]).0
+            } else {
+                keccak::hashv(&[sibling, &node]).0
+            };
+        }
+        require!(node == self.merkle_root, CurveLaunchpadError::NotAllowlisted);
+
+        Ok(())
+    }
+
+    pub fn leaf(user: &Pubkey, max_buy_sol: u64) -> [u8; 32] {
+        keccak::hashv(&[user.as_ref(), &max_buy_sol.to_le_bytes()]).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            keccak::hashv(&[&a, &b]).0
+        } else {
+            keccak::hashv(&[&b, &a]).0
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let users: Vec<Pubkey> = (0..4u8).map(|i| Pubkey::new_from_array([i + 1; 32])).collect();
+        let leaves: Vec<[u8; 32]> = users
+            .iter()
+            .enumerate()
+            .map(|(i, user)| AllowlistPhase::leaf(user, i as u64 * 1_000_000_000))
+            .collect();
+        let left = hash_pair(leaves[0], leaves[1]);
+        let right = hash_pair(leaves[2], leaves[3]);
+
+        let params = AllowlistParams { merkle_root: hash_pair(left, right), duration_seconds: 600 };
+        let phase = AllowlistPhase::new(&params, 1_000).unwrap();
+        assert!(phase.is_active(1_599));
+        assert!(!phase.is_active(1_600));
+
+        let proof = AllowlistProof { max_buy_sol: 2_000_000_000, proof: vec![leaves[3], left] };
+        phase.verify(&users[2], &proof).unwrap();
+
+        //a wallet can't claim a bigger cap, or another wallet's leaf
+        let inflated = AllowlistProof { max_buy_sol: 3_000_000_000, ..proof.clone() };
+        assert!(phase.verify(&users[2], &inflated).is_err());
+        assert!(phase.verify(&users[3], &proof).is_err());
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(AllowlistPhase::new(&AllowlistParams { merkle_root: [0; 32], duration_seconds: 600 }, 0).is_err());
+        assert!(AllowlistPhase::new(&AllowlistParams { merkle_root: [1; 32], duration_seconds: 0 }, 0).is_err());
+    }
+}
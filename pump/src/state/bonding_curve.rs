@@ -1,6 +1,43 @@
 use anchor_lang::prelude::*;
 use std::fmt;
 
+use crate::amm::{
+    CurveModel, ExponentialPricing, LinearPricing, SigmoidPricing, SupplyCurve, AMM,
+};
+use crate::state::{AllowlistPhase, FeeBasisPoints, LaunchProtection, SandwichProtection, SlotTrade};
+use crate::CurveLaunchpadError;
+
+/// Pricing family of a bonding curve. `curve_params` is read per family:
+/// - `ConstantProduct`: unused, priced off the virtual reserves
+/// - `Linear`: [start_price, end_price, _, _]
+/// - `Exponential`: [start_price, growth, _, _], growth scaled by `math::ONE`
+/// - `Sigmoid`: [start_price, end_price, ramp_start, ramp_end]
+///
+/// Prices are lamports per token base unit scaled by `math::ONE`, ramp bounds
+/// are tokens sold.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum CurveKind {
+    ConstantProduct,
+    Linear,
+    Exponential,
+    Sigmoid,
+}
+
+/// Lifecycle of a bonding curve. Only `Trading` curves accept buys and sells,
+/// and every status can only move forward to the next one.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum CurveStatus {
+    /// Open for buys and sells.
+    Trading,
+    /// Real token reserves are sold out, the liquidity is snapshotted into the pool fields.
+    Completed,
+    /// The snapshotted liquidity has left the curve and is on its way into a
+    /// pool, which `withdraw` leaves until the external pool is seeded.
+    Migrating,
+    /// The pool holds the liquidity.
+    Migrated,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct BondingCurve {
@@ -18,23 +55,321 @@ pub struct BondingCurve {
     pub mint: Pubkey,
     pub create_time: u64,
     pub update_time: u64,
+
+    pub curve_kind: CurveKind,
+    pub curve_params: [u64; 4],
+
+    pub status: CurveStatus,
+
+    /// SPL mint the curve is priced in, `Pubkey::default()` for native SOL.
+    /// The `*_sol_*` fields are in this asset's base units.
+    pub quote_mint: Pubkey,
+
+    /// Launch parameters snapshotted from the curve template at create time,
+    /// so later `Global` or template changes never reach a live curve.
+    pub template_id: u32,
+    pub initial_real_token_reserves: u64,
+    pub fees: FeeBasisPoints,
+    /// Real SOL reserves that complete the curve, 0 to complete only once sold out.
+    pub graduation_sol_target: u64,
+
+    /// Creator's purchase made inside `create`, before anyone else could trade.
+    pub dev_buy_sol_amount: u64,
+    pub dev_buy_token_amount: u64,
+
+    /// Tokens of the supply held in the protocol allocation escrow instead of the curve.
+    pub protocol_token_allocation: u64,
+
+    /// Per-wallet buy caps right after `create_time`.
+    pub launch_protection: LaunchProtection,
+
+    /// Allowlist phase committed at create, zeroed when the curve has none.
+    pub allowlist: AllowlistPhase,
+
+    pub sandwich_protection: SandwichProtection,
+    /// Last slot the curve was traded in, tracked while sandwich protection is on.
+    pub slot_trade: SlotTrade,
 }
 
 impl BondingCurve {
     pub const SEED_PREFIX: &'static [u8; 13] = b"bonding-curve";
+
+    pub fn is_trading(&self) -> bool {
+        self.status == CurveStatus::Trading
+    }
+
+    pub fn is_native_quote(&self) -> bool {
+        self.quote_mint == Pubkey::default()
+    }
+
+    /// Trading -> Completed. Locks trading and records the SOL and tokens left
+    /// in the curve for the pool.
+    pub fn complete_curve(&mut self, pool_sol_amount: u64, pool_token_amount: u64) -> Result<()> {
+        self.transition(CurveStatus::Trading, CurveStatus::Completed)?;
+        self.complete = true;
+        self.pool_sol_amount = pool_sol_amount;
+        self.pool_token_amount = pool_token_amount;
+        Ok(())
+    }
+
+    /// Completed -> Migrating.
+    pub fn start_migration(&mut self) -> Result<()> {
+        self.transition(CurveStatus::Completed, CurveStatus::Migrating)
+    }
+
+    /// Migrating -> Migrated.
+    pub fn finish_migration(&mut self) -> Result<()> {
+        self.transition(CurveStatus::Migrating, CurveStatus::Migrated)
+    }
+
+    /// Whether the curve has sold out or reached its graduation target.
+    pub fn reached_graduation(&self) -> bool {
+        self.real_token_reserves == 0
+            || (self.graduation_sol_target > 0 && self.real_sol_reserves >= self.graduation_sol_target)
+    }
+
+    /// Takes the protocol allocation out of the tokens the curve sells. The
+    /// virtual token reserves shrink by the same amount, so the tokens left for
+    /// the pool do not change. The curve sells fewer tokens over a shorter
+    /// range, it starts at a higher price and ends at a lower one.
+    pub fn carve_out_protocol_allocation(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount < self.real_token_reserves && amount <= self.virtual_token_reserves,
+            CurveLaunchpadError::ProtocolAllocationTooLarge
+        );
+        self.real_token_reserves -= amount;
+        self.virtual_token_reserves -= amount;
+        self.initial_real_token_reserves -= amount;
+        self.protocol_token_allocation = amount;
+        Ok(())
+    }
+
+    /// Extra fee rate of the launch protection's sniper fee at `now`.
+    pub fn sniper_fee_basis_points(&self, now: u64) -> u64 {
+        self.launch_protection
+            .sniper_fee
+            .basis_points(now.saturating_sub(self.create_time))
+    }
+
+    fn transition(&mut self, from: CurveStatus, to: CurveStatus) -> Result<()> {
+        require!(self.status == from, CurveLaunchpadError::InvalidCurveStatus);
+        self.status = to;
+        Ok(())
+    }
+
+    /// Pricing model for the curve's kind and parameters at its current reserves,
+    /// `None` if the parameters are invalid for that kind.
+    pub fn curve_model(&self) -> Option<Box<dyn CurveModel>> {
+        let params = self.curve_params.map(|param| param as u128);
+        let real_sol_reserves = self.real_sol_reserves as u128;
+        let real_token_reserves = self.real_token_reserves as u128;
+        let initial_real_token_reserves = self.initial_real_token_reserves as u128;
+
+        let curve: Box<dyn CurveModel> = match self.curve_kind {
+            CurveKind::ConstantProduct => Box::new(AMM::new(
+                self.virtual_sol_reserves as u128,
+                self.virtual_token_reserves as u128,
+                real_sol_reserves,
+                real_token_reserves,
+            )),
+            CurveKind::Linear => Box::new(SupplyCurve::new(
+                LinearPricing::new(params[0], params[1], initial_real_token_reserves)?,
+                real_sol_reserves,
+                real_token_reserves,
+                initial_real_token_reserves,
+            )),
+            CurveKind::Exponential => Box::new(SupplyCurve::new(
+                ExponentialPricing::new(params[0], params[1], initial_real_token_reserves)?,
+                real_sol_reserves,
+                real_token_reserves,
+                initial_real_token_reserves,
+            )),
+            CurveKind::Sigmoid => Box::new(SupplyCurve::new(
+                SigmoidPricing::new(params[0], params[1], params[2], params[3])?,
+                real_sol_reserves,
+                real_token_reserves,
+                initial_real_token_reserves,
+            )),
+        };
+
+        Some(curve)
+    }
+
+    /// Spot price in lamports per token base unit, scaled by `math::ONE`.
+    pub fn spot_price(&self) -> Option<u128> {
+        self.curve_model()?.spot_price()
+    }
+
+    /// Fully diluted market cap of `token_total_supply` at the spot price, in lamports.
+    pub fn market_cap(&self) -> Option<u64> {
+        let market_cap = self
+            .curve_model()?
+            .market_cap(self.token_total_supply as u128)?;
+        u64::try_from(market_cap).ok()
+    }
+
+    /// Progress towards completion in basis points: the share of the initial
+    /// real token reserves already sold, or of the SOL target if that is closer.
+    pub fn graduation_progress_bps(&self) -> u64 {
+        if self.initial_real_token_reserves == 0 {
+            return 0;
+        }
+
+        let sold = self
+            .initial_real_token_reserves
+            .saturating_sub(self.real_token_reserves) as u128;
+        let token_progress = (sold * 10000 / self.initial_real_token_reserves as u128) as u64;
+        if self.graduation_sol_target == 0 {
+            return token_progress;
+        }
+
+        let sol_progress =
+            (self.real_sol_reserves as u128 * 10000 / self.graduation_sol_target as u128).min(10000) as u64;
+        token_progress.max(sol_progress)
+    }
+
+    /// SOL, before fees, that still has to go in to complete the curve.
+    pub fn sol_to_graduation(&self) -> Option<u64> {
+        let sol_to_complete = u64::try_from(self.curve_model()?.sol_to_complete()?).ok()?;
+        if self.graduation_sol_target == 0 {
+            return Some(sol_to_complete);
+        }
+
+        let sol_to_target = self.graduation_sol_target.saturating_sub(self.real_sol_reserves);
+        Some(sol_to_complete.min(sol_to_target))
+    }
+
+    /// Price impact of a `token_amount` trade against the current reserves, in basis points.
+    pub fn price_impact_bps(&self, token_amount: u64, is_buy: bool) -> Option<u64> {
+        self.curve_model()?
+            .price_impact_bps(token_amount as u128, is_buy)
+    }
 }
 
 impl fmt::Display for BondingCurve {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, token_total_supply: {}, complete: {}",
+            "virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, token_total_supply: {}, complete: {}, status: {:?}, curve_kind: {:?}",
             self.virtual_sol_reserves,
             self.virtual_token_reserves,
             self.real_sol_reserves,
             self.real_token_reserves,
             self.token_total_supply,
-            self.complete
+            self.complete,
+            self.status,
+            self.curve_kind
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bonding_curve() -> BondingCurve {
+        BondingCurve {
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 0,
+            real_sol_reserves: 85_000_000_000,
+            real_token_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+            pool_sol_amount: 0,
+            pool_token_amount: 0,
+            creator: Pubkey::default(),
+            mint: Pubkey::default(),
+            create_time: 0,
+            update_time: 0,
+            curve_kind: CurveKind::ConstantProduct,
+            curve_params: [0; 4],
+            status: CurveStatus::Trading,
+            quote_mint: Pubkey::default(),
+            template_id: 0,
+            initial_real_token_reserves: 793_100_000_000_000,
+            fees: FeeBasisPoints::default(),
+            graduation_sol_target: 0,
+            dev_buy_sol_amount: 0,
+            dev_buy_token_amount: 0,
+            protocol_token_allocation: 0,
+            launch_protection: LaunchProtection::default(),
+            allowlist: AllowlistPhase::default(),
+            sandwich_protection: SandwichProtection::default(),
+            slot_trade: SlotTrade::default(),
+        }
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let mut bonding_curve = bonding_curve();
+        assert!(bonding_curve.is_trading());
+
+        // can't skip ahead
+        assert!(bonding_curve.start_migration().is_err());
+        assert!(bonding_curve.finish_migration().is_err());
+
+        bonding_curve.complete_curve(85_000_000_000, 206_900_000_000_000).unwrap();
+        assert!(!bonding_curve.is_trading());
+        assert!(bonding_curve.complete);
+        assert_eq!(bonding_curve.status, CurveStatus::Completed);
+        assert_eq!(bonding_curve.pool_sol_amount, 85_000_000_000);
+        assert_eq!(bonding_curve.pool_token_amount, 206_900_000_000_000);
+
+        // completing twice would overwrite the snapshot
+        assert!(bonding_curve.complete_curve(0, 0).is_err());
+        assert_eq!(bonding_curve.pool_sol_amount, 85_000_000_000);
+
+        bonding_curve.start_migration().unwrap();
+        assert!(bonding_curve.start_migration().is_err());
+        assert_eq!(bonding_curve.status, CurveStatus::Migrating);
+        assert!(bonding_curve.complete_curve(0, 0).is_err());
+
+        bonding_curve.finish_migration().unwrap();
+        assert!(bonding_curve.finish_migration().is_err());
+        assert_eq!(bonding_curve.status, CurveStatus::Migrated);
+        assert!(bonding_curve.complete_curve(0, 0).is_err());
+    }
+
+    #[test]
+    fn test_graduation_target() {
+        let mut bonding_curve = bonding_curve();
+        bonding_curve.virtual_token_reserves = 500_000_000_000_000;
+        bonding_curve.real_token_reserves = 396_550_000_000_000;
+        bonding_curve.real_sol_reserves = 20_000_000_000;
+        assert!(!bonding_curve.reached_graduation());
+        assert_eq!(bonding_curve.graduation_progress_bps(), 5000);
+
+        //a target closer than selling out caps the SOL still needed
+        bonding_curve.graduation_sol_target = 25_000_000_000;
+        assert_eq!(bonding_curve.graduation_progress_bps(), 8000);
+        assert_eq!(bonding_curve.sol_to_graduation(), Some(5_000_000_000));
+
+        bonding_curve.real_sol_reserves = 25_000_000_000;
+        assert!(bonding_curve.reached_graduation());
+        assert_eq!(bonding_curve.graduation_progress_bps(), 10000);
+    }
+
+    #[test]
+    fn test_carve_out_protocol_allocation() {
+        let mut bonding_curve = bonding_curve();
+        bonding_curve.real_sol_reserves = 0;
+        bonding_curve.virtual_token_reserves = 1_073_000_000_000_000;
+        bonding_curve.real_token_reserves = 793_100_000_000_000;
+        let end_token_reserves = bonding_curve.virtual_token_reserves - bonding_curve.real_token_reserves;
+        let spot_price = bonding_curve.spot_price().unwrap();
+
+        bonding_curve.carve_out_protocol_allocation(5_000_000_000_000).unwrap();
+        assert_eq!(bonding_curve.real_token_reserves, 788_100_000_000_000);
+        assert_eq!(bonding_curve.initial_real_token_reserves, 788_100_000_000_000);
+        assert_eq!(bonding_curve.protocol_token_allocation, 5_000_000_000_000);
+        assert_eq!(
+            bonding_curve.virtual_token_reserves - bonding_curve.real_token_reserves,
+            end_token_reserves
+        );
+        assert!(bonding_curve.spot_price().unwrap() > spot_price);
+
+        //the curve must keep something to sell
+        let real_token_reserves = bonding_curve.real_token_reserves;
+        assert!(bonding_curve.carve_out_protocol_allocation(real_token_reserves).is_err());
+    }
+}
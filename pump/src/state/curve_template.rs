@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::{Global, LaunchProtection, SandwichProtection}, CurveLaunchpadError, DEFAULT_DECIMALS};
+
+/// Trade fee split, in basis points of the traded SOL.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct FeeBasisPoints {
+    pub protocol: u64,
+    pub creator: u64,
+    pub invite: u64,
+}
+
+impl FeeBasisPoints {
+    pub fn total(&self) -> u64 {
+        self.protocol + self.creator + self.invite
+    }
+}
+
+/// Launch preset managed by the authority and picked by the creator at
+/// create time. Its parameters are copied into the bonding curve, editing the
+/// template only affects curves created afterwards.
+#[account]
+#[derive(InitSpace)]
+pub struct CurveTemplate {
+    pub id: u32,
+    pub enabled: bool,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub decimals: u8,
+    /// Trade fees of curves on this template, `None` for `Global`'s fees at create time.
+    pub fee_override: Option<FeeBasisPoints>,
+    /// Real SOL reserves that complete the curve, 0 to complete only once sold out.
+    pub graduation_sol_target: u64,
+    pub launch_protection: LaunchProtection,
+    pub sandwich_protection: SandwichProtection,
+}
+
+impl CurveTemplate {
+    pub const SEED_PREFIX: &'static [u8; 14] = b"curve-template";
+
+    /// Launch parameters of curves created without a template.
+    pub fn from_global(global: &Global) -> Self {
+        CurveTemplate {
+            id: 0,
+            enabled: true,
+            virtual_sol_reserves: global.initial_virtual_sol_reserves,
+            virtual_token_reserves: global.initial_virtual_token_reserves,
+            real_token_reserves: global.initial_real_token_reserves,
+            token_total_supply: global.initial_token_supply,
+            decimals: DEFAULT_DECIMALS as u8,
+            fee_override: None,
+            graduation_sol_target: 0,
+            launch_protection: global.launch_protection,
+            sandwich_protection: global.sandwich_protection,
+        }
+    }
+
+    /// Same bounds `set_amm_params` puts on the global parameters.
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.virtual_sol_reserves > 0
+                && self.real_token_reserves > 0
+                && self.real_token_reserves < self.virtual_token_reserves
+                && self.real_token_reserves <= self.token_total_supply,
+            CurveLaunchpadError::InvalidAmmParams
+        );
+        require!(self.decimals <= 9, CurveLaunchpadError::InvalidAmmParams);
+        if let Some(fees) = self.fee_override {
+            require!(fees.total() < 10000, CurveLaunchpadError::InvalidAmmParams);
+        }
+        self.launch_protection.validate()?;
+
+        Ok(())
+    }
+
+    /// Fails when `global`'s protocol allocation would leave curves on this
+    /// template nothing to sell.
+    pub fn validate_protocol_allocation(&self, global: &Global) -> Result<()> {
+        require!(
+            global.protocol_token_allocation(self.token_total_supply) < self.real_token_reserves,
+            CurveLaunchpadError::ProtocolAllocationTooLarge
+        );
+
+        Ok(())
+    }
+
+    pub fn fees(&self, global: &Global) -> FeeBasisPoints {
+        self.fee_override.unwrap_or_else(|| global.trade_fee_basis_points())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{MintSuffixMode, MintSuffixPolicy};
+
+    fn template() -> CurveTemplate {
+        CurveTemplate {
+            id: 1,
+            enabled: true,
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+            decimals: 6,
+            fee_override: None,
+            graduation_sol_target: 0,
+            launch_protection: LaunchProtection::default(),
+            sandwich_protection: SandwichProtection::default(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        template().validate().unwrap();
+
+        let mut invalid = template();
+        invalid.virtual_sol_reserves = 0;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = template();
+        invalid.real_token_reserves = invalid.virtual_token_reserves;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = template();
+        invalid.decimals = 10;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = template();
+        invalid.fee_override = Some(FeeBasisPoints { protocol: 5000, creator: 5000, invite: 0 });
+        assert!(invalid.validate().is_err());
+    }
+
+    fn global() -> Global {
+        Global {
+            authority: Pubkey::default(),
+            initialized: true,
+            fee_recipient: Pubkey::default(),
+            initial_virtual_token_reserves: 0,
+            initial_virtual_sol_reserves: 0,
+            initial_real_token_reserves: 0,
+            initial_real_sol_reserves: 0,
+            initial_token_supply: 0,
+            fee_basis_points: 50,
+            withdraw_authority: Pubkey::default(),
+            creator_fee_basis_points: 35,
+            protocol_token_alloc_points: 0,
+            protocol_token_alloc_recipient: Pubkey::default(),
+            invite_fee_basis_points: 15,
+            mint_suffix_policy: MintSuffixPolicy::new(MintSuffixMode::Disabled, "").unwrap(),
+            protocol_token_alloc_cliff_seconds: 0,
+            protocol_token_alloc_vesting_seconds: 0,
+            launch_protection: LaunchProtection::default(),
+            sandwich_protection: SandwichProtection::default(),
+        }
+    }
+
+    #[test]
+    fn test_validate_protocol_allocation() {
+        let mut global = global();
+        global.protocol_token_alloc_points = 500;
+        template().validate_protocol_allocation(&global).unwrap();
+
+        //an allocation of the whole real reserve leaves nothing to sell
+        let mut invalid = template();
+        invalid.real_token_reserves = 50_000_000_000_000;
+        assert!(invalid.validate_protocol_allocation(&global).is_err());
+
+        global.protocol_token_alloc_points = 8000;
+        assert!(template().validate_protocol_allocation(&global).is_err());
+    }
+
+    #[test]
+    fn test_fees() {
+        let global = global();
+
+        let mut template = template();
+        assert_eq!(template.fees(&global), FeeBasisPoints { protocol: 50, creator: 35, invite: 15 });
+        assert_eq!(template.fees(&global).total(), 100);
+
+        let fee_override = FeeBasisPoints { protocol: 20, creator: 0, invite: 10 };
+        template.fee_override = Some(fee_override);
+        assert_eq!(template.fees(&global), fee_override);
+    }
+}
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+
+use crate::{amm::math::ONE, CurveLaunchpadError};
+
+/// Recurring buy of one SOL curve. The vault holds `balance` lamports on top
+/// of its rent and anyone can crank `execute_dca` once `next_purchase_time`
+/// is reached, spending `amount_per_interval`, fees included, as long as the
+/// buy averages at `max_price` or better before fees.
+#[account]
+#[derive(InitSpace)]
+pub struct DcaVault {
+    pub owner: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub mint: Pubkey,
+    /// Picked by the owner, so one wallet can have several vaults on a curve.
+    pub id: u64,
+    pub balance: u64,
+    pub interval_seconds: u64,
+    pub amount_per_interval: u64,
+    /// Lamports per token base unit, scaled by `amm::math::ONE`.
+    pub max_price: u128,
+    pub next_purchase_time: u64,
+    pub purchases: u64,
+    pub total_sol_spent: u64,
+    pub total_tokens_bought: u64,
+}
+
+impl DcaVault {
+    pub const SEED_PREFIX: &'static [u8; 9] = b"dca-vault";
+
+    pub fn validate_terms(deposit: u64, interval_seconds: u64, amount_per_interval: u64, max_price: u128) -> Result<()> {
+        require!(
+            interval_seconds > 0
+                && amount_per_interval > 0
+                && deposit >= amount_per_interval
+                && max_price > 0,
+            CurveLaunchpadError::InvalidDcaTerms
+        );
+
+        Ok(())
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        now >= self.next_purchase_time
+    }
+
+    /// Whether a purchase moving `sol_amount` against `token_amount` on the
+    /// curve, both before fees, averages at the max price or better. A max
+    /// price too large to multiply out caps nothing.
+    pub fn honors_max_price(&self, sol_amount: u64, token_amount: u64) -> bool {
+        token_amount > 0
+            && self
+                .max_price
+                .checked_mul(token_amount as u128)
+                .is_none_or(|max_cost| sol_amount as u128 * ONE <= max_cost)
+    }
+
+    /// Whether the balance can't pay for another interval. What is left is
+    /// refunded to the owner when the vault closes.
+    pub fn is_empty(&self) -> bool {
+        self.balance < self.amount_per_interval
+    }
+
+    /// Adds a purchase and schedules the next one. Intervals missed by the
+    /// crank are skipped rather than bought back to back.
+    pub fn record_purchase(&mut self, now: u64, sol_amount: u64, token_amount: u64) {
+        self.balance -= sol_amount;
+        self.purchases += 1;
+        self.total_sol_spent += sol_amount;
+        self.total_tokens_bought += token_amount;
+
+        self.next_purchase_time = self.next_purchase_time.saturating_add(self.interval_seconds);
+        if self.next_purchase_time <= now {
+            self.next_purchase_time = now.saturating_add(self.interval_seconds);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_purchase() {
+        let mut vault = DcaVault {
+            owner: Pubkey::default(),
+            bonding_curve: Pubkey::default(),
+            mint: Pubkey::default(),
+            id: 0,
+            balance: 250_000_000,
+            interval_seconds: 3_600,
+            amount_per_interval: 100_000_000,
+            max_price: ONE,
+            next_purchase_time: 1_000,
+            purchases: 0,
+            total_sol_spent: 0,
+            total_tokens_bought: 0,
+        };
+        assert!(!vault.is_due(999));
+        assert!(vault.is_due(1_000));
+
+        //a late crank keeps the schedule
+        vault.record_purchase(1_100, 99_999_990, 5_000);
+        assert_eq!(vault.next_purchase_time, 4_600);
+        assert!(!vault.is_empty());
+
+        //missed intervals are skipped
+        vault.record_purchase(20_000, 100_000_000, 4_000);
+        assert_eq!(vault.next_purchase_time, 23_600);
+        assert_eq!(vault.balance, 50_000_010);
+        assert_eq!(vault.purchases, 2);
+        assert_eq!(vault.total_sol_spent, 199_999_990);
+        assert_eq!(vault.total_tokens_bought, 9_000);
+        assert!(vault.is_empty());
+    }
+
+    #[test]
+    fn test_honors_max_price() {
+        let vault = DcaVault {
+            owner: Pubkey::default(),
+            bonding_curve: Pubkey::default(),
+            mint: Pubkey::default(),
+            id: 0,
+            balance: 0,
+            interval_seconds: 3_600,
+            amount_per_interval: 100_000_000,
+            //30 lamports per token base unit
+            max_price: 30 * ONE,
+            next_purchase_time: 0,
+            purchases: 0,
+            total_sol_spent: 0,
+            total_tokens_bought: 0,
+        };
+        assert!(vault.honors_max_price(3_000, 100));
+        assert!(vault.honors_max_price(2_999, 100));
+        assert!(!vault.honors_max_price(3_001, 100));
+        assert!(!vault.honors_max_price(3_000, 0));
+
+        //no cap
+        let vault = DcaVault { max_price: u128::MAX, ..vault };
+        assert!(vault.honors_max_price(u64::MAX, 1));
+        assert!(vault.honors_max_price(u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn test_validate_terms() {
+        DcaVault::validate_terms(1_000, 60, 100, ONE).unwrap();
+        assert!(DcaVault::validate_terms(99, 60, 100, ONE).is_err());
+        assert!(DcaVault::validate_terms(1_000, 0, 100, ONE).is_err());
+        assert!(DcaVault::validate_terms(1_000, 60, 0, ONE).is_err());
+        assert!(DcaVault::validate_terms(1_000, 60, 100, 0).is_err());
+    }
+}
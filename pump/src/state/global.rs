@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    state::{FeeBasisPoints, LaunchProtection, MintSuffixMode, MintSuffixPolicy, SandwichProtection},
+    DEFAULT_MINT_SUFFIX,
+};
+
 
 
 #[account]
@@ -9,18 +14,110 @@ pub struct Global {
     pub initialized: bool,
     pub fee_recipient: Pubkey,
     pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
     pub initial_real_token_reserves: u64,
     pub initial_real_sol_reserves: u64,
+    pub initial_token_supply: u64,
     pub fee_basis_points: u64,
     pub withdraw_authority: Pubkey,
 
     pub creator_fee_basis_points: u64,
+    /// Basis points of each launch's supply set aside for the protocol.
     pub protocol_token_alloc_points:u64,
     pub protocol_token_alloc_recipient:Pubkey,
 
     pub invite_fee_basis_points: u64,
+
+    pub mint_suffix_policy: MintSuffixPolicy,
+
+    /// Vesting of the protocol token allocation, 0 to release it at create time.
+    pub protocol_token_alloc_cliff_seconds: u64,
+    pub protocol_token_alloc_vesting_seconds: u64,
+
+    /// Launch protection of curves created without a template.
+    pub launch_protection: LaunchProtection,
+    /// Sandwich protection of curves created without a template.
+    pub sandwich_protection: SandwichProtection,
 }
 
 impl Global {
    pub const SEED_PREFIX: &'static [u8; 6] = b"CONFIG";
-}
\ No newline at end of file
+
+   /// Size of the layout before the mint suffix policy, the first field
+   /// `migrate_global` grows the account by.
+   pub const MINT_SUFFIX_POLICY_OFFSET: usize = 8 + 32 + 1 + 32 + 8 * 6 + 32 + 8 + 8 + 32 + 8;
+
+   /// Sets the fields a `Global` of `old_len` bytes did not have yet to the
+   /// defaults of `initialize`, where those are not zero.
+   pub fn fill_grown_fields(&mut self, old_len: usize) -> Result<()> {
+       if old_len <= Self::MINT_SUFFIX_POLICY_OFFSET {
+           self.mint_suffix_policy = MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, DEFAULT_MINT_SUFFIX)?;
+       }
+       Ok(())
+   }
+
+   /// Fees of pool trades, and of curves whose template does not override them.
+   pub fn trade_fee_basis_points(&self) -> FeeBasisPoints {
+       FeeBasisPoints {
+           protocol: self.fee_basis_points,
+           creator: self.creator_fee_basis_points,
+           invite: self.invite_fee_basis_points,
+       }
+   }
+
+   /// Protocol's share of a launch minting `token_total_supply`.
+   pub fn protocol_token_allocation(&self, token_total_supply: u64) -> u64 {
+       (token_total_supply as u128 * self.protocol_token_alloc_points as u128 / 10000) as u64
+   }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_grown_global_keeps_enforcing_meme_suffix() {
+        let global = Global {
+            authority: Pubkey::new_unique(),
+            initialized: true,
+            fee_recipient: Pubkey::new_unique(),
+            initial_virtual_token_reserves: 1_075_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_real_token_reserves: 793_100_000_000_000,
+            initial_real_sol_reserves: 0,
+            initial_token_supply: 1_000_000_000_000_000,
+            fee_basis_points: 50,
+            withdraw_authority: Pubkey::new_unique(),
+            creator_fee_basis_points: 35,
+            protocol_token_alloc_points: 50,
+            protocol_token_alloc_recipient: Pubkey::new_unique(),
+            invite_fee_basis_points: 15,
+            mint_suffix_policy: MintSuffixPolicy::new(MintSuffixMode::CaseSensitive, "abc").unwrap(),
+            protocol_token_alloc_cliff_seconds: 0,
+            protocol_token_alloc_vesting_seconds: 0,
+            launch_protection: LaunchProtection::default(),
+            sandwich_protection: SandwichProtection::default(),
+        };
+
+        //an account of the old layout, grown and zero-filled by realloc
+        let mut data = Vec::new();
+        global.try_serialize(&mut data).unwrap();
+        data.truncate(Global::MINT_SUFFIX_POLICY_OFFSET);
+        data.resize(8 + Global::INIT_SPACE, 0);
+
+        let mut grown = Global::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(grown.invite_fee_basis_points, 15);
+        grown.fill_grown_fields(Global::MINT_SUFFIX_POLICY_OFFSET).unwrap();
+
+        assert_eq!(grown.mint_suffix_policy.mode, MintSuffixMode::CaseInsensitive);
+        assert!(grown.mint_suffix_policy.matches(&Pubkey::from_str("3mvmucaXydtR5YPyKMaMfbPLpHf1pTaniDGPNf6QMeMe").unwrap()));
+        assert!(!grown.mint_suffix_policy.matches(&crate::ID));
+
+        //a policy that was already there is kept
+        let mut current = global.clone();
+        current.fill_grown_fields(8 + Global::INIT_SPACE).unwrap();
+        assert_eq!(current.mint_suffix_policy, global.mint_suffix_policy);
+    }
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+
+use crate::CurveLaunchpadError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub enum SniperFeeSchedule {
+    /// Decays continuously.
+    #[default]
+    Linear,
+    /// Drops by an equal amount every `step_seconds`.
+    Step,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub enum SniperFeeDestination {
+    /// Credited to the fee recipient like the protocol fee.
+    #[default]
+    Protocol,
+    /// Credited to the curve's creator like the creator fee.
+    Creator,
+    /// Sent to the incinerator.
+    Burn,
+}
+
+/// Extra trade fee right after `create_time`, on top of the curve's fees. It
+/// starts at `start_basis_points` and decays to nothing over `decay_seconds`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct SniperFee {
+    pub start_basis_points: u64,
+    pub decay_seconds: u64,
+    pub schedule: SniperFeeSchedule,
+    /// Only read by the `Step` schedule.
+    pub step_seconds: u64,
+    pub destination: SniperFeeDestination,
+}
+
+impl SniperFee {
+    pub fn validate(&self) -> Result<()> {
+        if self.start_basis_points == 0 {
+            return Ok(());
+        }
+        require!(
+            self.start_basis_points < 10000 && self.decay_seconds > 0,
+            CurveLaunchpadError::InvalidAmmParams
+        );
+        if self.schedule == SniperFeeSchedule::Step {
+            require!(
+                self.step_seconds > 0 && self.step_seconds <= self.decay_seconds,
+                CurveLaunchpadError::InvalidAmmParams
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Extra fee rate `elapsed` seconds after the curve was created.
+    pub fn basis_points(&self, elapsed: u64) -> u64 {
+        if self.start_basis_points == 0 || elapsed >= self.decay_seconds {
+            return 0;
+        }
+
+        let start = self.start_basis_points as u128;
+        match self.schedule {
+            SniperFeeSchedule::Linear => {
+                let remaining = (self.decay_seconds - elapsed) as u128;
+                (start * remaining / self.decay_seconds as u128) as u64
+            }
+            SniperFeeSchedule::Step => {
+                let steps = self.decay_seconds.div_ceil(self.step_seconds) as u128;
+                let remaining = steps - (elapsed / self.step_seconds) as u128;
+                (start * remaining / steps) as u64
+            }
+        }
+    }
+}
+
+/// Launch defenses of a curve. For the first `window_seconds` after its
+/// `create_time` each wallet's cumulative buys are capped, 0 for no cap on
+/// that side, and every trade pays the decaying `sniper_fee`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct LaunchProtection {
+    pub window_seconds: u64,
+    /// SOL spent on the curve, before fees.
+    pub max_buy_sol: u64,
+    /// Tokens taken out of the curve, before any transfer fee.
+    pub max_buy_tokens: u64,
+    pub sniper_fee: SniperFee,
+}
+
+impl LaunchProtection {
+    pub fn is_active(&self, create_time: u64, now: u64) -> bool {
+        self.window_seconds > 0 && now < create_time.saturating_add(self.window_seconds)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.sniper_fee.validate()
+    }
+
+    /// Fails with `LaunchBuyCapExceeded` when a wallet's cumulative buys in
+    /// the window go over either cap.
+    pub fn check_buy(&self, total_sol: u64, total_tokens: u64) -> Result<()> {
+        require!(
+            (self.max_buy_sol == 0 || total_sol <= self.max_buy_sol)
+                && (self.max_buy_tokens == 0 || total_tokens <= self.max_buy_tokens),
+            CurveLaunchpadError::LaunchBuyCapExceeded
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_active() {
+        let protection = LaunchProtection { window_seconds: 60, max_buy_sol: 1, ..Default::default() };
+        assert!(protection.is_active(1_000, 1_000));
+        assert!(protection.is_active(1_000, 1_059));
+        assert!(!protection.is_active(1_000, 1_060));
+
+        assert!(!LaunchProtection::default().is_active(1_000, 1_000));
+    }
+
+    #[test]
+    fn test_check_buy() {
+        let protection = LaunchProtection { window_seconds: 60, max_buy_sol: 1_000_000_000, ..Default::default() };
+        protection.check_buy(1_000_000_000, u64::MAX).unwrap();
+        assert!(protection.check_buy(1_000_000_001, 0).is_err());
+
+        let protection = LaunchProtection { window_seconds: 60, max_buy_tokens: 5_000_000, ..Default::default() };
+        protection.check_buy(u64::MAX, 5_000_000).unwrap();
+        assert!(protection.check_buy(0, 5_000_001).is_err());
+    }
+
+    #[test]
+    fn test_sniper_fee_linear() {
+        let sniper_fee = SniperFee {
+            start_basis_points: 2000,
+            decay_seconds: 100,
+            ..Default::default()
+        };
+        sniper_fee.validate().unwrap();
+        assert_eq!(sniper_fee.basis_points(0), 2000);
+        assert_eq!(sniper_fee.basis_points(25), 1500);
+        assert_eq!(sniper_fee.basis_points(99), 20);
+        assert_eq!(sniper_fee.basis_points(100), 0);
+
+        assert_eq!(SniperFee::default().basis_points(0), 0);
+    }
+
+    #[test]
+    fn test_sniper_fee_step() {
+        let sniper_fee = SniperFee {
+            start_basis_points: 3000,
+            decay_seconds: 90,
+            schedule: SniperFeeSchedule::Step,
+            step_seconds: 30,
+            destination: SniperFeeDestination::Burn,
+        };
+        sniper_fee.validate().unwrap();
+        assert_eq!(sniper_fee.basis_points(0), 3000);
+        assert_eq!(sniper_fee.basis_points(29), 3000);
+        assert_eq!(sniper_fee.basis_points(30), 2000);
+        assert_eq!(sniper_fee.basis_points(89), 1000);
+        assert_eq!(sniper_fee.basis_points(90), 0);
+
+        //a last partial step still counts as a step
+        let sniper_fee = SniperFee { decay_seconds: 100, ..sniper_fee };
+        assert_eq!(sniper_fee.basis_points(90), 750);
+
+        assert!(SniperFee { step_seconds: 0, ..sniper_fee }.validate().is_err());
+        assert!(SniperFee { decay_seconds: 0, ..sniper_fee }.validate().is_err());
+    }
+}
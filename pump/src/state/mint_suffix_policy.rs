@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::CurveLaunchpadError;
+
+/// Longest suffix the policy accepts. `58^9 * 256` still fits a u64, which
+/// keeps the suffix check free of wide arithmetic.
+pub const MAX_MINT_SUFFIX_LEN: usize = 9;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum MintSuffixMode {
+    /// Any mint address is accepted.
+    Disabled,
+    CaseInsensitive,
+    CaseSensitive,
+}
+
+/// Suffix new mint addresses must end with in base58.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct MintSuffixPolicy {
+    pub mode: MintSuffixMode,
+    pub len: u8,
+    /// Base58 characters, lowercased when the mode is case-insensitive.
+    pub suffix: [u8; MAX_MINT_SUFFIX_LEN],
+}
+
+impl MintSuffixPolicy {
+    pub fn new(mode: MintSuffixMode, suffix: &str) -> Result<Self> {
+        let mut policy = MintSuffixPolicy {
+            mode,
+            len: 0,
+            suffix: [0; MAX_MINT_SUFFIX_LEN],
+        };
+        if mode == MintSuffixMode::Disabled {
+            return Ok(policy);
+        }
+
+        require!(
+            !suffix.is_empty() && suffix.len() <= MAX_MINT_SUFFIX_LEN,
+            CurveLaunchpadError::InvalidMintSuffix
+        );
+        for (i, c) in suffix.bytes().enumerate() {
+            let c = if mode == MintSuffixMode::CaseSensitive { c } else { c.to_ascii_lowercase() };
+            require!(
+                BASE58_ALPHABET.iter().any(|a| policy.normalize(*a) == c),
+                CurveLaunchpadError::InvalidMintSuffix
+            );
+            policy.suffix[i] = c;
+        }
+        policy.len = suffix.len() as u8;
+
+        Ok(policy)
+    }
+
+    pub fn suffix_str(&self) -> String {
+        String::from_utf8_lossy(&self.suffix[..self.len as usize]).into_owned()
+    }
+
+    /// Compares the last base58 digits of `address` without encoding it: the
+    /// last `len` digits are the address taken modulo `58^len`.
+    pub fn matches(&self, address: &Pubkey) -> bool {
+        if self.mode == MintSuffixMode::Disabled {
+            return true;
+        }
+
+        let len = self.len as usize;
+        let modulus = 58u64.pow(len as u32);
+        let mut rem = 0u64;
+        for byte in address.as_ref() {
+            rem = ((rem << 8) | *byte as u64) % modulus;
+        }
+
+        for expected in self.suffix[..len].iter().rev() {
+            let digit = BASE58_ALPHABET[(rem % 58) as usize];
+            if self.normalize(digit) != *expected {
+                return false;
+            }
+            rem /= 58;
+        }
+
+        true
+    }
+
+    fn normalize(&self, c: u8) -> u8 {
+        match self.mode {
+            MintSuffixMode::CaseSensitive => c,
+            _ => c.to_ascii_lowercase(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_base58_suffix() {
+        for seed in 0u64..200 {
+            let address = Pubkey::find_program_address(&[&seed.to_le_bytes()], &crate::ID).0;
+            let encoded = address.to_string();
+
+            for len in 1..=4 {
+                let suffix = &encoded[encoded.len() - len..];
+                let policy = MintSuffixPolicy::new(MintSuffixMode::CaseSensitive, suffix).unwrap();
+                assert!(policy.matches(&address));
+
+                let policy = MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, &suffix.to_uppercase()).unwrap();
+                assert!(policy.matches(&address));
+            }
+
+            let policy = MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, "meme").unwrap();
+            assert_eq!(policy.matches(&address), encoded.to_lowercase().ends_with("meme"));
+        }
+
+        let address = crate::ID; //ends with "jpmg"
+        assert!(!MintSuffixPolicy::new(MintSuffixMode::CaseSensitive, "JPMG").unwrap().matches(&address));
+        assert!(MintSuffixPolicy::new(MintSuffixMode::Disabled, "").unwrap().matches(&address));
+    }
+
+    #[test]
+    fn test_invalid_suffix() {
+        assert!(MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, "").is_err());
+        assert!(MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, "memememe12").is_err());
+        assert!(MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, "me0").is_err());
+        assert!(MintSuffixPolicy::new(MintSuffixMode::CaseSensitive, "l").is_err());
+        //'L' exists, so a case-insensitive 'l' can still match
+        assert_eq!(MintSuffixPolicy::new(MintSuffixMode::CaseInsensitive, "L").unwrap().suffix_str(), "l");
+    }
+}
@@ -1,8 +1,34 @@
 pub mod global;
 pub mod bonding_curve;
 pub mod fee_account;
+pub mod user_invite_stats;
+pub mod pool;
+pub mod quote_asset;
+pub mod mint_suffix_policy;
+pub mod curve_template;
+pub mod vesting_escrow;
+pub mod launch_protection;
+pub mod user_curve_stats;
+pub mod allowlist;
+pub mod sandwich_protection;
+pub mod order;
+pub mod dca_vault;
+pub mod sell_trigger;
 
 pub use global::*;
 pub use bonding_curve::*;
 pub use fee_account::*;
+pub use user_invite_stats::*;
+pub use pool::*;
+pub use quote_asset::*;
+pub use mint_suffix_policy::*;
+pub use curve_template::*;
+pub use vesting_escrow::*;
+pub use launch_protection::*;
+pub use user_curve_stats::*;
+pub use allowlist::*;
+pub use sandwich_protection::*;
+pub use order::*;
+pub use dca_vault::*;
+pub use sell_trigger::*;
 
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::{amm::math::ONE, TradeSide};
+
+/// Limit order on a SOL curve, filled by anyone through `execute_order` once
+/// the spot price crosses `limit_price`.
+///
+/// A buy escrows `amount` SOL, fees included, in the order's lamports and an
+/// order is only filled if its average price before fees honors the limit,
+/// so a big order may wait for a better price than its limit. A sell escrows
+/// `amount` tokens in `token_account`, owned by the order. Both escrow the
+/// `keeper_fee` paid to whoever fills them.
+#[account]
+#[derive(InitSpace)]
+pub struct Order {
+    pub owner: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub mint: Pubkey,
+    /// Picked by the owner, so one wallet can have several orders on a curve.
+    pub id: u64,
+    pub side: TradeSide,
+    pub amount: u64,
+    /// Lamports per token base unit, scaled by `amm::math::ONE`.
+    pub limit_price: u128,
+    pub expiry: u64,
+    pub keeper_fee: u64,
+    /// Token escrow of a sell, the default key for a buy.
+    pub token_account: Pubkey,
+}
+
+impl Order {
+    pub const SEED_PREFIX: &'static [u8; 5] = b"order";
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expiry
+    }
+
+    /// Whether the spot price is at the limit or better for this order.
+    pub fn is_crossed(&self, spot_price: u128) -> bool {
+        match self.side {
+            TradeSide::Buy => spot_price <= self.limit_price,
+            TradeSide::Sell => spot_price >= self.limit_price,
+        }
+    }
+
+    /// Whether a fill moving `sol_amount` against `token_amount` on the
+    /// curve, both before fees, averages at the limit or better. A limit
+    /// too large to multiply out is above any price the curve can fill at.
+    pub fn honors_limit(&self, sol_amount: u64, token_amount: u64) -> bool {
+        if token_amount == 0 {
+            return false;
+        }
+
+        let sol_amount = sol_amount as u128 * ONE;
+        let limit_amount = self.limit_price.checked_mul(token_amount as u128);
+        match self.side {
+            TradeSide::Buy => limit_amount.is_none_or(|limit_amount| sol_amount <= limit_amount),
+            TradeSide::Sell => limit_amount.is_some_and(|limit_amount| sol_amount >= limit_amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(side: TradeSide) -> Order {
+        Order {
+            owner: Pubkey::default(),
+            bonding_curve: Pubkey::default(),
+            mint: Pubkey::default(),
+            id: 0,
+            side,
+            amount: 1_000_000_000,
+            //0.00003 lamports per base unit
+            limit_price: 30_000_000,
+            expiry: 1_000,
+            keeper_fee: 10_000,
+            token_account: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn test_is_crossed() {
+        let buy = order(TradeSide::Buy);
+        assert!(buy.is_crossed(30_000_000));
+        assert!(buy.is_crossed(29_000_000));
+        assert!(!buy.is_crossed(30_000_001));
+
+        let sell = order(TradeSide::Sell);
+        assert!(sell.is_crossed(30_000_000));
+        assert!(sell.is_crossed(31_000_000));
+        assert!(!sell.is_crossed(29_999_999));
+
+        assert!(!buy.is_expired(999));
+        assert!(buy.is_expired(1_000));
+    }
+
+    #[test]
+    fn test_honors_limit() {
+        let buy = order(TradeSide::Buy);
+        assert!(buy.honors_limit(30_000, 1_000_000_000));
+        assert!(!buy.honors_limit(30_001, 1_000_000_000));
+
+        let sell = order(TradeSide::Sell);
+        assert!(sell.honors_limit(30_000, 1_000_000_000));
+        assert!(!sell.honors_limit(29_999, 1_000_000_000));
+
+        assert!(!buy.honors_limit(0, 0));
+
+        //the limit times the amount overflows u128
+        let buy = Order { limit_price: u128::MAX, ..order(TradeSide::Buy) };
+        assert!(buy.honors_limit(u64::MAX, u64::MAX));
+        let sell = Order { limit_price: u128::MAX, ..order(TradeSide::Sell) };
+        assert!(!sell.honors_limit(u64::MAX, u64::MAX));
+    }
+}
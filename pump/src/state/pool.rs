@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+/// Constant product SOL/token pool a graduated bonding curve migrates into.
+/// SOL is held as lamports on the pool account itself, tokens in the pool's
+/// token account.
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub creator: Pubkey,
+
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+
+    /// LP tokens outstanding, including `locked_lp_supply`.
+    pub lp_supply: u64,
+    /// LP supply backing the liquidity the curve seeded the pool with. It is
+    /// never minted, so that liquidity can't be withdrawn.
+    pub locked_lp_supply: u64,
+
+    pub create_time: u64,
+    pub update_time: u64,
+}
+
+impl Pool {
+    pub const SEED_PREFIX: &'static [u8; 4] = b"pool";
+    pub const LP_MINT_SEED_PREFIX: &'static [u8; 12] = b"pool-lp-mint";
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+
+/// An allowlisted SPL mint that curves can be quoted in instead of native SOL.
+#[account]
+#[derive(InitSpace)]
+pub struct QuoteAsset {
+    pub mint: Pubkey,
+    pub enabled: bool,
+    /// Virtual reserves new constant product curves start with, in quote base units.
+    pub initial_virtual_reserves: u64,
+}
+
+impl QuoteAsset {
+    pub const SEED_PREFIX: &'static [u8; 11] = b"quote-asset";
+}
+
+/// Invite and fee profits of a user in one quote asset. It mirrors the
+/// profit fields of `UserInviteStats`, which only track lamports.
+#[account]
+#[derive(InitSpace)]
+pub struct QuoteFeeLedger {
+    pub key: Pubkey,
+    pub quote_mint: Pubkey,
+    pub profit_from_child: u64,
+    pub profit_to_parent: u64,
+    pub profit_claimable: u64,
+    pub profit_claim_accumulated: u64,
+}
+
+/// What one trade adds to a quote ledger.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct QuoteLedgerCredit {
+    pub profit_from_child: u64,
+    pub profit_to_parent: u64,
+    pub profit_claimable: u64,
+}
+
+impl QuoteLedgerCredit {
+    /// Merges the credits of ledgers owned by the same key, a creator trading
+    /// their own curve say, so each ledger is written once. Returns the index
+    /// of each key's first credit with the sum of all of its credits.
+    pub fn merge_by_key(credits: &[(Pubkey, QuoteLedgerCredit)]) -> Vec<(usize, QuoteLedgerCredit)> {
+        let mut merged: Vec<(usize, QuoteLedgerCredit)> = Vec::with_capacity(credits.len());
+        for (index, (key, credit)) in credits.iter().enumerate() {
+            match merged.iter_mut().find(|(first, _)| credits[*first].0 == *key) {
+                Some((_, total)) => {
+                    total.profit_from_child += credit.profit_from_child;
+                    total.profit_to_parent += credit.profit_to_parent;
+                    total.profit_claimable += credit.profit_claimable;
+                }
+                None => merged.push((index, *credit)),
+            }
+        }
+        merged
+    }
+}
+
+impl QuoteFeeLedger {
+    pub const SEED_PREFIX: &'static [u8; 16] = b"quote-fee-ledger";
+
+    /// Ledgers are created on the first trade that credits them.
+    pub fn init_if_needed(&mut self, key: Pubkey, quote_mint: Pubkey) {
+        if self.key == Pubkey::default() {
+            self.key = key;
+            self.quote_mint = quote_mint;
+        }
+    }
+
+    pub fn credit(&mut self, credit: &QuoteLedgerCredit) {
+        self.profit_from_child += credit.profit_from_child;
+        self.profit_to_parent += credit.profit_to_parent;
+        self.profit_claimable += credit.profit_claimable;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ledger_init_keeps_owner() {
+        let mut ledger = QuoteFeeLedger {
+            key: Pubkey::default(),
+            quote_mint: Pubkey::default(),
+            profit_from_child: 0,
+            profit_to_parent: 0,
+            profit_claimable: 0,
+            profit_claim_accumulated: 0,
+        };
+        let owner = Pubkey::new_unique();
+        let quote_mint = Pubkey::new_unique();
+
+        ledger.init_if_needed(owner, quote_mint);
+        ledger.init_if_needed(Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(ledger.key, owner);
+        assert_eq!(ledger.quote_mint, quote_mint);
+    }
+
+    #[test]
+    fn test_merge_credits_of_creator_trading_own_curve() {
+        let user = Pubkey::new_unique();
+        let parent = Pubkey::new_unique();
+        let fee_recipient = Pubkey::new_unique();
+        let credits = [
+            (user, QuoteLedgerCredit { profit_to_parent: 15, ..Default::default() }),
+            (parent, QuoteLedgerCredit { profit_from_child: 15, profit_claimable: 15, ..Default::default() }),
+            (fee_recipient, QuoteLedgerCredit { profit_claimable: 50, ..Default::default() }),
+            //the user is the curve's creator
+            (user, QuoteLedgerCredit { profit_claimable: 35, ..Default::default() }),
+        ];
+
+        let merged = QuoteLedgerCredit::merge_by_key(&credits);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(
+            merged[0],
+            (0, QuoteLedgerCredit { profit_from_child: 0, profit_to_parent: 15, profit_claimable: 35 })
+        );
+        assert_eq!(merged[1], (1, credits[1].1));
+        assert_eq!(merged[2], (2, credits[2].1));
+    }
+
+    #[test]
+    fn test_merge_credits_all_aliased() {
+        let key = Pubkey::new_unique();
+        let credit = QuoteLedgerCredit { profit_from_child: 1, profit_to_parent: 2, profit_claimable: 3 };
+
+        let merged = QuoteLedgerCredit::merge_by_key(&[(key, credit); 4]);
+        assert_eq!(
+            merged,
+            vec![(0, QuoteLedgerCredit { profit_from_child: 4, profit_to_parent: 8, profit_claimable: 12 })]
+        );
+    }
+}
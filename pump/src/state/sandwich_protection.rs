@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::UserCurveStats, CurveLaunchpadError};
+
+/// Opt-in defense against sandwiches within one slot: a wallet can't sell
+/// right after buying in the same slot, and the curve's spot price can't move
+/// more than `max_slot_price_impact_bps` away from where the slot started,
+/// 0 for no bound.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct SandwichProtection {
+    pub enabled: bool,
+    pub max_slot_price_impact_bps: u64,
+}
+
+/// How far `price_after` moved from `price_before`, in basis points of
+/// `price_before`, 0 when there was no price before.
+pub fn price_move_bps(price_before: u128, price_after: u128) -> u64 {
+    if price_before == 0 {
+        return 0;
+    }
+    let move_bps = price_before.abs_diff(price_after) * 10000 / price_before;
+    move_bps.min(u64::MAX as u128) as u64
+}
+
+/// Last slot a curve was traded in, and its spot price before that slot's first trade.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct SlotTrade {
+    pub slot: u64,
+    pub start_price: u128,
+}
+
+impl SlotTrade {
+    pub fn record(
+        &mut self,
+        protection: &SandwichProtection,
+        slot: u64,
+        price_before: u128,
+        price_after: u128,
+    ) -> Result<()> {
+        if !protection.enabled {
+            return Ok(());
+        }
+
+        if self.slot != slot {
+            self.slot = slot;
+            self.start_price = price_before;
+        }
+
+        if protection.max_slot_price_impact_bps > 0 && self.start_price > 0 {
+            require!(
+                price_move_bps(self.start_price, price_after) <= protection.max_slot_price_impact_bps,
+                CurveLaunchpadError::SlotPriceImpactExceeded
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl UserCurveStats {
+    /// Records the wallet's trade, rejecting a sell in the slot of its last buy.
+    pub fn record_trade(&mut self, protection: &SandwichProtection, slot: u64, is_buy: bool) -> Result<()> {
+        if !protection.enabled {
+            return Ok(());
+        }
+
+        require!(
+            is_buy || !(self.last_trade_is_buy && self.last_trade_slot == slot),
+            CurveLaunchpadError::SameSlotBuySell
+        );
+        self.last_trade_slot = slot;
+        self.last_trade_is_buy = is_buy;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROTECTION: SandwichProtection = SandwichProtection { enabled: true, max_slot_price_impact_bps: 500 };
+
+    #[test]
+    fn test_slot_price_impact() {
+        let mut slot_trade = SlotTrade::default();
+        slot_trade.record(&PROTECTION, 10, 1_000, 1_030).unwrap();
+        //the bound is against the slot's start, not the previous trade
+        slot_trade.record(&PROTECTION, 10, 1_030, 1_050).unwrap();
+        assert!(slot_trade.record(&PROTECTION, 10, 1_050, 1_051).is_err());
+        assert!(slot_trade.record(&PROTECTION, 10, 1_050, 949).is_err());
+
+        //a new slot starts from its own price
+        slot_trade.record(&PROTECTION, 11, 1_050, 1_100).unwrap();
+        assert_eq!(slot_trade.start_price, 1_050);
+
+        let mut slot_trade = SlotTrade::default();
+        slot_trade.record(&SandwichProtection::default(), 10, 1_000, 2_000).unwrap();
+        assert_eq!(slot_trade, SlotTrade::default());
+    }
+
+    #[test]
+    fn test_price_move_bps() {
+        assert_eq!(price_move_bps(1_000, 1_030), 300);
+        assert_eq!(price_move_bps(1_000, 950), 500);
+        assert_eq!(price_move_bps(0, 1_000), 0);
+    }
+
+    #[test]
+    fn test_same_slot_buy_sell() {
+        let mut stats = UserCurveStats {
+            user: Pubkey::default(),
+            bonding_curve: Pubkey::default(),
+            launch_sol_bought: 0,
+            launch_tokens_bought: 0,
+            allowlist_sol_bought: 0,
+            last_trade_slot: 0,
+            last_trade_is_buy: false,
+        };
+
+        stats.record_trade(&PROTECTION, 10, false).unwrap();
+        stats.record_trade(&PROTECTION, 10, true).unwrap();
+        stats.record_trade(&PROTECTION, 10, true).unwrap();
+        assert!(stats.record_trade(&PROTECTION, 10, false).is_err());
+        stats.record_trade(&PROTECTION, 11, false).unwrap();
+
+        stats.record_trade(&PROTECTION, 12, true).unwrap();
+        stats.record_trade(&SandwichProtection::default(), 12, false).unwrap();
+    }
+}
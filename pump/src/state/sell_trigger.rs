@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    amm::math::{self, ONE},
+    CurveLaunchpadError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum TriggerDirection {
+    /// Fires once the spot price falls to the trigger price or below.
+    StopLoss,
+    /// Fires once the spot price rises to the trigger price or above.
+    TakeProfit,
+}
+
+/// Stop-loss or take-profit on a SOL curve. `token_amount` tokens sit in
+/// `token_account`, owned by the trigger, until a keeper sells them through
+/// `execute_sell_trigger`. The fill has to pay at least what the tokens are
+/// worth at `trigger_price`, less the curve's fees and `max_slippage_bps`,
+/// so a stop-loss gapped far below its trigger waits rather than dumps.
+///
+/// The owner can cancel at any time. Once the curve completes anyone can,
+/// the tokens going back to the owner either way.
+#[account]
+#[derive(InitSpace)]
+pub struct SellTrigger {
+    pub owner: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub mint: Pubkey,
+    /// Picked by the owner, so one wallet can have several triggers on a curve.
+    pub id: u64,
+    pub direction: TriggerDirection,
+    pub token_amount: u64,
+    /// Lamports per token base unit, scaled by `amm::math::ONE`.
+    pub trigger_price: u128,
+    pub max_slippage_bps: u64,
+    pub keeper_fee: u64,
+    pub token_account: Pubkey,
+}
+
+impl SellTrigger {
+    pub const SEED_PREFIX: &'static [u8; 12] = b"sell-trigger";
+    /// A whole `u64` of lamports per token base unit.
+    pub const MAX_TRIGGER_PRICE: u128 = u64::MAX as u128 * ONE;
+
+    pub fn validate_terms(token_amount: u64, trigger_price: u128, max_slippage_bps: u64) -> Result<()> {
+        require!(
+            token_amount > 0
+                && trigger_price > 0
+                && trigger_price <= Self::MAX_TRIGGER_PRICE
+                && max_slippage_bps <= 10000,
+            CurveLaunchpadError::InvalidSellTrigger
+        );
+
+        Ok(())
+    }
+
+    pub fn is_triggered(&self, spot_price: u128) -> bool {
+        match self.direction {
+            TriggerDirection::StopLoss => spot_price <= self.trigger_price,
+            TriggerDirection::TakeProfit => spot_price >= self.trigger_price,
+        }
+    }
+
+    /// Least SOL the fill has to pay out, after fees charged at
+    /// `fee_basis_points`, for `net_token_amount` tokens reaching the curve.
+    /// Saturates, a trigger worth more than any fill can pay never fills.
+    pub fn min_sol_output(&self, net_token_amount: u64, fee_basis_points: u64) -> u64 {
+        let min_sol_output = math::mul_div(net_token_amount as u128, self.trigger_price, ONE)
+            .and_then(|trigger_value| {
+                math::mul_div(trigger_value, 10000u128.saturating_sub(fee_basis_points as u128), 10000)
+            })
+            .and_then(|after_fee| {
+                math::mul_div(after_fee, 10000u128.saturating_sub(self.max_slippage_bps as u128), 10000)
+            })
+            .unwrap_or(u128::MAX);
+        u64::try_from(min_sol_output).unwrap_or(u64::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(direction: TriggerDirection) -> SellTrigger {
+        SellTrigger {
+            owner: Pubkey::default(),
+            bonding_curve: Pubkey::default(),
+            mint: Pubkey::default(),
+            id: 0,
+            direction,
+            token_amount: 1_000_000_000,
+            //0.00003 lamports per base unit
+            trigger_price: 30_000_000,
+            max_slippage_bps: 500,
+            keeper_fee: 10_000,
+            token_account: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn test_is_triggered() {
+        let stop_loss = trigger(TriggerDirection::StopLoss);
+        assert!(stop_loss.is_triggered(30_000_000));
+        assert!(stop_loss.is_triggered(1));
+        assert!(!stop_loss.is_triggered(30_000_001));
+
+        let take_profit = trigger(TriggerDirection::TakeProfit);
+        assert!(take_profit.is_triggered(30_000_000));
+        assert!(take_profit.is_triggered(u128::MAX));
+        assert!(!take_profit.is_triggered(29_999_999));
+    }
+
+    #[test]
+    fn test_min_sol_output() {
+        let stop_loss = trigger(TriggerDirection::StopLoss);
+        //30_000 at the trigger price, less a 1% fee, less 5% slippage
+        assert_eq!(stop_loss.min_sol_output(1_000_000_000, 100), 28_215);
+
+        let no_slippage = SellTrigger { max_slippage_bps: 0, ..stop_loss };
+        assert_eq!(no_slippage.min_sol_output(1_000_000_000, 0), 30_000);
+
+        //the trigger value overflows u128
+        let above_market = SellTrigger { trigger_price: u128::MAX, ..stop_loss };
+        assert_eq!(above_market.min_sol_output(u64::MAX, 100), u64::MAX);
+
+        assert!(SellTrigger::validate_terms(1, 1, 10_001).is_err());
+        assert!(SellTrigger::validate_terms(0, 1, 0).is_err());
+        SellTrigger::validate_terms(1, SellTrigger::MAX_TRIGGER_PRICE, 0).unwrap();
+        assert!(SellTrigger::validate_terms(1, SellTrigger::MAX_TRIGGER_PRICE + 1, 0).is_err());
+    }
+}
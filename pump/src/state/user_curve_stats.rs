@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::LaunchProtection, CurveLaunchpadError};
+
+/// What one wallet has done on one bonding curve.
+#[account]
+#[derive(InitSpace)]
+pub struct UserCurveStats {
+    pub user: Pubkey,
+    pub bonding_curve: Pubkey,
+    /// Buys made inside the curve's launch protection window.
+    pub launch_sol_bought: u64,
+    pub launch_tokens_bought: u64,
+    /// SOL spent during the curve's allowlist phase, before fees.
+    pub allowlist_sol_bought: u64,
+    /// Last trade, tracked while the curve has sandwich protection.
+    pub last_trade_slot: u64,
+    pub last_trade_is_buy: bool,
+}
+
+impl UserCurveStats {
+    pub const SEED_PREFIX: &'static [u8; 16] = b"user-curve-stats";
+
+    /// Adds a buy made inside the launch window and checks the wallet is
+    /// still within the caps.
+    pub fn record_launch_buy(&mut self, protection: &LaunchProtection, sol_amount: u64, token_amount: u64) -> Result<()> {
+        self.launch_sol_bought = self.launch_sol_bought.saturating_add(sol_amount);
+        self.launch_tokens_bought = self.launch_tokens_bought.saturating_add(token_amount);
+        protection.check_buy(self.launch_sol_bought, self.launch_tokens_bought)
+    }
+
+    /// Adds a buy made during the allowlist phase and checks it stays within
+    /// the wallet's cap, 0 for no cap.
+    pub fn record_allowlist_buy(&mut self, max_buy_sol: u64, sol_amount: u64) -> Result<()> {
+        self.allowlist_sol_bought = self.allowlist_sol_bought.saturating_add(sol_amount);
+        require!(
+            max_buy_sol == 0 || self.allowlist_sol_bought <= max_buy_sol,
+            CurveLaunchpadError::AllowlistCapExceeded
+        );
+
+        Ok(())
+    }
+}
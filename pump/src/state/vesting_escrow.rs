@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::CurveLaunchpadError;
+
+/// Holds the creator's dev-buy tokens of one bonding curve and releases them
+/// on a schedule. The tokens sit in a token account owned by this PDA, so the
+/// creator cannot move or `sell` them before `claim_vested` pays them out.
+///
+/// Nothing unlocks before `cliff_seconds`, then the vested amount grows
+/// linearly from `start_time` until `duration_seconds` have passed. A pure
+/// cliff schedule sets both to the same value, a pure linear one has no cliff.
+///
+/// The protocol token allocation of a curve is held the same way, under
+/// `PROTOCOL_SEED_PREFIX`, with a duration of 0 releasing it at create time.
+#[account]
+#[derive(InitSpace)]
+pub struct VestingEscrow {
+    pub bonding_curve: Pubkey,
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub token_account: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    /// Create time of the curve, 0 until `create` funds the escrow.
+    pub start_time: u64,
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+}
+
+impl VestingEscrow {
+    pub const SEED_PREFIX: &'static [u8; 14] = b"vesting-escrow";
+    pub const PROTOCOL_SEED_PREFIX: &'static [u8; 19] = b"protocol-allocation";
+
+    pub fn validate_terms(cliff_seconds: u64, duration_seconds: u64) -> Result<()> {
+        require!(
+            duration_seconds > 0 && cliff_seconds <= duration_seconds,
+            CurveLaunchpadError::InvalidVestingTerms
+        );
+
+        Ok(())
+    }
+
+    pub fn vested_amount(&self, now: u64) -> u64 {
+        if self.start_time == 0 || now < self.start_time.saturating_add(self.cliff_seconds) {
+            return 0;
+        }
+
+        let elapsed = now.saturating_sub(self.start_time);
+        if elapsed >= self.duration_seconds {
+            return self.total_amount;
+        }
+        (self.total_amount as u128 * elapsed as u128 / self.duration_seconds as u128) as u64
+    }
+
+    pub fn claimable_amount(&self, now: u64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.claimed_amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escrow(cliff_seconds: u64, duration_seconds: u64) -> VestingEscrow {
+        VestingEscrow {
+            bonding_curve: Pubkey::default(),
+            mint: Pubkey::default(),
+            beneficiary: Pubkey::default(),
+            token_account: Pubkey::default(),
+            total_amount: 1_000_000,
+            claimed_amount: 0,
+            start_time: 1_000,
+            cliff_seconds,
+            duration_seconds,
+        }
+    }
+
+    #[test]
+    fn test_vested_amount() {
+        //linear with a cliff
+        let mut escrow = escrow(100, 1_000);
+        assert_eq!(escrow.vested_amount(999), 0);
+        assert_eq!(escrow.vested_amount(1_099), 0);
+        assert_eq!(escrow.vested_amount(1_100), 100_000);
+        assert_eq!(escrow.vested_amount(1_500), 500_000);
+        assert_eq!(escrow.vested_amount(2_000), 1_000_000);
+        assert_eq!(escrow.vested_amount(u64::MAX), 1_000_000);
+
+        escrow.claimed_amount = 500_000;
+        assert_eq!(escrow.claimable_amount(1_600), 100_000);
+        assert_eq!(escrow.claimable_amount(1_200), 0);
+
+        //pure cliff
+        let escrow = self::escrow(1_000, 1_000);
+        assert_eq!(escrow.vested_amount(1_999), 0);
+        assert_eq!(escrow.vested_amount(2_000), 1_000_000);
+
+        //not funded yet
+        let mut escrow = self::escrow(0, 1_000);
+        escrow.start_time = 0;
+        assert_eq!(escrow.vested_amount(5_000), 0);
+    }
+
+    #[test]
+    fn test_validate_terms() {
+        VestingEscrow::validate_terms(0, 1).unwrap();
+        VestingEscrow::validate_terms(10, 10).unwrap();
+        assert!(VestingEscrow::validate_terms(0, 0).is_err());
+        assert!(VestingEscrow::validate_terms(11, 10).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::mint_address;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Seeds handed to a worker at a time.
+const CHUNK_SIZE: u64 = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrindError {
+    EmptySuffix,
+    /// The suffix contains a character no base58 address can end with.
+    InvalidSuffix(char),
+    /// The pool was ground for another program or suffix.
+    PoolMismatch,
+}
+
+impl fmt::Display for GrindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GrindError::EmptySuffix => write!(f, "the suffix is empty"),
+            GrindError::InvalidSuffix(c) => write!(f, "'{}' is not a base58 character", c),
+            GrindError::PoolMismatch => write!(f, "the pool was ground for another program or suffix"),
+        }
+    }
+}
+
+impl std::error::Error for GrindError {}
+
+/// Suffix check on base58 addresses, matching the programs' on-chain check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuffixMatcher {
+    suffix: String,
+    case_sensitive: bool,
+}
+
+impl SuffixMatcher {
+    pub fn new(suffix: &str, case_sensitive: bool) -> Result<Self, GrindError> {
+        if suffix.is_empty() {
+            return Err(GrindError::EmptySuffix);
+        }
+
+        let suffix = if case_sensitive { suffix.to_string() } else { suffix.to_lowercase() };
+        for c in suffix.chars() {
+            let valid = if case_sensitive {
+                BASE58_ALPHABET.contains(c)
+            } else {
+                BASE58_ALPHABET.chars().any(|a| a.to_ascii_lowercase() == c)
+            };
+            if !valid {
+                return Err(GrindError::InvalidSuffix(c));
+            }
+        }
+
+        Ok(SuffixMatcher { suffix, case_sensitive })
+    }
+
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    pub fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    pub fn matches(&self, address: &Pubkey) -> bool {
+        let address = address.to_string();
+        if self.case_sensitive {
+            address.ends_with(&self.suffix)
+        } else {
+            address.to_lowercase().ends_with(&self.suffix)
+        }
+    }
+}
+
+/// A seed whose mint PDA carries the suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VanitySeed {
+    pub seed: u64,
+    pub mint: Pubkey,
+}
+
+/// Searches u64 seeds of one program for mint PDAs ending with a suffix,
+/// splitting the seed range across threads.
+#[derive(Debug, Clone)]
+pub struct Grinder {
+    program_id: Pubkey,
+    matcher: SuffixMatcher,
+    threads: usize,
+}
+
+impl Grinder {
+    pub fn new(program_id: Pubkey, matcher: SuffixMatcher) -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Grinder { program_id, matcher, threads }
+    }
+
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    pub fn matcher(&self) -> &SuffixMatcher {
+        &self.matcher
+    }
+
+    /// Grinds upward from `start` until at least `count` seeds are found,
+    /// leaving out those `skip` rejects. Returns the seeds in order and the
+    /// seed to resume from, past every seed that was searched.
+    pub fn grind<F>(&self, start: u64, count: usize, skip: F) -> (Vec<VanitySeed>, u64)
+    where
+        F: Fn(&VanitySeed) -> bool + Sync,
+    {
+        let next_chunk = AtomicU64::new(start);
+        let done = AtomicBool::new(count == 0);
+        let found = Mutex::new(Vec::new());
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads {
+                scope.spawn(|| {
+                    while !done.load(Ordering::Relaxed) {
+                        let chunk_start = next_chunk.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+                        if chunk_start > u64::MAX - CHUNK_SIZE {
+                            break;
+                        }
+
+                        for seed in chunk_start..chunk_start + CHUNK_SIZE {
+                            let mint = mint_address(&self.program_id, seed);
+                            if !self.matcher.matches(&mint) {
+                                continue;
+                            }
+                            let vanity_seed = VanitySeed { seed, mint };
+                            if skip(&vanity_seed) {
+                                continue;
+                            }
+
+                            let mut found = found.lock().unwrap();
+                            found.push(vanity_seed);
+                            if found.len() >= count {
+                                done.store(true, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut found = found.into_inner().unwrap();
+        found.sort_by_key(|vanity_seed| vanity_seed.seed);
+        (found, next_chunk.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CURVE_LAUNCHPAD_ID;
+
+    #[test]
+    fn test_suffix_matcher() {
+        let matcher = SuffixMatcher::new("MEME", false).unwrap();
+        assert_eq!(matcher.suffix(), "meme");
+
+        let address = CURVE_LAUNCHPAD_ID; //ends with "jpmg"
+        assert!(SuffixMatcher::new("JPMG", false).unwrap().matches(&address));
+        assert!(SuffixMatcher::new("jpmg", true).unwrap().matches(&address));
+        assert!(!SuffixMatcher::new("JPMG", true).unwrap().matches(&address));
+
+        assert_eq!(SuffixMatcher::new("", false), Err(GrindError::EmptySuffix));
+        assert_eq!(SuffixMatcher::new("0", false), Err(GrindError::InvalidSuffix('0')));
+        assert_eq!(SuffixMatcher::new("l", true), Err(GrindError::InvalidSuffix('l')));
+        //'L' exists, so a case-insensitive 'l' can still match
+        assert!(SuffixMatcher::new("l", false).is_ok());
+    }
+
+    #[test]
+    fn test_grind() {
+        let matcher = SuffixMatcher::new("m", false).unwrap();
+        let grinder = Grinder::new(CURVE_LAUNCHPAD_ID, matcher.clone()).threads(4);
+
+        let (seeds, next_seed) = grinder.grind(0, 5, |_| false);
+        assert!(seeds.len() >= 5);
+        assert!(seeds.windows(2).all(|pair| pair[0].seed < pair[1].seed));
+        for vanity_seed in &seeds {
+            assert!(vanity_seed.seed < next_seed);
+            assert_eq!(vanity_seed.mint, mint_address(&CURVE_LAUNCHPAD_ID, vanity_seed.seed));
+            assert!(matcher.matches(&vanity_seed.mint));
+        }
+
+        //skipped seeds are never returned
+        let first = seeds[0];
+        let (seeds, _) = grinder.grind(0, 5, |vanity_seed| vanity_seed.seed == first.seed);
+        assert!(seeds.iter().all(|vanity_seed| vanity_seed.seed != first.seed));
+    }
+}
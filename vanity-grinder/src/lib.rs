@@ -0,0 +1,25 @@
+//! Offline grinder for the mint seeds `curve_launchpad::create` and
+//! `memecoin::mint_memecoin` accept. Both derive the mint as the PDA of
+//! `seed.to_le_bytes()` and reject it unless its base58 address ends with the
+//! required suffix.
+
+use solana_program::{pubkey, pubkey::Pubkey};
+
+pub mod grinder;
+pub mod pool;
+
+pub use grinder::*;
+pub use pool::*;
+
+/// `curve_launchpad` program ID (pump).
+pub const CURVE_LAUNCHPAD_ID: Pubkey = pubkey!("8VmiQfMyGSeksAkHLuXYhpXccsqhkPavH26g1BTFjpmg");
+/// `memecoin` program ID (presale).
+pub const MEMECOIN_ID: Pubkey = pubkey!("4fTeRu8jGLDErJnPv9Cx9cdpRt9sm2vSvF41oTQ6CbED");
+
+/// Suffix both programs require by default, compared case-insensitively.
+pub const DEFAULT_SUFFIX: &str = "meme";
+
+/// Mint PDA the programs derive from `seed`.
+pub fn mint_address(program_id: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[&seed.to_le_bytes()], program_id).0
+}
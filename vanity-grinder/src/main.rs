@@ -0,0 +1,190 @@
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use solana_program::pubkey::Pubkey;
+use vanity_grinder::{
+    with_locked_pool, Grinder, SeedPool, SuffixMatcher, CURVE_LAUNCHPAD_ID, DEFAULT_SUFFIX, MEMECOIN_ID,
+};
+
+const USAGE: &str = "usage:
+  vanity-grinder grind [--program pump|presale|<pubkey>] [--suffix meme] [--case-sensitive]
+                       [--count 1] [--start 0] [--threads n]
+  vanity-grinder pool refill --pool FILE --count N [--program ...] [--suffix ...] [--case-sensitive]
+                       [--threads n] [--existing FILE]
+  vanity-grinder pool reserve --pool FILE --creator PUBKEY
+  vanity-grinder pool release --pool FILE --seed N
+  vanity-grinder pool consume --pool FILE --seed N
+  vanity-grinder pool prune --pool FILE --existing FILE
+  vanity-grinder pool expire --pool FILE --older-than SECONDS
+
+--existing is a file of mint addresses already created on chain, one per line.";
+
+#[derive(Default)]
+struct Args {
+    program: Option<String>,
+    suffix: Option<String>,
+    case_sensitive: bool,
+    count: Option<usize>,
+    start: Option<u64>,
+    threads: Option<usize>,
+    pool: Option<PathBuf>,
+    existing: Option<PathBuf>,
+    creator: Option<String>,
+    seed: Option<u64>,
+    older_than: Option<u64>,
+}
+
+fn main() {
+    if let Err(err) = run(env::args().skip(1).collect()) {
+        eprintln!("error: {}", err);
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    }
+}
+
+fn run(argv: Vec<String>) -> Result<(), String> {
+    let (command, rest) = argv.split_first().ok_or("missing command")?;
+    match command.as_str() {
+        "grind" => grind(parse_args(rest)?),
+        "pool" => {
+            let (action, rest) = rest.split_first().ok_or("missing pool action")?;
+            pool(action, parse_args(rest)?)
+        }
+        _ => Err(format!("unknown command {}", command)),
+    }
+}
+
+fn parse_args(argv: &[String]) -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = argv.iter();
+    while let Some(flag) = iter.next() {
+        if flag == "--case-sensitive" {
+            args.case_sensitive = true;
+            continue;
+        }
+
+        let value = iter.next().ok_or(format!("{} needs a value", flag))?;
+        match flag.as_str() {
+            "--program" => args.program = Some(value.clone()),
+            "--suffix" => args.suffix = Some(value.clone()),
+            "--count" => args.count = Some(parse_number(flag, value)?),
+            "--start" => args.start = Some(parse_number(flag, value)?),
+            "--threads" => args.threads = Some(parse_number(flag, value)?),
+            "--pool" => args.pool = Some(PathBuf::from(value)),
+            "--existing" => args.existing = Some(PathBuf::from(value)),
+            "--creator" => args.creator = Some(value.clone()),
+            "--seed" => args.seed = Some(parse_number(flag, value)?),
+            "--older-than" => args.older_than = Some(parse_number(flag, value)?),
+            _ => return Err(format!("unknown flag {}", flag)),
+        }
+    }
+    Ok(args)
+}
+
+fn parse_number<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value {} for {}", value, flag))
+}
+
+fn grinder(args: &Args) -> Result<Grinder, String> {
+    let program_id = match args.program.as_deref() {
+        None | Some("pump") => CURVE_LAUNCHPAD_ID,
+        Some("presale") => MEMECOIN_ID,
+        Some(program) => Pubkey::from_str(program).map_err(|_| format!("invalid program {}", program))?,
+    };
+    let suffix = args.suffix.as_deref().unwrap_or(DEFAULT_SUFFIX);
+    let matcher = SuffixMatcher::new(suffix, args.case_sensitive).map_err(|err| err.to_string())?;
+
+    let grinder = Grinder::new(program_id, matcher);
+    Ok(match args.threads {
+        Some(threads) => grinder.threads(threads),
+        None => grinder,
+    })
+}
+
+fn existing_mints(path: Option<&Path>) -> Result<HashSet<Pubkey>, String> {
+    let Some(path) = path else {
+        return Ok(HashSet::new());
+    };
+    let data = fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    data.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| Pubkey::from_str(line).map_err(|_| format!("invalid mint {}", line)))
+        .collect()
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+fn grind(args: Args) -> Result<(), String> {
+    let grinder = grinder(&args)?;
+    let (seeds, next_seed) = grinder.grind(args.start.unwrap_or(0), args.count.unwrap_or(1), |_| false);
+    for vanity_seed in seeds {
+        println!("{} {}", vanity_seed.seed, vanity_seed.mint);
+    }
+    eprintln!("next seed: {}", next_seed);
+    Ok(())
+}
+
+fn pool(action: &str, args: Args) -> Result<(), String> {
+    let path = args.pool.clone().ok_or("--pool is required")?;
+    let io_err = |err: std::io::Error| format!("{}: {}", path.display(), err);
+
+    match action {
+        "refill" => {
+            let grinder = grinder(&args)?;
+            let target = args.count.ok_or("--count is required")?;
+            let existing = existing_mints(args.existing.as_deref())?;
+            if !path.exists() {
+                SeedPool::new(&grinder).save(&path).map_err(io_err)?;
+            }
+
+            let added = with_locked_pool(&path, |pool| {
+                pool.prune(|mint| existing.contains(mint));
+                pool.refill(&grinder, target, |mint| existing.contains(mint))
+            })
+            .map_err(io_err)?
+            .map_err(|err| err.to_string())?;
+            println!("added {} seeds", added);
+        }
+        "reserve" => {
+            let creator = args.creator.ok_or("--creator is required")?;
+            let vanity_seed = with_locked_pool(&path, |pool| pool.reserve(&creator, now()))
+                .map_err(io_err)?
+                .ok_or("the pool is empty, refill it")?;
+            println!("{} {}", vanity_seed.seed, vanity_seed.mint);
+        }
+        "release" | "consume" => {
+            let seed = args.seed.ok_or("--seed is required")?;
+            let changed = with_locked_pool(&path, |pool| match action {
+                "release" => pool.release(seed),
+                _ => pool.consume(seed),
+            })
+            .map_err(io_err)?;
+            if !changed {
+                return Err(format!("seed {} is not {} in the pool", seed, if action == "release" { "reserved" } else { "listed" }));
+            }
+        }
+        "prune" => {
+            let existing = existing_mints(Some(args.existing.as_deref().ok_or("--existing is required")?))?;
+            let pruned = with_locked_pool(&path, |pool| pool.prune(|mint| existing.contains(mint))).map_err(io_err)?;
+            println!("pruned {} seeds", pruned);
+        }
+        "expire" => {
+            let older_than = args.older_than.ok_or("--older-than is required")?;
+            let before = now().saturating_sub(older_than);
+            let expired = with_locked_pool(&path, |pool| pool.expire_reservations(before)).map_err(io_err)?;
+            println!("expired {} reservations", expired);
+        }
+        _ => return Err(format!("unknown pool action {}", action)),
+    }
+
+    Ok(())
+}
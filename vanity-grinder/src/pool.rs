@@ -0,0 +1,260 @@
+use std::{
+    fs::{self, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::{GrindError, Grinder, VanitySeed};
+
+/// How long `with_locked_pool` waits for another process to release the pool.
+const LOCK_ATTEMPTS: u32 = 100;
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PooledSeed {
+    #[serde(flatten)]
+    pub vanity_seed: VanitySeed,
+    /// Creator holding the seed, `None` while it is free.
+    pub reserved_by: Option<String>,
+    /// Unix timestamp of the reservation.
+    pub reserved_at: Option<u64>,
+}
+
+/// Pre-ground seeds for one program and suffix. Creators reserve a seed
+/// before sending their create transaction, so no two of them race on the
+/// same mint, and consume it once the mint exists.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeedPool {
+    pub program_id: Pubkey,
+    pub suffix: String,
+    pub case_sensitive: bool,
+    /// Grinding resumes here, every seed below it was searched.
+    pub next_seed: u64,
+    pub seeds: Vec<PooledSeed>,
+}
+
+impl SeedPool {
+    pub fn new(grinder: &Grinder) -> Self {
+        SeedPool {
+            program_id: *grinder.program_id(),
+            suffix: grinder.matcher().suffix().to_string(),
+            case_sensitive: grinder.matcher().case_sensitive(),
+            next_seed: 0,
+            seeds: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let data = fs::read(path)?;
+        serde_json::from_slice(&data).map_err(io::Error::from)
+    }
+
+    /// Writes the pool through a temporary file so a crash never leaves it half written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(tmp_path, path)
+    }
+
+    /// Seeds free to reserve.
+    pub fn available(&self) -> usize {
+        self.seeds.iter().filter(|seed| seed.reserved_by.is_none()).count()
+    }
+
+    /// Grinds until `target` seeds are free, skipping seeds whose mint
+    /// `exists` reports as already created.
+    pub fn refill<F>(&mut self, grinder: &Grinder, target: usize, exists: F) -> Result<usize, GrindError>
+    where
+        F: Fn(&Pubkey) -> bool + Sync,
+    {
+        let matcher = grinder.matcher();
+        if *grinder.program_id() != self.program_id
+            || matcher.suffix() != self.suffix
+            || matcher.case_sensitive() != self.case_sensitive
+        {
+            return Err(GrindError::PoolMismatch);
+        }
+
+        let missing = target.saturating_sub(self.available());
+        if missing == 0 {
+            return Ok(0);
+        }
+
+        let (found, next_seed) = grinder.grind(self.next_seed, missing, |vanity_seed| exists(&vanity_seed.mint));
+        self.next_seed = next_seed;
+        let added = found.len();
+        self.seeds.extend(found.into_iter().map(|vanity_seed| PooledSeed {
+            vanity_seed,
+            reserved_by: None,
+            reserved_at: None,
+        }));
+
+        Ok(added)
+    }
+
+    /// Drops free seeds whose mint has been created since they were ground.
+    pub fn prune<F>(&mut self, exists: F) -> usize
+    where
+        F: Fn(&Pubkey) -> bool,
+    {
+        let len = self.seeds.len();
+        self.seeds
+            .retain(|seed| seed.reserved_by.is_some() || !exists(&seed.vanity_seed.mint));
+        len - self.seeds.len()
+    }
+
+    /// Hands the lowest free seed to `creator`.
+    pub fn reserve(&mut self, creator: &str, now: u64) -> Option<VanitySeed> {
+        let seed = self.seeds.iter_mut().find(|seed| seed.reserved_by.is_none())?;
+        seed.reserved_by = Some(creator.to_string());
+        seed.reserved_at = Some(now);
+        Some(seed.vanity_seed)
+    }
+
+    /// Frees a reserved seed that was not used.
+    pub fn release(&mut self, seed: u64) -> bool {
+        match self.seeds.iter_mut().find(|pooled| pooled.vanity_seed.seed == seed) {
+            Some(pooled) if pooled.reserved_by.is_some() => {
+                pooled.reserved_by = None;
+                pooled.reserved_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Frees reservations made before `before`, for creators that never came back.
+    pub fn expire_reservations(&mut self, before: u64) -> usize {
+        let mut expired = 0;
+        for pooled in &mut self.seeds {
+            if pooled.reserved_at.is_some_and(|reserved_at| reserved_at < before) {
+                pooled.reserved_by = None;
+                pooled.reserved_at = None;
+                expired += 1;
+            }
+        }
+        expired
+    }
+
+    /// Removes a seed for good once its mint is created.
+    pub fn consume(&mut self, seed: u64) -> bool {
+        let len = self.seeds.len();
+        self.seeds.retain(|pooled| pooled.vanity_seed.seed != seed);
+        self.seeds.len() != len
+    }
+}
+
+/// Loads the pool at `path`, applies `f` and saves it back while holding a
+/// lock file, so concurrent processes never hand out the same seed.
+pub fn with_locked_pool<T, F>(path: &Path, f: F) -> io::Result<T>
+where
+    F: FnOnce(&mut SeedPool) -> T,
+{
+    let lock_path = lock_path(path);
+    let mut attempts = 0;
+    while let Err(err) = OpenOptions::new().write(true).create_new(true).open(&lock_path) {
+        attempts += 1;
+        if err.kind() != io::ErrorKind::AlreadyExists || attempts >= LOCK_ATTEMPTS {
+            return Err(err);
+        }
+        thread::sleep(LOCK_RETRY_DELAY);
+    }
+
+    let result = SeedPool::load(path).and_then(|mut pool| {
+        let value = f(&mut pool);
+        pool.save(path)?;
+        Ok(value)
+    });
+
+    fs::remove_file(&lock_path)?;
+    result
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    PathBuf::from(lock_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{SuffixMatcher, CURVE_LAUNCHPAD_ID, MEMECOIN_ID};
+
+    fn grinder() -> Grinder {
+        Grinder::new(CURVE_LAUNCHPAD_ID, SuffixMatcher::new("m", false).unwrap()).threads(2)
+    }
+
+    #[test]
+    fn test_refill_skips_existing_mints() {
+        let grinder = grinder();
+        let (ground, _) = grinder.grind(0, 3, |_| false);
+        let existing: HashSet<Pubkey> = ground.iter().map(|vanity_seed| vanity_seed.mint).collect();
+
+        let mut pool = SeedPool::new(&grinder);
+        pool.refill(&grinder, 4, |mint| existing.contains(mint)).unwrap();
+        assert!(pool.available() >= 4);
+        assert!(pool.seeds.iter().all(|pooled| !existing.contains(&pooled.vanity_seed.mint)));
+
+        //a full pool grinds nothing
+        let next_seed = pool.next_seed;
+        assert_eq!(pool.refill(&grinder, 4, |_| false).unwrap(), 0);
+        assert_eq!(pool.next_seed, next_seed);
+
+        let other = Grinder::new(MEMECOIN_ID, SuffixMatcher::new("m", false).unwrap());
+        assert_eq!(pool.refill(&other, 8, |_| false), Err(GrindError::PoolMismatch));
+    }
+
+    #[test]
+    fn test_reservations() {
+        let grinder = grinder();
+        let mut pool = SeedPool::new(&grinder);
+        pool.refill(&grinder, 2, |_| false).unwrap();
+        pool.seeds.truncate(2);
+
+        let first = pool.reserve("alice", 10).unwrap();
+        let second = pool.reserve("bob", 20).unwrap();
+        assert_ne!(first.seed, second.seed);
+        assert_eq!(pool.reserve("carol", 30), None);
+
+        assert!(pool.release(first.seed));
+        assert!(!pool.release(first.seed));
+        assert_eq!(pool.reserve("carol", 30), Some(first));
+
+        //reserved seeds survive pruning
+        assert_eq!(pool.prune(|_| true), 0);
+
+        assert_eq!(pool.expire_reservations(25), 1);
+        assert!(pool.consume(first.seed));
+        assert!(!pool.consume(first.seed));
+        assert_eq!(pool.available(), 1);
+        assert_eq!(pool.prune(|mint| *mint == second.mint), 1);
+        assert_eq!(pool.available(), 0);
+    }
+
+    #[test]
+    fn test_locked_pool_round_trip() {
+        let grinder = grinder();
+        let mut pool = SeedPool::new(&grinder);
+        pool.refill(&grinder, 1, |_| false).unwrap();
+
+        let path = std::env::temp_dir().join(format!("vanity-grinder-{}.json", std::process::id()));
+        pool.save(&path).unwrap();
+
+        let reserved = with_locked_pool(&path, |pool| pool.reserve("alice", 1)).unwrap();
+        assert!(reserved.is_some());
+        assert!(!lock_path(&path).exists());
+
+        let loaded = SeedPool::load(&path).unwrap();
+        assert_eq!(loaded.available(), pool.available() - 1);
+
+        fs::remove_file(&path).unwrap();
+    }
+}